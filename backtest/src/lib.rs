@@ -0,0 +1,13 @@
+//! Off-chain backtester of `Chamber` strategy.
+//!
+//! Replays historical Pyth prices and AMM pool reserves through the same deposit, borrow
+//! and NAV math as program, while modelling borrow interest, farm rewards, impermanent loss
+//! and liquidations, which happen outside of chamber.
+
+mod report;
+mod series;
+mod simulation;
+
+pub use report::*;
+pub use series::*;
+pub use simulation::*;
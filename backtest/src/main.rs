@@ -0,0 +1,90 @@
+//! Backtester of `Chamber` strategy over historical prices and pool reserves.
+
+use anyhow::Result;
+use chamber_backtest::{read_pool_file, read_prices_file, run, BacktestConfig, Report};
+use clap::Parser;
+use std::{fs::File, io, path::PathBuf};
+
+#[derive(Parser)]
+#[clap(name = "chamber-backtest", version, about)]
+struct Cli {
+    /// CSV with `timestamp,base_price,quote_price` Pyth prices.
+    #[clap(long)]
+    prices: PathBuf,
+
+    /// CSV with `timestamp,base_reserve,quote_reserve,lp_supply` AMM pool state.
+    #[clap(long)]
+    pool: PathBuf,
+
+    /// Output CSV, prints to stdout if missing.
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+
+    #[clap(long, default_value = "9")]
+    base_decimals: u8,
+
+    #[clap(long, default_value = "6")]
+    quote_decimals: u8,
+
+    /// Initial deposit in base token units.
+    #[clap(long, default_value = "0")]
+    base_amount: u64,
+
+    /// Initial deposit in quote token units.
+    #[clap(long, default_value = "1000000000")]
+    quote_amount: u64,
+
+    #[clap(long, default_value = "1000")]
+    base_borrow_apr_bps: u16,
+
+    #[clap(long, default_value = "1000")]
+    quote_borrow_apr_bps: u16,
+
+    #[clap(long, default_value = "3000")]
+    reward_apr_bps: u16,
+
+    #[clap(long, default_value = "8500")]
+    liquidation_ltv_bps: u64,
+
+    #[clap(long, default_value = "500")]
+    liquidation_penalty_bps: u64,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let prices = read_prices_file(&cli.prices)?;
+    let pool = read_pool_file(&cli.pool)?;
+
+    let snapshots = run(
+        BacktestConfig {
+            base_decimals: cli.base_decimals,
+            quote_decimals: cli.quote_decimals,
+            base_amount: cli.base_amount,
+            quote_amount: cli.quote_amount,
+            base_borrow_apr_bps: cli.base_borrow_apr_bps,
+            quote_borrow_apr_bps: cli.quote_borrow_apr_bps,
+            reward_apr_bps: cli.reward_apr_bps,
+            liquidation_ltv_bps: cli.liquidation_ltv_bps,
+            liquidation_penalty_bps: cli.liquidation_penalty_bps,
+        },
+        &prices,
+        &pool,
+    )?;
+    let report = Report::new(&snapshots)?;
+
+    match cli.output {
+        Some(path) => report.write_csv(File::create(path)?)?,
+        None => report.write_csv(io::stdout())?,
+    }
+
+    let summary = &report.summary;
+    eprintln!("Initial NAV: {:.6}", summary.initial_nav);
+    eprintln!("Final NAV: {:.6}", summary.final_nav);
+    eprintln!("PnL: {:.6}", summary.pnl);
+    eprintln!("Max drawdown: {} bps", summary.max_drawdown_bps);
+    eprintln!("Max hedge drift: {} bps", summary.max_drift_bps);
+    eprintln!("Liquidated legs: {}", summary.liquidations);
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! Module summarize simulation into PnL, drawdown and hedge drift.
+
+use crate::Snapshot;
+use anyhow::{anyhow, Result};
+use cetra_chamber::math::BPS_DENOMINATOR;
+use std::io::Write;
+use tulipv2_sdk_common::math::{
+    common::{TryDiv, TryMul, TrySub},
+    decimal::Decimal,
+};
+
+/// Single output row, values are in quote token price units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportRow {
+    pub timestamp: i64,
+    pub nav: f64,
+    pub pnl: f64,
+    pub drawdown_bps: u64,
+    pub drift_bps: u64,
+    pub ltv_bps: [u64; 2],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub initial_nav: f64,
+    pub final_nav: f64,
+    pub pnl: f64,
+    pub max_drawdown_bps: u64,
+    pub max_drift_bps: u64,
+    pub liquidations: usize,
+}
+
+pub struct Report {
+    pub rows: Vec<ReportRow>,
+    pub summary: Summary,
+}
+
+impl Report {
+    pub fn new(snapshots: &[Snapshot]) -> Result<Self> {
+        let first = snapshots
+            .first()
+            .ok_or_else(|| anyhow!("no snapshots to report"))?;
+        let last = snapshots.last().unwrap();
+
+        let initial_nav = to_f64(first.nav)?;
+        let mut peak = Decimal::zero();
+        let mut rows = Vec::with_capacity(snapshots.len());
+
+        for snapshot in snapshots {
+            if snapshot.nav > peak {
+                peak = snapshot.nav;
+            }
+
+            let drawdown_bps = if peak == Decimal::zero() {
+                0
+            } else {
+                peak.try_sub(snapshot.nav)?
+                    .try_mul(BPS_DENOMINATOR)?
+                    .try_div(peak)?
+                    .try_floor_u64()?
+            };
+
+            let nav = to_f64(snapshot.nav)?;
+            rows.push(ReportRow {
+                timestamp: snapshot.timestamp,
+                nav,
+                pnl: nav - initial_nav,
+                drawdown_bps,
+                drift_bps: snapshot.drift_bps,
+                ltv_bps: snapshot.ltv_bps,
+            });
+        }
+
+        let final_nav = to_f64(last.nav)?;
+        let summary = Summary {
+            initial_nav,
+            final_nav,
+            pnl: final_nav - initial_nav,
+            max_drawdown_bps: rows.iter().map(|row| row.drawdown_bps).max().unwrap_or(0),
+            max_drift_bps: rows.iter().map(|row| row.drift_bps).max().unwrap_or(0),
+            liquidations: last
+                .liquidated
+                .iter()
+                .filter(|liquidated| **liquidated)
+                .count(),
+        };
+
+        Ok(Self { rows, summary })
+    }
+
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+
+        writer.write_record(&[
+            "timestamp",
+            "nav",
+            "pnl",
+            "drawdown_bps",
+            "drift_bps",
+            "ltv_bps_0",
+            "ltv_bps_1",
+        ])?;
+
+        for row in self.rows.iter() {
+            writer.write_record(&[
+                row.timestamp.to_string(),
+                format!("{:.6}", row.nav),
+                format!("{:.6}", row.pnl),
+                row.drawdown_bps.to_string(),
+                row.drift_bps.to_string(),
+                row.ltv_bps[0].to_string(),
+                row.ltv_bps[1].to_string(),
+            ])?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+fn to_f64(value: Decimal) -> Result<f64> {
+    Ok(value.to_scaled_val()? as f64 / 1e18)
+}
@@ -0,0 +1,112 @@
+//! Module read historical price and pool series from CSV files.
+
+use anyhow::{anyhow, bail, Result};
+use serde::Deserialize;
+use std::{fs::File, io::Read, path::Path};
+use tulipv2_sdk_common::math::decimal::Decimal;
+
+/// Scale of `Decimal`, 18 fractional digits.
+const DECIMAL_SCALE: usize = 18;
+
+/// Pyth prices of pair tokens at `timestamp`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceRow {
+    pub timestamp: i64,
+    pub base_price: Decimal,
+    pub quote_price: Decimal,
+}
+
+/// AMM pool reserves and LP supply at `timestamp`, in token units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct PoolRow {
+    pub timestamp: i64,
+    pub base_reserve: u64,
+    pub quote_reserve: u64,
+    pub lp_supply: u64,
+}
+
+#[derive(Deserialize)]
+struct RawPriceRow {
+    timestamp: i64,
+    base_price: String,
+    quote_price: String,
+}
+
+/// Parse decimal string, e.g. `40.125`, without going through floats.
+pub fn parse_decimal(value: &str) -> Result<Decimal> {
+    let value = value.trim();
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+
+    if integer.is_empty() && fraction.is_empty() {
+        bail!("empty decimal");
+    }
+    if fraction.len() > DECIMAL_SCALE {
+        bail!(
+            "{} has more than {} fractional digits",
+            value,
+            DECIMAL_SCALE
+        );
+    }
+
+    let digits = format!("{}{:0<width$}", integer, fraction, width = DECIMAL_SCALE);
+    let scaled_val = digits
+        .parse::<u128>()
+        .map_err(|e| anyhow!("invalid decimal {}: {}", value, e))?;
+
+    Ok(Decimal::from_scaled_val(scaled_val))
+}
+
+/// Read `timestamp,base_price,quote_price` rows sorted by timestamp.
+pub fn read_prices<R: Read>(reader: R) -> Result<Vec<PriceRow>> {
+    let mut rows = vec![];
+
+    for record in csv::Reader::from_reader(reader).deserialize() {
+        let record: RawPriceRow = record?;
+
+        rows.push(PriceRow {
+            timestamp: record.timestamp,
+            base_price: parse_decimal(&record.base_price)?,
+            quote_price: parse_decimal(&record.quote_price)?,
+        });
+    }
+
+    check_sorted(rows.iter().map(|row| row.timestamp))?;
+
+    Ok(rows)
+}
+
+/// Read `timestamp,base_reserve,quote_reserve,lp_supply` rows sorted by timestamp.
+pub fn read_pool<R: Read>(reader: R) -> Result<Vec<PoolRow>> {
+    let rows = csv::Reader::from_reader(reader)
+        .deserialize()
+        .collect::<std::result::Result<Vec<PoolRow>, _>>()?;
+
+    check_sorted(rows.iter().map(|row| row.timestamp))?;
+
+    Ok(rows)
+}
+
+pub fn read_prices_file(path: &Path) -> Result<Vec<PriceRow>> {
+    read_prices(File::open(path)?)
+}
+
+pub fn read_pool_file(path: &Path) -> Result<Vec<PoolRow>> {
+    read_pool(File::open(path)?)
+}
+
+fn check_sorted(timestamps: impl Iterator<Item = i64>) -> Result<()> {
+    let mut last = i64::MIN;
+
+    for timestamp in timestamps {
+        if timestamp <= last {
+            bail!(
+                "series must be sorted by timestamp, {} after {}",
+                timestamp,
+                last
+            );
+        }
+        last = timestamp;
+    }
+
+    Ok(())
+}
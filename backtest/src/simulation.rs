@@ -0,0 +1,298 @@
+//! Module simulate chamber legs over historical series.
+
+use crate::{PoolRow, PriceRow};
+use anyhow::{anyhow, bail, Result};
+use cetra_chamber::{
+    math::{self, BPS_DENOMINATOR},
+    state::TulipObligation,
+};
+use tulipv2_sdk_common::math::{
+    common::{TryAdd, TryDiv, TryMul, TrySub},
+    decimal::Decimal,
+};
+
+#[derive(Debug, Clone)]
+pub struct BacktestConfig {
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+
+    /// Initial chamber deposit.
+    pub base_amount: u64,
+    pub quote_amount: u64,
+
+    /// Yearly borrow rates of lending reserves.
+    pub base_borrow_apr_bps: u16,
+    pub quote_borrow_apr_bps: u16,
+
+    /// Yearly farm rewards rate on staked liquidity value, paid in quote token.
+    pub reward_apr_bps: u16,
+
+    /// Obligation LTV at which leg is liquidated.
+    pub liquidation_ltv_bps: u64,
+
+    /// Liquidator bonus, charged on debt value of liquidated leg.
+    pub liquidation_penalty_bps: u64,
+}
+
+/// State of single Tulip obligation.
+#[derive(Debug, Clone, Default)]
+pub struct LegState {
+    /// Staked LP tokens.
+    pub lp_amount: u64,
+    pub base_debt: u64,
+    pub quote_debt: u64,
+
+    /// Harvested farm rewards.
+    pub reward_quote_amount: u64,
+
+    /// Leg was liquidated, its remaining equity is kept as `liquidated_value`.
+    pub liquidated: bool,
+    pub liquidated_value: Decimal,
+
+    base_interest_remainder: u128,
+    quote_interest_remainder: u128,
+    reward_remainder: u128,
+}
+
+/// Chamber state at single point of series.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub timestamp: i64,
+    pub base_price: Decimal,
+    pub quote_price: Decimal,
+
+    /// Obligations equity, same as `ProcessChamberEpoch` NAV without idle funds.
+    pub nav: Decimal,
+
+    /// Drift of net base exposure in basis points of base liquidity.
+    pub drift_bps: u64,
+    pub ltv_bps: [u64; 2],
+    pub liquidated: [bool; 2],
+}
+
+pub struct Simulation {
+    pub config: BacktestConfig,
+    pub legs: [LegState; 2],
+    pub timestamp: i64,
+}
+
+impl Simulation {
+    /// Deposit initial amounts with program math and provide legs liquidity to pool.
+    pub fn open(config: BacktestConfig, price: &PriceRow, pool: &PoolRow) -> Result<Self> {
+        let deposit_legs = math::calculate_tulip_deposit(
+            config.base_amount,
+            config.quote_amount,
+            price.base_price,
+            price.quote_price,
+            config.base_decimals,
+            config.quote_decimals,
+        )?;
+
+        let mut legs: [LegState; 2] = Default::default();
+
+        for (leg, deposit_leg) in legs.iter_mut().zip(deposit_legs.iter()) {
+            let base_amount = deposit_leg
+                .base_collateral
+                .checked_add(deposit_leg.base_debt)
+                .ok_or_else(|| anyhow!("base amount overflow"))?;
+            let quote_amount = deposit_leg
+                .quote_collateral
+                .checked_add(deposit_leg.quote_debt)
+                .ok_or_else(|| anyhow!("quote amount overflow"))?;
+
+            // Crank swaps leg funds to even split by value before adding liquidity
+            let half_value =
+                math::calculate_value(base_amount, price.base_price, config.base_decimals)?
+                    .try_add(math::calculate_value(
+                        quote_amount,
+                        price.quote_price,
+                        config.quote_decimals,
+                    )?)?
+                    .try_div(2)?;
+            let base_liquidity = to_amount(half_value, price.base_price, config.base_decimals)?;
+            let quote_liquidity = to_amount(half_value, price.quote_price, config.quote_decimals)?;
+
+            leg.lp_amount =
+                math::calculate_pro_rata(pool.lp_supply, base_liquidity, pool.base_reserve)
+                    .map_err(|e| anyhow!("{:?}", e))?
+                    .min(
+                        math::calculate_pro_rata(
+                            pool.lp_supply,
+                            quote_liquidity,
+                            pool.quote_reserve,
+                        )
+                        .map_err(|e| anyhow!("{:?}", e))?,
+                    );
+            leg.base_debt = deposit_leg.base_debt;
+            leg.quote_debt = deposit_leg.quote_debt;
+        }
+
+        Ok(Self {
+            config,
+            legs,
+            timestamp: price.timestamp,
+        })
+    }
+
+    /// Accrue interest and rewards since previous step, liquidate unhealthy legs and
+    /// value chamber at `price`.
+    pub fn step(&mut self, price: &PriceRow, pool: &PoolRow) -> Result<Snapshot> {
+        if price.timestamp < self.timestamp {
+            bail!("step at {} is before {}", price.timestamp, self.timestamp);
+        }
+
+        let elapsed = (price.timestamp - self.timestamp) as u64;
+        self.timestamp = price.timestamp;
+
+        let config = &self.config;
+        let mut nav = Decimal::zero();
+        let mut base_liquidity = 0u64;
+        let mut base_debt = 0u64;
+        let mut ltv_bps = [0u64; 2];
+
+        for (index, leg) in self.legs.iter_mut().enumerate() {
+            if leg.liquidated {
+                nav = nav.try_add(leg.liquidated_value)?;
+                continue;
+            }
+
+            let (base_interest, base_remainder) = math::calculate_management_fee(
+                leg.base_debt,
+                config.base_borrow_apr_bps,
+                elapsed,
+                leg.base_interest_remainder,
+            )
+            .map_err(|e| anyhow!("{:?}", e))?;
+            let (quote_interest, quote_remainder) = math::calculate_management_fee(
+                leg.quote_debt,
+                config.quote_borrow_apr_bps,
+                elapsed,
+                leg.quote_interest_remainder,
+            )
+            .map_err(|e| anyhow!("{:?}", e))?;
+
+            leg.base_debt = leg.base_debt.saturating_add(base_interest);
+            leg.quote_debt = leg.quote_debt.saturating_add(quote_interest);
+            leg.base_interest_remainder = base_remainder;
+            leg.quote_interest_remainder = quote_remainder;
+
+            // Pool reserves movement is where impermanent loss comes from
+            let leg_base_liquidity =
+                math::calculate_pro_rata(pool.base_reserve, leg.lp_amount, pool.lp_supply)
+                    .map_err(|e| anyhow!("{:?}", e))?;
+            let leg_quote_liquidity =
+                math::calculate_pro_rata(pool.quote_reserve, leg.lp_amount, pool.lp_supply)
+                    .map_err(|e| anyhow!("{:?}", e))?;
+
+            // Constant product pool keeps even split, so quote side is half of liquidity value
+            let (reward, reward_remainder) = math::calculate_management_fee(
+                leg_quote_liquidity.saturating_mul(2),
+                config.reward_apr_bps,
+                elapsed,
+                leg.reward_remainder,
+            )
+            .map_err(|e| anyhow!("{:?}", e))?;
+            leg.reward_quote_amount = leg.reward_quote_amount.saturating_add(reward);
+            leg.reward_remainder = reward_remainder;
+
+            let obligation = TulipObligation {
+                deposited_value: math::calculate_value(
+                    leg_base_liquidity,
+                    price.base_price,
+                    config.base_decimals,
+                )?
+                .try_add(math::calculate_value(
+                    leg_quote_liquidity.saturating_add(leg.reward_quote_amount),
+                    price.quote_price,
+                    config.quote_decimals,
+                )?)?,
+                borrowed_value: math::calculate_value(
+                    leg.base_debt,
+                    price.base_price,
+                    config.base_decimals,
+                )?
+                .try_add(math::calculate_value(
+                    leg.quote_debt,
+                    price.quote_price,
+                    config.quote_decimals,
+                )?)?,
+                allowed_borrow_value: Decimal::zero(),
+                unhealthy_borrow_value: Decimal::zero(),
+            };
+
+            ltv_bps[index] = obligation.ltv_bps()?;
+
+            if ltv_bps[index] >= config.liquidation_ltv_bps {
+                let penalty = obligation
+                    .borrowed_value
+                    .try_mul(config.liquidation_penalty_bps)?
+                    .try_div(BPS_DENOMINATOR)?;
+                let equity = obligation.equity_value()?;
+
+                *leg = LegState {
+                    liquidated: true,
+                    liquidated_value: if equity > penalty {
+                        equity.try_sub(penalty)?
+                    } else {
+                        Decimal::zero()
+                    },
+                    ..Default::default()
+                };
+
+                nav = nav.try_add(leg.liquidated_value)?;
+                continue;
+            }
+
+            nav = nav.try_add(obligation.equity_value()?)?;
+            base_liquidity = base_liquidity.saturating_add(leg_base_liquidity);
+            base_debt = base_debt.saturating_add(leg.base_debt);
+        }
+
+        Ok(Snapshot {
+            timestamp: price.timestamp,
+            base_price: price.base_price,
+            quote_price: price.quote_price,
+            nav,
+            drift_bps: math::calculate_exposure_drift_bps(base_liquidity, base_debt)
+                .map_err(|e| anyhow!("{:?}", e))?,
+            ltv_bps,
+            liquidated: [self.legs[0].liquidated, self.legs[1].liquidated],
+        })
+    }
+}
+
+/// Replay series from the first price, which has pool state recorded before it.
+pub fn run(config: BacktestConfig, prices: &[PriceRow], pool: &[PoolRow]) -> Result<Vec<Snapshot>> {
+    let first_price = prices
+        .first()
+        .ok_or_else(|| anyhow!("empty price series"))?;
+
+    let mut pool_index = match pool
+        .iter()
+        .rposition(|row| row.timestamp <= first_price.timestamp)
+    {
+        Some(index) => index,
+        None => bail!("no pool state at or before {}", first_price.timestamp),
+    };
+
+    let mut simulation = Simulation::open(config, first_price, &pool[pool_index])?;
+    let mut snapshots = Vec::with_capacity(prices.len());
+
+    for price in prices {
+        while pool_index + 1 < pool.len() && pool[pool_index + 1].timestamp <= price.timestamp {
+            pool_index += 1;
+        }
+
+        snapshots.push(simulation.step(price, &pool[pool_index])?);
+    }
+
+    Ok(snapshots)
+}
+
+/// Convert `value` into token amount with `price`, rounded down.
+fn to_amount(value: Decimal, price: Decimal, decimals: u8) -> Result<u64> {
+    Ok(value
+        .try_div(price)?
+        .try_mul(math::decimals_factor(decimals).map_err(|e| anyhow!("{:?}", e))?)?
+        .try_floor_u64()?)
+}
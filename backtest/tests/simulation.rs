@@ -0,0 +1,128 @@
+use cetra_chamber::math::SECONDS_PER_YEAR;
+use chamber_backtest::{parse_decimal, read_pool, read_prices, run, BacktestConfig, Report};
+use tulipv2_sdk_common::math::decimal::Decimal;
+
+/// SOL-USDC pool of 1M SOL and 40M USDC.
+const POOL: &str = "timestamp,base_reserve,quote_reserve,lp_supply
+0,1000000000000000,40000000000000,1000000000000
+";
+
+/// 2.5 SOL and 100 USDC deposit, 200$ at 40$ SOL.
+fn config() -> BacktestConfig {
+    BacktestConfig {
+        base_decimals: 9,
+        quote_decimals: 6,
+        base_amount: 2500000000,
+        quote_amount: 100000000,
+        base_borrow_apr_bps: 0,
+        quote_borrow_apr_bps: 0,
+        reward_apr_bps: 0,
+        liquidation_ltv_bps: 8500,
+        liquidation_penalty_bps: 500,
+    }
+}
+
+#[test]
+fn parse_decimals() {
+    assert_eq!(parse_decimal("40").unwrap(), Decimal::from(40u64));
+    assert_eq!(
+        parse_decimal("0.5").unwrap(),
+        Decimal::from_scaled_val(500000000000000000)
+    );
+    assert_eq!(
+        parse_decimal("1.000000000000000001").unwrap(),
+        Decimal::from_scaled_val(1000000000000000001)
+    );
+
+    assert!(parse_decimal("").is_err());
+    assert!(parse_decimal("-1").is_err());
+    assert!(parse_decimal("0.0000000000000000001").is_err());
+}
+
+#[test]
+fn read_series() {
+    let prices =
+        read_prices("timestamp,base_price,quote_price\n0,40.5,1\n60,41,0.999\n".as_bytes())
+            .unwrap();
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices[1].quote_price, parse_decimal("0.999").unwrap());
+
+    let pool = read_pool(POOL.as_bytes()).unwrap();
+    assert_eq!(pool[0].lp_supply, 1000000000000);
+
+    assert!(read_prices("timestamp,base_price,quote_price\n60,40,1\n0,40,1\n".as_bytes()).is_err());
+}
+
+#[test]
+fn flat_market_keeps_nav_and_hedge() {
+    let prices =
+        read_prices("timestamp,base_price,quote_price\n0,40,1\n3600,40,1\n".as_bytes()).unwrap();
+    let pool = read_pool(POOL.as_bytes()).unwrap();
+
+    let snapshots = run(config(), &prices, &pool).unwrap();
+    assert_eq!(snapshots.len(), 2);
+
+    for snapshot in snapshots.iter() {
+        assert_eq!(snapshot.nav, Decimal::from(200u64));
+        assert_eq!(snapshot.drift_bps, 0);
+        assert_eq!(snapshot.liquidated, [false, false]);
+    }
+
+    // 1st leg: 150$ liquidity against 100$ USDC debt
+    assert_eq!(snapshots[0].ltv_bps, [6666, 6666]);
+}
+
+#[test]
+fn borrow_interest_reduces_nav() {
+    let prices = read_prices(
+        format!(
+            "timestamp,base_price,quote_price\n0,40,1\n{},40,1\n",
+            SECONDS_PER_YEAR
+        )
+        .as_bytes(),
+    )
+    .unwrap();
+    let pool = read_pool(POOL.as_bytes()).unwrap();
+
+    let mut config = config();
+    config.quote_borrow_apr_bps = 1000;
+
+    // 10% on 100$ USDC debt of 1st leg
+    let snapshots = run(config, &prices, &pool).unwrap();
+    assert_eq!(snapshots[1].nav, Decimal::from(190u64));
+
+    let report = Report::new(&snapshots).unwrap();
+    assert_eq!(report.summary.pnl, -10.0);
+    assert_eq!(report.summary.max_drawdown_bps, 500);
+}
+
+#[test]
+fn base_pump_liquidates_base_borrowing_leg() {
+    let prices =
+        read_prices("timestamp,base_price,quote_price\n0,40,1\n60,100,1\n".as_bytes()).unwrap();
+    let pool = read_pool(POOL.as_bytes()).unwrap();
+
+    let snapshots = run(config(), &prices, &pool).unwrap();
+
+    // 2nd leg: 787.5$ liquidity against 750$ SOL debt, 5% penalty takes the rest of equity
+    assert_eq!(snapshots[1].liquidated, [false, true]);
+    assert_eq!(snapshots[1].nav, parse_decimal("162.5").unwrap());
+
+    let report = Report::new(&snapshots).unwrap();
+    assert_eq!(report.summary.liquidations, 1);
+    assert_eq!(report.summary.max_drawdown_bps, 1875);
+
+    let mut output = vec![];
+    report.write_csv(&mut output).unwrap();
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .starts_with("timestamp,nav,pnl,drawdown_bps,drift_bps,ltv_bps_0,ltv_bps_1\n"));
+}
+
+#[test]
+fn missing_pool_state() {
+    let prices = read_prices("timestamp,base_price,quote_price\n-60,40,1\n".as_bytes()).unwrap();
+    let pool = read_pool(POOL.as_bytes()).unwrap();
+
+    assert!(run(config(), &prices, &pool).is_err());
+}
@@ -0,0 +1,329 @@
+//! Command-line tool for `Chamber` operators.
+
+mod nav;
+mod rpc;
+
+use anyhow::{anyhow, bail, Result};
+use cetra_chamber::{
+    state::{Chamber, ChamberConfig, UserPosition},
+    utils::derive_user_position,
+};
+use cetra_chamber_client::{instruction, TulipChamber};
+use clap::{ArgEnum, Parser, Subcommand};
+use rpc::Rpc;
+use solana_sdk::{pubkey::Pubkey, signature::read_keypair_file, signer::Signer};
+
+#[derive(Parser)]
+#[clap(name = "chamber-cli", version, about)]
+struct Cli {
+    /// RPC URL, e.g. local test validator.
+    #[clap(long, short, default_value = "http://localhost:8899")]
+    url: String,
+
+    /// Payer keypair, which is also chamber admin or user depending on command.
+    #[clap(long, short, default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// Leveraged farm pair.
+    #[clap(long, arg_enum, default_value = "sol-usdc-raydium")]
+    farm: Farm,
+
+    /// Chamber index on leveraged farm.
+    #[clap(long, default_value = "0")]
+    index: u8,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ArgEnum)]
+enum Farm {
+    SolUsdcRaydium,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create chamber for leveraged farm pair.
+    CreateChamber {
+        /// Wallet owning treasury token accounts, which must exist.
+        #[clap(long)]
+        treasury: Pubkey,
+
+        #[clap(long, default_value = "0")]
+        management_fee_bps: u16,
+
+        #[clap(long, default_value = "0")]
+        entry_fee_bps: u16,
+
+        #[clap(long, default_value = "0")]
+        exit_fee_bps: u16,
+
+        #[clap(long, default_value = "0")]
+        rebalance_threshold_bps: u16,
+
+        #[clap(long, default_value = "0")]
+        deleverage_ltv_bps: u16,
+
+        #[clap(long, default_value = "0")]
+        base_split_bps: u16,
+
+        #[clap(long, default_value = "0")]
+        withdrawal_cooldown: u32,
+
+        #[clap(long, default_value_t = Pubkey::default())]
+        swap_program: Pubkey,
+
+        /// Wallet allowed to run keeper instructions besides admin, payer by default.
+        #[clap(long)]
+        keeper: Option<Pubkey>,
+    },
+    /// Create strategy specific accounts.
+    InitStrategy,
+    /// Transfer accrued management fee to treasury.
+    CollectFee {
+        /// Wallet owning treasury token accounts stored in chamber.
+        #[clap(long)]
+        treasury: Pubkey,
+    },
+    /// Deploy idle funds of payer position.
+    Deposit {
+        #[clap(long, default_value = "0")]
+        base_amount: u64,
+
+        #[clap(long, default_value = "0")]
+        quote_amount: u64,
+    },
+    /// Deploy queued deposits and open next epoch.
+    ProcessEpoch,
+    /// Advance deploy flow, by single step or until settled.
+    Crank {
+        #[clap(long)]
+        all: bool,
+    },
+    /// Print `Chamber` state.
+    ShowChamber,
+    /// Print `UserPosition` state.
+    ShowPosition {
+        /// Position owner, payer by default.
+        #[clap(long)]
+        owner: Option<Pubkey>,
+    },
+    /// Compute NAV of chamber shareholders.
+    Nav,
+}
+
+/// Compute units requested by instructions, which CPI into levfarm.
+const DEPOSIT_COMPUTE_UNITS: u32 = 350000;
+const EPOCH_COMPUTE_UNITS: u32 = 400000;
+const CRANK_COMPUTE_UNITS: u32 = 530000;
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let keypair_path = match cli.keypair.strip_prefix("~/") {
+        Some(path) => format!("{}/{}", std::env::var("HOME")?, path),
+        None => cli.keypair.clone(),
+    };
+    let payer = match read_keypair_file(&keypair_path) {
+        Ok(payer) => payer,
+        Err(e) => bail!("failed to read keypair {}: {}", keypair_path, e),
+    };
+
+    // Treasury only matters for chamber creation and fee collection, actual one is
+    // stored in chamber
+    let treasury = match &cli.command {
+        Command::CreateChamber { treasury, .. } | Command::CollectFee { treasury } => *treasury,
+        _ => Pubkey::default(),
+    };
+    let chamber = match cli.farm {
+        Farm::SolUsdcRaydium => TulipChamber::new_sol_usdc_raydium(cli.index, treasury),
+    };
+
+    let rpc = Rpc::new(cli.url, payer);
+    let payer = rpc.payer.pubkey();
+
+    match cli.command {
+        Command::CreateChamber {
+            management_fee_bps,
+            entry_fee_bps,
+            exit_fee_bps,
+            rebalance_threshold_bps,
+            deleverage_ltv_bps,
+            base_split_bps,
+            withdrawal_cooldown,
+            swap_program,
+            keeper,
+            ..
+        } => {
+            let config = ChamberConfig {
+                management_fee_bps,
+                entry_fee_bps,
+                exit_fee_bps,
+                rebalance_threshold_bps,
+                deleverage_ltv_bps,
+                base_split_bps,
+                withdrawal_cooldown,
+                swap_program,
+            };
+            config
+                .validate()
+                .map_err(|e| anyhow!("invalid config: {:?}", e))?;
+
+            let signature = rpc.send(
+                instruction::initialize_chamber_ix(
+                    &chamber,
+                    &payer,
+                    &keeper.unwrap_or(payer),
+                    config,
+                ),
+                None,
+            )?;
+            println!("Chamber {} created: {}", chamber.pubkey, signature);
+        }
+        Command::InitStrategy => {
+            let signature = rpc.send(
+                instruction::initialize_chamber_strategy_ix(&chamber, &payer),
+                None,
+            )?;
+            println!("Strategy initialized: {}", signature);
+        }
+        Command::CollectFee { .. } => {
+            let state: Chamber = rpc.load(&chamber.pubkey)?;
+
+            let signature = rpc.send(
+                instruction::collect_management_fee_ix(&chamber, &payer),
+                None,
+            )?;
+            println!(
+                "Collected {} base, {} quote: {}",
+                { state.accrued_base_fee },
+                { state.accrued_quote_fee },
+                signature
+            );
+        }
+        Command::Deposit {
+            base_amount,
+            quote_amount,
+        } => {
+            let state: Chamber = rpc.load(&chamber.pubkey)?;
+
+            let signature = rpc.send(
+                instruction::deposit_chamber_ix(
+                    &chamber,
+                    &payer,
+                    state.epoch,
+                    base_amount,
+                    quote_amount,
+                ),
+                Some(DEPOSIT_COMPUTE_UNITS),
+            )?;
+            println!("Deposited: {}", signature);
+        }
+        Command::ProcessEpoch => {
+            let state: Chamber = rpc.load(&chamber.pubkey)?;
+
+            let signature = rpc.send(
+                instruction::process_chamber_epoch_ix(&chamber, &payer, state.epoch),
+                Some(EPOCH_COMPUTE_UNITS),
+            )?;
+            println!("Epoch {} processed: {}", { state.epoch }, signature);
+        }
+        Command::Crank { all } => loop {
+            let state: Chamber = rpc.load(&chamber.pubkey)?;
+            let operation = state.operation().map_err(|e| anyhow!("{:?}", e))?;
+
+            if operation.is_settled() {
+                println!("Chamber is settled: {:?}", operation);
+                break;
+            }
+
+            let signature = rpc.send(
+                instruction::crank_chamber_ix(&chamber, &payer, operation),
+                Some(CRANK_COMPUTE_UNITS),
+            )?;
+            println!("Cranked {:?}: {}", operation, signature);
+
+            if !all {
+                break;
+            }
+        },
+        Command::ShowChamber => {
+            let state: Chamber = rpc.load(&chamber.pubkey)?;
+            let protocol_type = state.protocol_type().map_err(|e| anyhow!("{:?}", e))?;
+            let amm_type = state.amm_type().map_err(|e| anyhow!("{:?}", e))?;
+            let operation = state.operation().map_err(|e| anyhow!("{:?}", e))?;
+
+            println!("Chamber: {}", chamber.pubkey);
+            println!("Authority: {}", state.authority);
+            println!("Admin: {}", state.admin);
+            println!("Keeper: {}", state.keeper);
+            println!("Leveraged farm: {}", state.leveraged_farm);
+            println!("Protocol: {:?}", protocol_type);
+            println!("AMM: {:?} {}", amm_type, state.pool.amm_id);
+            println!("Oracles: {} {}", state.base_oracle, state.quote_oracle);
+            println!("Operation: {:?}", operation);
+            // Packed zero-copy fields are copied out before formatting
+            println!("Epoch: {}", { state.epoch });
+            println!("Total shares: {}", { state.total_shares });
+            println!("Total base amount: {}", { state.total_base_amount });
+            println!("Total quote amount: {}", { state.total_quote_amount });
+            println!("LP amounts: {:?}", { state.lp_amounts });
+            println!("Claimable quote amount: {}", {
+                state.claimable_quote_amount
+            });
+            println!(
+                "Accrued fees: {} base, {} quote",
+                { state.accrued_base_fee },
+                { state.accrued_quote_fee }
+            );
+            for (id, leg) in state.legs.iter().enumerate() {
+                println!("Leg {}: {:?}", id, leg);
+            }
+            println!("Config: {:?}", state.config);
+        }
+        Command::ShowPosition { owner } => {
+            let owner = owner.unwrap_or(payer);
+            let (user_position, _) = derive_user_position(&owner, &chamber.pubkey);
+            let state: UserPosition = rpc.load(&user_position)?;
+            let chamber_state: Chamber = rpc.load(&chamber.pubkey)?;
+            let base_amount = chamber_state
+                .base_units_amount(state.base_amount)
+                .map_err(|e| anyhow!("{:?}", e))?;
+            let quote_amount = chamber_state
+                .quote_units_amount(state.quote_amount)
+                .map_err(|e| anyhow!("{:?}", e))?;
+
+            println!("User position: {}", user_position);
+            println!("Owner: {}", state.owner);
+            println!("Status: {:?}", state.status);
+            println!("Base amount: {} ({} units)", base_amount, state.base_amount);
+            println!(
+                "Quote amount: {} ({} units)",
+                quote_amount, state.quote_amount
+            );
+            println!("Shares: {}", state.shares);
+            println!(
+                "Queued: {} base, {} quote in epoch {}",
+                state.queued_base_amount, state.queued_quote_amount, state.queued_epoch
+            );
+            println!(
+                "Withdrawal: {} shares in epoch {}",
+                state.withdrawal_shares, state.withdrawal_epoch
+            );
+        }
+        Command::Nav => {
+            let state: Chamber = rpc.load(&chamber.pubkey)?;
+            let nav = nav::calculate_nav(&rpc, &chamber, &state)?;
+
+            println!("Obligations equity: {}", nav.obligations_value);
+            println!(
+                "Idle funds: {} base, {} quote",
+                nav.idle_base_amount, nav.idle_quote_amount
+            );
+            println!("NAV: {}", nav.total_value);
+            println!("Total shares: {}", { state.total_shares });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! Module compute `Chamber` NAV off-chain, mirroring `ProcessChamberEpoch`.
+
+use crate::rpc::Rpc;
+use anyhow::Result;
+use cetra_chamber::{
+    math,
+    state::{Chamber, ChamberEpoch, TulipObligation},
+    utils::derive_chamber_epoch,
+};
+use cetra_chamber_client::TulipChamber;
+use tulipv2_sdk_common::{
+    math::{common::TryAdd, decimal::Decimal},
+    pyth,
+};
+
+pub struct Nav {
+    pub obligations_value: Decimal,
+    pub idle_base_amount: u64,
+    pub idle_quote_amount: u64,
+
+    /// Obligations equity and idle funds.
+    pub total_value: Decimal,
+}
+
+pub fn calculate_nav(rpc: &Rpc, chamber: &TulipChamber, state: &Chamber) -> Result<Nav> {
+    let (epoch, _) = derive_chamber_epoch(&chamber.pubkey, state.epoch);
+    let epoch: ChamberEpoch = rpc.load(&epoch)?;
+
+    let base_price = pyth::load_pyth_price(
+        &rpc.client
+            .get_account_data(&chamber.farm_config.coin_price_account)?,
+    )?;
+    let quote_price = pyth::load_pyth_price(
+        &rpc.client
+            .get_account_data(&chamber.farm_config.pc_price_account)?,
+    )?;
+
+    let mut obligations_value = Decimal::zero();
+    for id in 0..2 {
+        let data = rpc
+            .client
+            .get_account_data(&chamber.derive_chamber_obligation(id).0)?;
+
+        obligations_value =
+            obligations_value.try_add(TulipObligation::unpack(&data)?.equity_value()?)?;
+    }
+
+    // Idle funds, which don't belong to idle positions with accrued fees, queued deposits or claims
+    let idle_base_amount = rpc
+        .load_token_amount(&chamber.get_base_ata())?
+        .saturating_sub(state.total_base_amount)
+        .saturating_sub(epoch.pending_base_amount);
+    let idle_quote_amount = rpc
+        .load_token_amount(&chamber.get_quote_ata())?
+        .saturating_sub(state.total_quote_amount)
+        .saturating_sub(epoch.pending_quote_amount)
+        .saturating_sub(state.claimable_quote_amount);
+
+    let total_value = obligations_value
+        .try_add(math::calculate_value(
+            idle_base_amount,
+            base_price,
+            state.base_decimals,
+        )?)?
+        .try_add(math::calculate_value(
+            idle_quote_amount,
+            quote_price,
+            state.quote_decimals,
+        )?)?;
+
+    Ok(Nav {
+        obligations_value,
+        idle_base_amount,
+        idle_quote_amount,
+        total_value,
+    })
+}
@@ -0,0 +1,60 @@
+//! Module provide RPC helpers shared by commands.
+
+use anchor_lang::AccountDeserialize;
+use anyhow::{anyhow, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+pub struct Rpc {
+    pub client: RpcClient,
+    pub payer: Keypair,
+}
+
+impl Rpc {
+    pub fn new(url: String, payer: Keypair) -> Self {
+        Rpc {
+            client: RpcClient::new_with_commitment(url, CommitmentConfig::confirmed()),
+            payer,
+        }
+    }
+
+    /// Sign with payer and send, optionally requesting more compute units.
+    pub fn send(&self, instruction: Instruction, compute_units: Option<u32>) -> Result<Signature> {
+        let mut instructions = vec![];
+
+        if let Some(units) = compute_units {
+            instructions.push(ComputeBudgetInstruction::request_units(units, 0));
+        }
+        instructions.push(instruction);
+
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            self.client.get_latest_blockhash()?,
+        );
+
+        Ok(self.client.send_and_confirm_transaction(&tx)?)
+    }
+
+    pub fn load<T: AccountDeserialize>(&self, pubkey: &Pubkey) -> Result<T> {
+        let data = self.client.get_account_data(pubkey)?;
+
+        T::try_deserialize(&mut data.as_ref()).map_err(|e| anyhow!("{}: {}", pubkey, e))
+    }
+
+    pub fn load_token_amount(&self, pubkey: &Pubkey) -> Result<u64> {
+        let data = self.client.get_account_data(pubkey)?;
+        let account = anchor_spl::token::TokenAccount::try_deserialize(&mut data.as_ref())?;
+
+        Ok(account.amount)
+    }
+}
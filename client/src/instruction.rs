@@ -0,0 +1,728 @@
+//! Module provide builders of `cetra-chamber` instructions.
+//!
+//! Builders return bare program instruction, so callers are free to prepend compute budget
+//! requests and sign transactions in their own way.
+
+use crate::{serum_program, TulipChamber};
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::{
+        bpf_loader_upgradeable,
+        instruction::Instruction,
+        system_program,
+        sysvar::{clock, rent},
+    },
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address},
+    token,
+};
+use cetra_chamber::{
+    accounts as cetra_chamber_accounts, instruction as cetra_chamber_instruction,
+    state::{ChamberConfig, ChamberOperation, TokenSide},
+    utils::{derive_chamber_epoch, derive_native_unwrap_account, derive_user_position},
+};
+use tulipv2_sdk_common::config::RAYDIUM_LIQUIDITY_V4;
+
+fn build(accounts: Vec<AccountMeta>, data: Vec<u8>) -> Instruction {
+    Instruction {
+        program_id: cetra_chamber::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Treasury token accounts of `chamber.treasury` must exist.
+pub fn initialize_chamber_ix(
+    chamber: &TulipChamber,
+    payer: &Pubkey,
+    keeper: &Pubkey,
+    config: ChamberConfig,
+) -> Instruction {
+    let (epoch, epoch_bump) = derive_chamber_epoch(&chamber.pubkey, 0);
+
+    let accounts = cetra_chamber_accounts::InitializeChamber {
+        chamber: chamber.pubkey,
+        authority: chamber.authority,
+        epoch,
+        base_ata: chamber.get_base_ata(),
+        quote_ata: chamber.get_quote_ata(),
+        base_mint: chamber.farm_config.base_token_mint,
+        quote_mint: chamber.farm_config.quote_token_mint,
+        treasury_base_ata: chamber.get_treasury_base_ata(),
+        treasury_quote_ata: chamber.get_treasury_quote_ata(),
+        payer: *payer,
+        clock_sysvar: clock::id(),
+        rent_sysvar: rent::id(),
+        associated_token_program: associated_token::ID,
+        token_program: token::ID,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = cetra_chamber_instruction::InitializeChamber {
+        leveraged_farm: chamber.farm_config.account,
+        index: chamber.index,
+        bump: chamber.bump,
+        authority_bump: chamber.authority_bump,
+        epoch_bump,
+        protocol_type: chamber.protocol_type,
+        amm_type: chamber.amm_type,
+        config,
+        keeper: *keeper,
+        pool: chamber.get_pool(),
+        base_oracle: chamber.farm_config.coin_price_account,
+        quote_oracle: chamber.farm_config.pc_price_account,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+pub fn update_chamber_config_ix(
+    chamber: &TulipChamber,
+    admin: &Pubkey,
+    config: ChamberConfig,
+) -> Instruction {
+    let accounts = cetra_chamber_accounts::UpdateChamberConfig {
+        chamber: chamber.pubkey,
+        admin: *admin,
+        clock_sysvar: clock::id(),
+    }
+    .to_account_metas(None);
+
+    build(
+        accounts,
+        cetra_chamber_instruction::UpdateChamberConfig { config }.data(),
+    )
+}
+
+pub fn collect_management_fee_ix(chamber: &TulipChamber, admin: &Pubkey) -> Instruction {
+    let accounts = cetra_chamber_accounts::CollectManagementFee {
+        chamber: chamber.pubkey,
+        authority: chamber.authority,
+        chamber_base_ata: chamber.get_base_ata(),
+        chamber_quote_ata: chamber.get_quote_ata(),
+        treasury_base_ata: chamber.get_treasury_base_ata(),
+        treasury_quote_ata: chamber.get_treasury_quote_ata(),
+        admin: *admin,
+        clock_sysvar: clock::id(),
+        token_program: token::ID,
+    }
+    .to_account_metas(None);
+
+    build(
+        accounts,
+        cetra_chamber_instruction::CollectManagementFee {}.data(),
+    )
+}
+
+pub fn initialize_chamber_strategy_ix(chamber: &TulipChamber, payer: &Pubkey) -> Instruction {
+    let mut accounts = cetra_chamber_accounts::InitializeChamberStrategy {
+        chamber: chamber.pubkey,
+        authority: chamber.authority,
+        payer: *payer,
+        clock_sysvar: clock::id(),
+        rent_sysvar: rent::id(),
+        associated_token_program: associated_token::ID,
+        token_program: token::ID,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    accounts.extend(chamber.get_strategy_remaining_accounts());
+
+    build(
+        accounts,
+        cetra_chamber_instruction::InitializeChamberStrategy {}.data(),
+    )
+}
+
+pub fn initialize_user_position_ix(
+    chamber: &TulipChamber,
+    user: &Pubkey,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Instruction {
+    let (user_position, bump) = derive_user_position(user, &chamber.pubkey);
+
+    let accounts = cetra_chamber_accounts::InitializeUserPosition {
+        chamber: chamber.pubkey,
+        user_position,
+        user_base_ata: get_associated_token_address(user, &chamber.farm_config.base_token_mint),
+        user_quote_ata: get_associated_token_address(user, &chamber.farm_config.quote_token_mint),
+        chamber_base_ata: chamber.get_base_ata(),
+        chamber_quote_ata: chamber.get_quote_ata(),
+        treasury_base_ata: chamber.get_treasury_base_ata(),
+        treasury_quote_ata: chamber.get_treasury_quote_ata(),
+        payer: *user,
+        clock_sysvar: clock::id(),
+        token_program: token::ID,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = cetra_chamber_instruction::InitializeUserPosition {
+        bump,
+        base_amount,
+        quote_amount,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+pub fn initialize_user_position_single_sided_ix(
+    chamber: &TulipChamber,
+    user: &Pubkey,
+    side: TokenSide,
+    amount: u64,
+    minimum_amount_out: u64,
+) -> Instruction {
+    let (user_position, bump) = derive_user_position(user, &chamber.pubkey);
+    let farm_config = &chamber.farm_config;
+
+    let mint = match side {
+        TokenSide::Base => farm_config.base_token_mint,
+        TokenSide::Quote => farm_config.quote_token_mint,
+    };
+
+    let accounts = cetra_chamber_accounts::InitializeUserPositionSingleSided {
+        user_position,
+        chamber: chamber.pubkey,
+        authority: chamber.authority,
+        user_ata: get_associated_token_address(user, &mint),
+        chamber_base_ata: chamber.get_base_ata(),
+        chamber_quote_ata: chamber.get_quote_ata(),
+        treasury_base_ata: chamber.get_treasury_base_ata(),
+        treasury_quote_ata: chamber.get_treasury_quote_ata(),
+        amm_program: RAYDIUM_LIQUIDITY_V4,
+        amm_id: farm_config.amm_id,
+        amm_authority: chamber.market_amm_authority,
+        amm_open_orders: farm_config.amm_open_orders,
+        amm_quantities_or_target_orders: farm_config.amm_quantities_or_target_orders,
+        pool_coin_token_account: farm_config.amm_coin_account,
+        pool_pc_token_account: farm_config.amm_pc_account,
+        serum_program_id: serum_program::id(),
+        serum_market: farm_config.serum_market,
+        serum_bids: farm_config.serum_bids,
+        serum_asks: farm_config.serum_asks,
+        serum_event_queue: farm_config.serum_event_queue,
+        serum_coin_vault_account: farm_config.serum_coin_vault,
+        serum_pc_vault_account: farm_config.serum_pc_vault,
+        serum_vault_signer: chamber.market_vault_signer,
+        payer: *user,
+        clock_sysvar: clock::id(),
+        token_program: token::ID,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = cetra_chamber_instruction::InitializeUserPositionSingleSided {
+        bump,
+        side,
+        amount,
+        minimum_amount_out,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+pub fn initialize_user_position_native_ix(
+    chamber: &TulipChamber,
+    user: &Pubkey,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Instruction {
+    let (user_position, bump) = derive_user_position(user, &chamber.pubkey);
+
+    let accounts = cetra_chamber_accounts::InitializeUserPositionNative {
+        chamber: chamber.pubkey,
+        user_position,
+        user_quote_ata: get_associated_token_address(user, &chamber.farm_config.quote_token_mint),
+        chamber_base_ata: chamber.get_base_ata(),
+        chamber_quote_ata: chamber.get_quote_ata(),
+        treasury_base_ata: chamber.get_treasury_base_ata(),
+        treasury_quote_ata: chamber.get_treasury_quote_ata(),
+        payer: *user,
+        clock_sysvar: clock::id(),
+        token_program: token::ID,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = cetra_chamber_instruction::InitializeUserPositionNative {
+        bump,
+        base_amount,
+        quote_amount,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+pub fn withdraw_user_position_ix(
+    chamber: &TulipChamber,
+    user: &Pubkey,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Instruction {
+    let (user_position, _) = derive_user_position(user, &chamber.pubkey);
+
+    let accounts = cetra_chamber_accounts::WithdrawUserPosition {
+        user_position,
+        chamber: chamber.pubkey,
+        authority: chamber.authority,
+        user_base_ata: get_associated_token_address(user, &chamber.farm_config.base_token_mint),
+        user_quote_ata: get_associated_token_address(user, &chamber.farm_config.quote_token_mint),
+        chamber_base_ata: chamber.get_base_ata(),
+        chamber_quote_ata: chamber.get_quote_ata(),
+        treasury_base_ata: chamber.get_treasury_base_ata(),
+        treasury_quote_ata: chamber.get_treasury_quote_ata(),
+        payer: *user,
+        clock_sysvar: clock::id(),
+        token_program: token::ID,
+    }
+    .to_account_metas(None);
+
+    let data = cetra_chamber_instruction::WithdrawUserPosition {
+        base_amount,
+        quote_amount,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+pub fn withdraw_user_position_native_ix(
+    chamber: &TulipChamber,
+    user: &Pubkey,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Instruction {
+    let (user_position, _) = derive_user_position(user, &chamber.pubkey);
+    let (unwrap_account, _) = derive_native_unwrap_account(&chamber.pubkey, user);
+
+    let accounts = cetra_chamber_accounts::WithdrawUserPositionNative {
+        user_position,
+        chamber: chamber.pubkey,
+        authority: chamber.authority,
+        unwrap_account,
+        base_mint: chamber.farm_config.base_token_mint,
+        user_quote_ata: get_associated_token_address(user, &chamber.farm_config.quote_token_mint),
+        chamber_base_ata: chamber.get_base_ata(),
+        chamber_quote_ata: chamber.get_quote_ata(),
+        treasury_base_ata: chamber.get_treasury_base_ata(),
+        treasury_quote_ata: chamber.get_treasury_quote_ata(),
+        payer: *user,
+        clock_sysvar: clock::id(),
+        rent: rent::id(),
+        token_program: token::ID,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = cetra_chamber_instruction::WithdrawUserPositionNative {
+        base_amount,
+        quote_amount,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+/// `epoch` is current `Chamber` epoch.
+pub fn queue_deposit_ix(
+    chamber: &TulipChamber,
+    user: &Pubkey,
+    epoch: u64,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Instruction {
+    let accounts = cetra_chamber_accounts::QueueDeposit {
+        user_position: derive_user_position(user, &chamber.pubkey).0,
+        chamber: chamber.pubkey,
+        epoch: derive_chamber_epoch(&chamber.pubkey, epoch).0,
+        payer: *user,
+        clock_sysvar: clock::id(),
+    }
+    .to_account_metas(None);
+
+    let data = cetra_chamber_instruction::QueueDeposit {
+        base_amount,
+        quote_amount,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+/// `epoch` is current `Chamber` epoch.
+pub fn request_withdrawal_ix(
+    chamber: &TulipChamber,
+    user: &Pubkey,
+    epoch: u64,
+    shares: u64,
+) -> Instruction {
+    let accounts = cetra_chamber_accounts::RequestWithdrawal {
+        user_position: derive_user_position(user, &chamber.pubkey).0,
+        chamber: chamber.pubkey,
+        epoch: derive_chamber_epoch(&chamber.pubkey, epoch).0,
+        payer: *user,
+        clock_sysvar: clock::id(),
+    }
+    .to_account_metas(None);
+
+    build(
+        accounts,
+        cetra_chamber_instruction::RequestWithdrawal { shares }.data(),
+    )
+}
+
+/// `withdrawal_epoch` is taken from `UserPosition`.
+pub fn cancel_withdrawal_ix(
+    chamber: &TulipChamber,
+    user: &Pubkey,
+    withdrawal_epoch: u64,
+) -> Instruction {
+    let accounts = cetra_chamber_accounts::CancelWithdrawal {
+        user_position: derive_user_position(user, &chamber.pubkey).0,
+        chamber: chamber.pubkey,
+        epoch: derive_chamber_epoch(&chamber.pubkey, withdrawal_epoch).0,
+        payer: *user,
+    }
+    .to_account_metas(None);
+
+    build(
+        accounts,
+        cetra_chamber_instruction::CancelWithdrawal {}.data(),
+    )
+}
+
+/// `queued_epoch` is taken from `UserPosition`.
+pub fn claim_deposit_ix(chamber: &TulipChamber, user: &Pubkey, queued_epoch: u64) -> Instruction {
+    let accounts = cetra_chamber_accounts::ClaimDeposit {
+        user_position: derive_user_position(user, &chamber.pubkey).0,
+        chamber: chamber.pubkey,
+        epoch: derive_chamber_epoch(&chamber.pubkey, queued_epoch).0,
+        payer: *user,
+    }
+    .to_account_metas(None);
+
+    build(accounts, cetra_chamber_instruction::ClaimDeposit {}.data())
+}
+
+/// `withdrawal_epoch` is taken from `UserPosition`.
+pub fn claim_withdrawal_ix(
+    chamber: &TulipChamber,
+    user: &Pubkey,
+    withdrawal_epoch: u64,
+) -> Instruction {
+    let accounts = cetra_chamber_accounts::ClaimWithdrawal {
+        user_position: derive_user_position(user, &chamber.pubkey).0,
+        chamber: chamber.pubkey,
+        authority: chamber.authority,
+        epoch: derive_chamber_epoch(&chamber.pubkey, withdrawal_epoch).0,
+        user_quote_ata: get_associated_token_address(user, &chamber.farm_config.quote_token_mint),
+        chamber_quote_ata: chamber.get_quote_ata(),
+        payer: *user,
+        clock_sysvar: clock::id(),
+        token_program: token::ID,
+    }
+    .to_account_metas(None);
+
+    build(
+        accounts,
+        cetra_chamber_instruction::ClaimWithdrawal {}.data(),
+    )
+}
+
+/// `epoch` is current `Chamber` epoch, which is processed.
+pub fn process_chamber_epoch_ix(chamber: &TulipChamber, payer: &Pubkey, epoch: u64) -> Instruction {
+    let (next_epoch, next_epoch_bump) = derive_chamber_epoch(&chamber.pubkey, epoch + 1);
+
+    let mut accounts = cetra_chamber_accounts::ProcessChamberEpoch {
+        chamber: chamber.pubkey,
+        authority: chamber.authority,
+        epoch: derive_chamber_epoch(&chamber.pubkey, epoch).0,
+        next_epoch,
+        chamber_base_ata: chamber.get_base_ata(),
+        chamber_quote_ata: chamber.get_quote_ata(),
+        payer: *payer,
+        clock_sysvar: clock::id(),
+        rent_sysvar: rent::id(),
+        token_program: token::ID,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    accounts.extend(chamber.get_deposit_remaining_accounts());
+
+    build(
+        accounts,
+        cetra_chamber_instruction::ProcessChamberEpoch { next_epoch_bump }.data(),
+    )
+}
+
+/// `epoch` is current `Chamber` epoch.
+pub fn deposit_chamber_ix(
+    chamber: &TulipChamber,
+    user: &Pubkey,
+    epoch: u64,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Instruction {
+    let mut accounts = cetra_chamber_accounts::DepositChamber {
+        user_position: derive_user_position(user, &chamber.pubkey).0,
+        chamber: chamber.pubkey,
+        epoch: derive_chamber_epoch(&chamber.pubkey, epoch).0,
+        authority: chamber.authority,
+        chamber_base_ata: chamber.get_base_ata(),
+        chamber_quote_ata: chamber.get_quote_ata(),
+        payer: *user,
+        clock_sysvar: clock::id(),
+        rent_sysvar: rent::id(),
+        token_program: token::ID,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    accounts.extend(chamber.get_deposit_remaining_accounts());
+
+    let data = cetra_chamber_instruction::DepositChamber {
+        base_amount,
+        quote_amount,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+/// `repay` selects remaining accounts of base debt repayment, when chamber base debt
+/// outgrows base liquidity.
+pub fn rebalance_chamber_ix(chamber: &TulipChamber, payer: &Pubkey, repay: bool) -> Instruction {
+    let mut accounts = cetra_chamber_accounts::RebalanceChamber {
+        chamber: chamber.pubkey,
+        authority: chamber.authority,
+        chamber_base_ata: chamber.get_base_ata(),
+        chamber_quote_ata: chamber.get_quote_ata(),
+        pool_coin_token_account: chamber.farm_config.amm_coin_account,
+        pool_pc_token_account: chamber.farm_config.amm_pc_account,
+        lp_mint: chamber.farm_config.lp_mint,
+        base_oracle: chamber.farm_config.coin_price_account,
+        quote_oracle: chamber.farm_config.pc_price_account,
+        payer: *payer,
+        clock_sysvar: clock::id(),
+        rent_sysvar: rent::id(),
+        token_program: token::ID,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    if repay {
+        accounts.extend(chamber.get_vault_remaining_accounts());
+        accounts.extend(vec![
+            AccountMeta::new(chamber.derive_chamber_position_info(0).0, false),
+            AccountMeta::new(chamber.derive_chamber_position_info(1).0, false),
+        ]);
+    } else {
+        accounts.extend(chamber.get_deposit_remaining_accounts());
+    }
+
+    let data = cetra_chamber_instruction::RebalanceChamber {
+        nonce: chamber.derive_chamber_balance_account(1).1,
+        meta_nonce: chamber.derive_chamber_balance_metadata(1).1,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+/// `operation` is current `Chamber` operation, which selects remaining accounts of next step.
+pub fn crank_chamber_ix(
+    chamber: &TulipChamber,
+    payer: &Pubkey,
+    operation: ChamberOperation,
+) -> Instruction {
+    let mut accounts = cetra_chamber_accounts::CrankChamber {
+        chamber: chamber.pubkey,
+        authority: chamber.authority,
+        payer: *payer,
+        clock_sysvar: clock::id(),
+        rent_sysvar: rent::id(),
+        token_program: token::ID,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    // Settled chamber is cranked only to restake LP left outside of vault
+    if operation == ChamberOperation::LiquidityAdded || operation == ChamberOperation::Staked {
+        accounts.extend(chamber.get_vault_remaining_accounts());
+    } else {
+        accounts.extend(chamber.get_liquidity_remaining_accounts());
+    }
+
+    let data = cetra_chamber_instruction::CrankChamber {
+        nonce_0: chamber.derive_chamber_balance_account(0).1,
+        nonce_1: chamber.derive_chamber_balance_account(1).1,
+        meta_nonce_0: chamber.derive_chamber_balance_metadata(0).1,
+        meta_nonce_1: chamber.derive_chamber_balance_metadata(1).1,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+pub fn deleverage_chamber_ix(
+    chamber: &TulipChamber,
+    payer: &Pubkey,
+    withdraw_percent: u8,
+) -> Instruction {
+    let mut accounts = cetra_chamber_accounts::DeleverageChamber {
+        chamber: chamber.pubkey,
+        authority: chamber.authority,
+        payer: *payer,
+        clock_sysvar: clock::id(),
+        rent_sysvar: rent::id(),
+        token_program: token::ID,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    accounts.extend(chamber.get_vault_remaining_accounts());
+    accounts.extend(vec![
+        AccountMeta::new(chamber.derive_chamber_position_info(0).0, false),
+        AccountMeta::new(chamber.derive_chamber_position_info(1).0, false),
+    ]);
+
+    let data = cetra_chamber_instruction::DeleverageChamber {
+        nonce_0: chamber.derive_chamber_balance_account(0).1,
+        nonce_1: chamber.derive_chamber_balance_account(1).1,
+        meta_nonce_0: chamber.derive_chamber_balance_metadata(0).1,
+        meta_nonce_1: chamber.derive_chamber_balance_metadata(1).1,
+        withdraw_percent,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+/// `route` and `data` are provided by aggregator for `swap_program`.
+pub fn swap_chamber_ix(
+    chamber: &TulipChamber,
+    admin: &Pubkey,
+    epoch: u64,
+    source_ata: &Pubkey,
+    destination_ata: &Pubkey,
+    swap_program: &Pubkey,
+    route: Vec<AccountMeta>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    data: Vec<u8>,
+) -> Instruction {
+    let mut accounts = cetra_chamber_accounts::SwapChamber {
+        chamber: chamber.pubkey,
+        epoch: derive_chamber_epoch(&chamber.pubkey, epoch).0,
+        authority: chamber.authority,
+        chamber_base_ata: chamber.get_base_ata(),
+        chamber_quote_ata: chamber.get_quote_ata(),
+        source_ata: *source_ata,
+        destination_ata: *destination_ata,
+        swap_program: *swap_program,
+        admin: *admin,
+    }
+    .to_account_metas(None);
+
+    accounts.extend(route);
+
+    let data = cetra_chamber_instruction::SwapChamber {
+        amount_in,
+        minimum_amount_out,
+        data,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+/// Takes bare `chamber` address, because accounts in previous layout can't be loaded as `TulipChamber`.
+/// `keeper` and pool of `chamber` are set on chambers created before they were introduced.
+pub fn migrate_chamber_ix(chamber: &TulipChamber, admin: &Pubkey, keeper: &Pubkey) -> Instruction {
+    let accounts = cetra_chamber_accounts::MigrateChamber {
+        chamber: chamber.pubkey,
+        admin: *admin,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = cetra_chamber_instruction::MigrateChamber {
+        keeper: *keeper,
+        pool: chamber.get_pool(),
+        base_oracle: chamber.farm_config.coin_price_account,
+        quote_oracle: chamber.farm_config.pc_price_account,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+/// `chamber` must be resolved by `TulipChamber::new_legacy_sol_usdc_raydium`, `admin` is
+/// program upgrade authority, which becomes chamber admin.
+pub fn migrate_legacy_chamber_ix(
+    chamber: &TulipChamber,
+    admin: &Pubkey,
+    keeper: &Pubkey,
+    config: ChamberConfig,
+) -> Instruction {
+    let (epoch, epoch_bump) = derive_chamber_epoch(&chamber.pubkey, 0);
+    let (program_data, _) = Pubkey::find_program_address(
+        &[cetra_chamber::id().as_ref()],
+        &bpf_loader_upgradeable::id(),
+    );
+
+    let accounts = cetra_chamber_accounts::MigrateLegacyChamber {
+        chamber: chamber.pubkey,
+        epoch,
+        base_mint: chamber.farm_config.base_token_mint,
+        quote_mint: chamber.farm_config.quote_token_mint,
+        base_ata: chamber.get_base_ata(),
+        quote_ata: chamber.get_quote_ata(),
+        treasury_base_ata: chamber.get_treasury_base_ata(),
+        treasury_quote_ata: chamber.get_treasury_quote_ata(),
+        program: cetra_chamber::id(),
+        program_data,
+        admin: *admin,
+        clock_sysvar: clock::id(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = cetra_chamber_instruction::MigrateLegacyChamber {
+        epoch_bump,
+        amm_type: chamber.amm_type,
+        config,
+        keeper: *keeper,
+        pool: chamber.get_pool(),
+        base_oracle: chamber.farm_config.coin_price_account,
+        quote_oracle: chamber.farm_config.pc_price_account,
+    }
+    .data();
+
+    build(accounts, data)
+}
+
+pub fn migrate_user_position_ix(user_position: &Pubkey, payer: &Pubkey) -> Instruction {
+    let accounts = cetra_chamber_accounts::MigrateUserPosition {
+        user_position: *user_position,
+        payer: *payer,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    build(
+        accounts,
+        cetra_chamber_instruction::MigrateUserPosition {}.data(),
+    )
+}
@@ -0,0 +1,28 @@
+//! Client SDK for building `cetra-chamber` instructions.
+//!
+//! `TulipChamber` resolves strategy specific accounts (Tulip levfarm, Raydium AMM
+//! and Serum market) of a single chamber, while `instruction` module provides builders,
+//! which assemble program accounts and remaining accounts in expected order.
+
+pub mod instruction;
+mod tulip_chamber;
+
+pub use tulip_chamber::*;
+
+pub mod serum_program {
+    use anchor_lang::declare_id;
+
+    declare_id!("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin");
+}
+
+pub mod tulip_mint {
+    use anchor_lang::declare_id;
+
+    declare_id!("TuLipcqtGVXP9XR62wM8WWCm6a9vhLs7T1uoWBk6FDs");
+}
+
+pub mod raydium_raysrm_farm {
+    use anchor_lang::declare_id;
+
+    declare_id!("GUzaohfNuFbBqQTnPgPSNciv3aUvriXYjQduRE3ZkqFw");
+}
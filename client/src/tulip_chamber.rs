@@ -0,0 +1,465 @@
+//! Module resolve accounts of `Chamber` running Tulip levfarm strategy.
+
+use crate::{raydium_raysrm_farm, serum_program, tulip_mint};
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_spl::associated_token::get_associated_token_address;
+use cetra_chamber::{
+    state::{AmmType, ChamberPool, ProtocolType},
+    utils::{derive_chamber_address, derive_chamber_authority, derive_legacy_chamber_address},
+};
+use tulipv2_sdk_common::config::{
+    levfarm::{
+        ray_solusdc,
+        reserves::{
+            sol::LIQUIDITY_SUPPLY_TOKEN_ACCOUNT as SOL_LIQUIDITY_SUPPLY_TOKEN_ACCOUNT,
+            usdc::LIQUIDITY_SUPPLY_TOKEN_ACCOUNT as USDC_LIQUIDITY_SUPPLY_TOKEN_ACCOUNT,
+        },
+        LevFarmConfig, BORROW_AUTHORIZER,
+    },
+    RAYDIUM_LIQUIDITY_V4, RAYDIUM_STAKE_V5,
+};
+use tulipv2_sdk_levfarm::accounts::{
+    derivations::{
+        derive_user_farm_address, derive_user_farm_obligation_address,
+        derive_user_farm_obligation_vault_address, derive_user_position_info_address,
+    },
+    Farms,
+};
+
+/// TODO: Add support for other AMMs.
+pub struct TulipChamber {
+    pub farm_config: LevFarmConfig,
+    pub farm: Farms,
+    pub protocol_type: ProtocolType,
+    pub amm_type: AmmType,
+    pub index: u8,
+
+    /// Wallet owning treasury token accounts.
+    pub treasury: Pubkey,
+
+    pub pubkey: Pubkey,
+    pub authority: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub base_liquidity_supply_token_account: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub quote_liquidity_supply_token_account: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub market_amm_authority: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub market_vault_signer: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub vault_pool_authority: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub vault_pda: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub vault_info: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub vault_lp_token_account: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub vault_reward_a_token_account: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub vault_pool_reward_a_token_account: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub vault_reward_b_token_account: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub vault_pool_reward_b_token_account: Pubkey,
+
+    /// Depends on underlying AMM.
+    pub vault_farm: Pubkey,
+
+    pub bump: u8,
+    pub authority_bump: u8,
+}
+
+impl TulipChamber {
+    pub fn new(
+        farm_config: LevFarmConfig,
+        farm: Farms,
+        amm_type: AmmType,
+        index: u8,
+        treasury: Pubkey,
+        base_liquidity_supply_token_account: Pubkey,
+        quote_liquidity_supply_token_account: Pubkey,
+        market_amm_authority: Pubkey,
+        market_vault_signer: Pubkey,
+        vault_pool_authority: Pubkey,
+        vault_pda: Pubkey,
+        vault_info: Pubkey,
+        vault_lp_token_account: Pubkey,
+        vault_reward_a_token_account: Pubkey,
+        vault_pool_reward_a_token_account: Pubkey,
+        vault_reward_b_token_account: Pubkey,
+        vault_pool_reward_b_token_account: Pubkey,
+        vault_farm: Pubkey,
+    ) -> Self {
+        let (pubkey, bump) = derive_chamber_address(&farm_config.account, index);
+        let (authority, authority_bump) = derive_chamber_authority(&pubkey);
+
+        TulipChamber {
+            farm_config,
+            farm,
+            protocol_type: ProtocolType::Tulip,
+            amm_type,
+            index,
+            treasury,
+            pubkey,
+            authority,
+            base_liquidity_supply_token_account,
+            quote_liquidity_supply_token_account,
+            market_amm_authority,
+            market_vault_signer,
+            vault_pool_authority,
+            vault_pda,
+            vault_info,
+            vault_lp_token_account,
+            vault_reward_a_token_account,
+            vault_pool_reward_a_token_account,
+            vault_reward_b_token_account,
+            vault_pool_reward_b_token_account,
+            vault_farm,
+            bump,
+            authority_bump,
+        }
+    }
+
+    pub fn new_sol_usdc_raydium(index: u8, treasury: Pubkey) -> Self {
+        Self::new(
+            ray_solusdc::get_lev_farm_config(),
+            Farms::SolUsdcRayVault,
+            AmmType::Raydium,
+            index,
+            treasury,
+            SOL_LIQUIDITY_SUPPLY_TOKEN_ACCOUNT,
+            USDC_LIQUIDITY_SUPPLY_TOKEN_ACCOUNT,
+            ray_solusdc::market_config::AMM_AUTHORITY,
+            ray_solusdc::market_config::SERUM_VAULT_SIGNER,
+            ray_solusdc::vault_config::POOL_AUTHORITY,
+            ray_solusdc::vault_config::PDA,
+            ray_solusdc::vault_config::VAULT_INFO_ACCOUNT,
+            ray_solusdc::vault_config::LP_TOKEN_ACCOUNT,
+            ray_solusdc::vault_config::REWARD_A_TOKEN_ACCOUNT,
+            ray_solusdc::vault_config::POOL_REWARD_A_TOKEN_ACCOUNT,
+            ray_solusdc::vault_config::REWARD_B_TOKEN_ACCOUNT,
+            ray_solusdc::vault_config::POOL_REWARD_B_TOKEN_ACCOUNT,
+            raydium_raysrm_farm::id(),
+        )
+    }
+
+    /// SOL-USDC chamber created by the first release, derived without `index` seed.
+    pub fn new_legacy_sol_usdc_raydium(treasury: Pubkey) -> Self {
+        let mut chamber = Self::new_sol_usdc_raydium(0, treasury);
+        let (pubkey, bump) = derive_legacy_chamber_address(&chamber.farm_config.account);
+        let (authority, authority_bump) = derive_chamber_authority(&pubkey);
+
+        chamber.pubkey = pubkey;
+        chamber.bump = bump;
+        chamber.authority = authority;
+        chamber.authority_bump = authority_bump;
+
+        chamber
+    }
+
+    pub fn derive_chamber_farm(&self) -> (Pubkey, u8) {
+        derive_user_farm_address(self.authority, tulipv2_sdk_levfarm::ID, 0, self.farm)
+    }
+
+    pub fn derive_chamber_obligation(&self, id: usize) -> (Pubkey, u8) {
+        let (chamber_farm, _) = self.derive_chamber_farm();
+
+        derive_user_farm_obligation_address(
+            self.authority,
+            chamber_farm,
+            tulipv2_sdk_levfarm::ID,
+            id as u8,
+        )
+    }
+
+    pub fn derive_chamber_obligation_vault(&self, id: usize) -> (Pubkey, u8) {
+        let (chamber_farm, _) = self.derive_chamber_farm();
+
+        derive_user_farm_obligation_vault_address(chamber_farm, tulipv2_sdk_levfarm::ID, id as u8)
+    }
+
+    pub fn derive_chamber_position_info(&self, id: usize) -> (Pubkey, u8) {
+        let (chamber_farm, _) = self.derive_chamber_farm();
+
+        derive_user_position_info_address(chamber_farm, tulipv2_sdk_levfarm::ID, id as u8)
+    }
+
+    pub fn derive_chamber_balance_account(&self, id: usize) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                self.vault_info.as_ref(),
+                self.derive_chamber_obligation_vault(id).0.as_ref(),
+            ],
+            &self.farm_config.solfarm_vault_program,
+        )
+    }
+
+    pub fn derive_chamber_balance_metadata(&self, id: usize) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                self.derive_chamber_balance_account(id).0.as_ref(),
+                self.derive_chamber_obligation_vault(id).0.as_ref(),
+            ],
+            &self.farm_config.solfarm_vault_program,
+        )
+    }
+
+    pub fn derive_chamber_all(&self, id: usize) -> (Pubkey, Pubkey, Pubkey, Pubkey, Pubkey) {
+        (
+            self.derive_chamber_obligation(id).0,
+            self.derive_chamber_obligation_vault(id).0,
+            self.derive_chamber_position_info(id).0,
+            self.derive_chamber_balance_account(id).0,
+            self.derive_chamber_balance_metadata(id).0,
+        )
+    }
+
+    pub fn get_base_ata(&self) -> Pubkey {
+        get_associated_token_address(&self.authority, &self.farm_config.base_token_mint)
+    }
+
+    pub fn get_quote_ata(&self) -> Pubkey {
+        get_associated_token_address(&self.authority, &self.farm_config.quote_token_mint)
+    }
+
+    pub fn get_treasury_base_ata(&self) -> Pubkey {
+        get_associated_token_address(&self.treasury, &self.farm_config.base_token_mint)
+    }
+
+    pub fn get_treasury_quote_ata(&self) -> Pubkey {
+        get_associated_token_address(&self.treasury, &self.farm_config.quote_token_mint)
+    }
+
+    pub fn get_tulip_ata(&self, id: usize) -> Pubkey {
+        get_associated_token_address(
+            &self.derive_chamber_obligation_vault(id).0,
+            &tulip_mint::id(),
+        )
+    }
+
+    pub fn get_lp_ata(&self, id: usize) -> Pubkey {
+        get_associated_token_address(
+            &self.derive_chamber_obligation_vault(id).0,
+            &self.farm_config.lp_mint,
+        )
+    }
+
+    /// AMM pool chamber provides liquidity to.
+    pub fn get_pool(&self) -> ChamberPool {
+        ChamberPool {
+            amm_id: self.farm_config.amm_id,
+            coin_token_account: self.farm_config.amm_coin_account,
+            pc_token_account: self.farm_config.amm_pc_account,
+            lp_mint: self.farm_config.lp_mint,
+        }
+    }
+
+    /// Remaining accounts of `InitializeChamberStrategy`.
+    pub fn get_strategy_remaining_accounts(&self) -> Vec<AccountMeta> {
+        let (chamber_farm, _) = self.derive_chamber_farm();
+
+        let (chamber_farm_obligation_0, chamber_obligation_vault_0, _, _, _) =
+            self.derive_chamber_all(0);
+
+        let (chamber_farm_obligation_1, chamber_obligation_vault_1, _, _, _) =
+            self.derive_chamber_all(1);
+
+        vec![
+            AccountMeta::new_readonly(self.farm_config.global, false),
+            AccountMeta::new(chamber_farm, false),
+            AccountMeta::new(chamber_farm_obligation_0, false),
+            AccountMeta::new(chamber_farm_obligation_1, false),
+            AccountMeta::new(chamber_obligation_vault_0, false),
+            AccountMeta::new(chamber_obligation_vault_1, false),
+            AccountMeta::new(self.farm_config.lending_market, false),
+            AccountMeta::new_readonly(self.farm_config.account, false),
+            AccountMeta::new(self.get_lp_ata(0), false),
+            AccountMeta::new(self.get_lp_ata(1), false),
+            AccountMeta::new_readonly(self.farm_config.lp_mint, false),
+            AccountMeta::new(self.get_tulip_ata(0), false),
+            AccountMeta::new(self.get_tulip_ata(1), false),
+            AccountMeta::new_readonly(tulip_mint::id(), false),
+            AccountMeta::new_readonly(self.farm_config.lending_program, false),
+            AccountMeta::new_readonly(tulipv2_sdk_levfarm::ID, false),
+            AccountMeta::new_readonly(self.farm_config.solfarm_vault_program, false),
+        ]
+    }
+
+    /// Remaining accounts of `DepositChamber`, `ProcessChamberEpoch` and `RebalanceChamber`
+    /// borrowing base.
+    pub fn get_deposit_remaining_accounts(&self) -> Vec<AccountMeta> {
+        let (chamber_farm, _) = self.derive_chamber_farm();
+
+        let (chamber_farm_obligation_0, _, chamber_position_info_0, _, _) =
+            self.derive_chamber_all(0);
+
+        let (chamber_farm_obligation_1, _, chamber_position_info_1, _, _) =
+            self.derive_chamber_all(1);
+
+        vec![
+            AccountMeta::new(chamber_farm, false),
+            AccountMeta::new_readonly(self.farm_config.account, false),
+            AccountMeta::new(chamber_farm_obligation_0, false),
+            AccountMeta::new(chamber_farm_obligation_1, false),
+            AccountMeta::new(self.farm_config.base_token_account, false),
+            AccountMeta::new(self.farm_config.quote_token_account, false),
+            AccountMeta::new(self.farm_config.base_reserve, false),
+            AccountMeta::new(self.farm_config.quote_reserve, false),
+            AccountMeta::new_readonly(self.farm_config.coin_price_account, false),
+            AccountMeta::new_readonly(self.farm_config.pc_price_account, false),
+            AccountMeta::new_readonly(self.farm_config.lending_market, false),
+            AccountMeta::new_readonly(self.farm_config.lending_market_authority, false),
+            AccountMeta::new_readonly(self.farm_config.lending_program, false),
+            AccountMeta::new(self.base_liquidity_supply_token_account, false),
+            AccountMeta::new(self.quote_liquidity_supply_token_account, false),
+            AccountMeta::new(self.farm_config.coin_reserve_fee_receiver, false),
+            AccountMeta::new(self.farm_config.pc_reserve_fee_receiver, false),
+            AccountMeta::new_readonly(BORROW_AUTHORIZER, false),
+            AccountMeta::new_readonly(self.farm_config.lp_price_account, false),
+            AccountMeta::new(self.farm_config.vault_account, false),
+            AccountMeta::new(chamber_position_info_0, false),
+            AccountMeta::new(chamber_position_info_1, false),
+            AccountMeta::new_readonly(tulipv2_sdk_levfarm::ID, false),
+        ]
+    }
+
+    /// Remaining accounts of `CrankChamber` swap and add liquidity steps.
+    pub fn get_liquidity_remaining_accounts(&self) -> Vec<AccountMeta> {
+        let (chamber_farm, _) = self.derive_chamber_farm();
+
+        let (chamber_farm_obligation_0, _, chamber_position_info_0, _, _) =
+            self.derive_chamber_all(0);
+
+        let (chamber_farm_obligation_1, _, chamber_position_info_1, _, _) =
+            self.derive_chamber_all(1);
+
+        vec![
+            AccountMeta::new(self.farm_config.account, false),
+            AccountMeta::new(chamber_farm, false),
+            AccountMeta::new(chamber_farm_obligation_0, false),
+            AccountMeta::new(chamber_farm_obligation_1, false),
+            AccountMeta::new(self.farm_config.amm_id, false),
+            AccountMeta::new(self.market_amm_authority, false),
+            AccountMeta::new(self.farm_config.amm_open_orders, false),
+            AccountMeta::new(self.farm_config.amm_quantities_or_target_orders, false),
+            AccountMeta::new(self.farm_config.amm_coin_account, false),
+            AccountMeta::new(self.farm_config.amm_pc_account, false),
+            AccountMeta::new_readonly(serum_program::id(), false),
+            AccountMeta::new(self.farm_config.serum_market, false),
+            AccountMeta::new(self.farm_config.serum_bids, false),
+            AccountMeta::new(self.farm_config.serum_asks, false),
+            AccountMeta::new(self.farm_config.serum_event_queue, false),
+            AccountMeta::new(self.farm_config.serum_coin_vault, false),
+            AccountMeta::new(self.farm_config.serum_pc_vault, false),
+            AccountMeta::new(self.market_vault_signer, false),
+            AccountMeta::new(self.farm_config.base_token_account, false),
+            AccountMeta::new(self.farm_config.quote_token_account, false),
+            AccountMeta::new_readonly(self.farm_config.lending_market, false),
+            AccountMeta::new_readonly(self.farm_config.lending_market_authority, false),
+            AccountMeta::new_readonly(self.farm_config.lending_program, false),
+            AccountMeta::new(chamber_position_info_0, false),
+            AccountMeta::new(chamber_position_info_1, false),
+            AccountMeta::new(self.farm_config.lp_mint, false),
+            AccountMeta::new(self.get_lp_ata(0), false),
+            AccountMeta::new(self.get_lp_ata(1), false),
+            AccountMeta::new_readonly(self.farm_config.lp_price_account, false),
+            AccountMeta::new_readonly(tulipv2_sdk_levfarm::ID, false),
+            AccountMeta::new_readonly(RAYDIUM_LIQUIDITY_V4, false),
+        ]
+    }
+
+    /// Remaining accounts of `CrankChamber` stake step, `DeleverageChamber` and
+    /// `RebalanceChamber` repaying base debt, the latter two followed by position infos.
+    pub fn get_vault_remaining_accounts(&self) -> Vec<AccountMeta> {
+        let (chamber_farm, _) = self.derive_chamber_farm();
+
+        let (
+            chamber_farm_obligation_0,
+            chamber_farm_obligation_vault_0,
+            _,
+            chamber_balance_account_0,
+            chamber_balance_metadata_0,
+        ) = self.derive_chamber_all(0);
+
+        let (
+            chamber_farm_obligation_1,
+            chamber_farm_obligation_vault_1,
+            _,
+            chamber_balance_account_1,
+            chamber_balance_metadata_1,
+        ) = self.derive_chamber_all(1);
+
+        let lp_token_account =
+            get_associated_token_address(&self.vault_pda, &self.farm_config.lp_mint);
+
+        vec![
+            AccountMeta::new(chamber_farm, false),
+            AccountMeta::new(chamber_farm_obligation_vault_0, false),
+            AccountMeta::new(chamber_farm_obligation_vault_1, false),
+            AccountMeta::new(self.farm_config.account, false),
+            AccountMeta::new_readonly(self.farm_config.solfarm_vault_program, false),
+            AccountMeta::new(self.get_lp_ata(0), false),
+            AccountMeta::new(self.get_lp_ata(1), false),
+            AccountMeta::new(self.vault_pda, false),
+            AccountMeta::new(self.farm_config.vault_account, false),
+            AccountMeta::new(lp_token_account, false),
+            AccountMeta::new(chamber_balance_account_0, false),
+            AccountMeta::new(chamber_balance_account_1, false),
+            AccountMeta::new_readonly(RAYDIUM_STAKE_V5, false),
+            AccountMeta::new(self.vault_farm, false),
+            AccountMeta::new(self.vault_pool_authority, false),
+            AccountMeta::new(self.vault_info, false),
+            AccountMeta::new(self.vault_lp_token_account, false),
+            AccountMeta::new(self.vault_reward_a_token_account, false),
+            AccountMeta::new(self.vault_pool_reward_a_token_account, false),
+            AccountMeta::new(self.vault_reward_b_token_account, false),
+            AccountMeta::new(self.vault_pool_reward_b_token_account, false),
+            AccountMeta::new(chamber_balance_metadata_0, false),
+            AccountMeta::new(chamber_balance_metadata_1, false),
+            AccountMeta::new_readonly(self.farm_config.lending_market, false),
+            AccountMeta::new(chamber_farm_obligation_0, false),
+            AccountMeta::new(chamber_farm_obligation_1, false),
+            AccountMeta::new_readonly(self.farm_config.lending_market_authority, false),
+            AccountMeta::new_readonly(self.farm_config.lending_program, false),
+            AccountMeta::new_readonly(tulipv2_sdk_levfarm::ID, false),
+        ]
+    }
+
+    /// Raydium `SwapBaseIn` route of chamber pool, usable with `SwapChamber`.
+    pub fn get_raydium_swap_route(&self, source: Pubkey, destination: Pubkey) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new(self.farm_config.amm_id, false),
+            AccountMeta::new_readonly(self.market_amm_authority, false),
+            AccountMeta::new(self.farm_config.amm_open_orders, false),
+            AccountMeta::new(self.farm_config.amm_quantities_or_target_orders, false),
+            AccountMeta::new(self.farm_config.amm_coin_account, false),
+            AccountMeta::new(self.farm_config.amm_pc_account, false),
+            AccountMeta::new_readonly(serum_program::id(), false),
+            AccountMeta::new(self.farm_config.serum_market, false),
+            AccountMeta::new(self.farm_config.serum_bids, false),
+            AccountMeta::new(self.farm_config.serum_asks, false),
+            AccountMeta::new(self.farm_config.serum_event_queue, false),
+            AccountMeta::new(self.farm_config.serum_coin_vault, false),
+            AccountMeta::new(self.farm_config.serum_pc_vault, false),
+            AccountMeta::new_readonly(self.market_vault_signer, false),
+            AccountMeta::new(source, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(self.authority, false),
+        ]
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::Pubkey;
+use cetra_chamber::{
+    state::{ChamberOperation, ProtocolType},
+    utils::{derive_chamber_address, derive_chamber_authority, derive_user_position},
+};
+use cetra_chamber_client::{instruction, TulipChamber};
+
+#[test]
+fn chamber_addresses_match_program_derivation() {
+    let chamber = TulipChamber::new_sol_usdc_raydium(1, Pubkey::new_unique());
+
+    let (pubkey, bump) = derive_chamber_address(&chamber.farm_config.account, 1);
+    let (authority, authority_bump) = derive_chamber_authority(&pubkey);
+
+    assert_eq!(chamber.pubkey, pubkey);
+    assert_eq!(chamber.bump, bump);
+    assert_eq!(chamber.authority, authority);
+    assert_eq!(chamber.authority_bump, authority_bump);
+    assert_eq!(chamber.protocol_type, ProtocolType::Tulip);
+}
+
+#[test]
+fn deposit_chamber_ix_appends_remaining_accounts() {
+    let chamber = TulipChamber::new_sol_usdc_raydium(0, Pubkey::new_unique());
+    let user = Pubkey::new_unique();
+
+    let ix = instruction::deposit_chamber_ix(&chamber, &user, 1, 2);
+
+    assert_eq!(ix.program_id, cetra_chamber::id());
+    assert_eq!(ix.accounts.len(), 10 + 23);
+    assert_eq!(
+        ix.accounts[0].pubkey,
+        derive_user_position(&user, &chamber.pubkey).0
+    );
+    assert_eq!(ix.accounts[1].pubkey, chamber.pubkey);
+    assert!(ix.accounts[5].is_signer);
+    assert_eq!(ix.accounts[10].pubkey, chamber.derive_chamber_farm().0);
+    assert_eq!(ix.accounts[32].pubkey, tulipv2_sdk_levfarm::ID);
+}
+
+#[test]
+fn crank_chamber_ix_follows_operation() {
+    let chamber = TulipChamber::new_sol_usdc_raydium(0, Pubkey::new_unique());
+    let payer = Pubkey::new_unique();
+
+    // Swap and add liquidity steps
+    for operation in [ChamberOperation::Borrowed, ChamberOperation::Swapped] {
+        let ix = instruction::crank_chamber_ix(&chamber, &payer, operation);
+        assert_eq!(ix.accounts.len(), 7 + 31);
+    }
+
+    // Stake step
+    let ix = instruction::crank_chamber_ix(&chamber, &payer, ChamberOperation::LiquidityAdded);
+    assert_eq!(ix.accounts.len(), 7 + 29);
+}
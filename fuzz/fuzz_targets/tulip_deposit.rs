@@ -0,0 +1,79 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use cetra_chamber::math::{self, TULIP_DEPOSIT_LEVERAGE};
+use libfuzzer_sys::fuzz_target;
+use tulipv2_sdk_common::math::{
+    common::{TryAdd, TryDiv, TryMul},
+    decimal::Decimal,
+};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    base_amount: u64,
+    quote_amount: u64,
+    extra_base_amount: u64,
+    extra_quote_amount: u64,
+    base_price: u64,
+    base_price_expo: u8,
+    quote_price: u64,
+    quote_price_expo: u8,
+    base_decimals: u8,
+    quote_decimals: u8,
+}
+
+fn price(units: u64, expo: u8) -> Decimal {
+    Decimal::from(units)
+        .try_div(10u64.pow((expo % 19) as u32))
+        .unwrap()
+}
+
+fuzz_target!(|input: Input| {
+    let base_price = price(input.base_price, input.base_price_expo);
+    let quote_price = price(input.quote_price, input.quote_price_expo);
+
+    let legs = match math::calculate_tulip_deposit(
+        input.base_amount,
+        input.quote_amount,
+        base_price,
+        quote_price,
+        input.base_decimals,
+        input.quote_decimals,
+    ) {
+        Ok(legs) => legs,
+        Err(_) => return,
+    };
+
+    // Leverage of each leg never exceeds maximum
+    for leg in legs.iter() {
+        let deposit_value =
+            math::calculate_value(leg.base_collateral, base_price, input.base_decimals)
+                .unwrap()
+                .try_add(
+                    math::calculate_value(leg.quote_collateral, quote_price, input.quote_decimals)
+                        .unwrap(),
+                )
+                .unwrap();
+        let borrow_value = math::calculate_value(leg.base_debt, base_price, input.base_decimals)
+            .unwrap()
+            .try_add(
+                math::calculate_value(leg.quote_debt, quote_price, input.quote_decimals).unwrap(),
+            )
+            .unwrap();
+
+        assert!(borrow_value <= deposit_value.try_mul(TULIP_DEPOSIT_LEVERAGE - 1).unwrap());
+    }
+
+    // Larger deposit never borrows less
+    if let Ok(larger_legs) = math::calculate_tulip_deposit(
+        input.base_amount.saturating_add(input.extra_base_amount),
+        input.quote_amount.saturating_add(input.extra_quote_amount),
+        base_price,
+        quote_price,
+        input.base_decimals,
+        input.quote_decimals,
+    ) {
+        assert!(legs[0].quote_debt <= larger_legs[0].quote_debt);
+        assert!(legs[1].base_debt <= larger_legs[1].base_debt);
+    }
+});
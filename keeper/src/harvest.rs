@@ -0,0 +1,197 @@
+//! Module convert farm rewards into chamber tokens through Raydium AMM v4 pools.
+
+use anchor_lang::{
+    prelude::AccountMeta, solana_program::instruction::Instruction, AccountDeserialize,
+};
+use anchor_spl::{associated_token::get_associated_token_address, token::TokenAccount};
+use anyhow::{anyhow, bail, Result};
+use cetra_chamber::state::Chamber;
+use cetra_chamber_client::{instruction, serum_program, TulipChamber};
+use serde::{de, Deserialize, Deserializer};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::{fs, path::Path, str::FromStr};
+use tulipv2_sdk_common::config::RAYDIUM_LIQUIDITY_V4;
+
+/// Raydium AMM v4 trade fee.
+const RAYDIUM_FEE_BPS: u64 = 25;
+
+/// Raydium AMM v4 `SwapBaseIn` instruction tag.
+const RAYDIUM_SWAP_BASE_IN: u8 = 9;
+
+fn load_token_account(client: &RpcClient, pubkey: &Pubkey) -> Result<TokenAccount> {
+    let data = client.get_account_data(pubkey)?;
+
+    Ok(TokenAccount::try_deserialize(&mut data.as_ref())?)
+}
+
+fn deserialize_pubkey<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    let value = String::deserialize(deserializer)?;
+
+    Pubkey::from_str(&value).map_err(de::Error::custom)
+}
+
+/// Raydium pool of reward token against chamber base or quote token, loaded from JSON
+/// with base58 addresses.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RewardPool {
+    /// Reward token, harvested to associated token account of chamber authority.
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub reward_mint: Pubkey,
+
+    /// Chamber base or quote token, which reward is converted into.
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub destination_mint: Pubkey,
+
+    /// Balance below which reward isn't worth transaction fee.
+    pub minimum_amount: u64,
+
+    pub slippage_bps: u64,
+
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub amm_id: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub amm_authority: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub amm_open_orders: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub amm_target_orders: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub amm_coin_account: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub amm_pc_account: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub serum_market: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub serum_bids: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub serum_asks: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub serum_event_queue: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub serum_coin_vault: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub serum_pc_vault: Pubkey,
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub serum_vault_signer: Pubkey,
+}
+
+impl RewardPool {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let data = fs::read(path.as_ref())?;
+
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// Pool reserves of reward and destination tokens.
+    pub fn fetch_reserves(&self, client: &RpcClient) -> Result<(u64, u64)> {
+        let coin = load_token_account(client, &self.amm_coin_account)?;
+        let pc = load_token_account(client, &self.amm_pc_account)?;
+
+        if coin.mint == self.reward_mint && pc.mint == self.destination_mint {
+            Ok((coin.amount, pc.amount))
+        } else if pc.mint == self.reward_mint && coin.mint == self.destination_mint {
+            Ok((pc.amount, coin.amount))
+        } else {
+            bail!("pool {} doesn't trade reward for destination", self.amm_id)
+        }
+    }
+
+    pub fn get_reward_account(&self, chamber: &TulipChamber) -> Pubkey {
+        get_associated_token_address(&chamber.authority, &self.reward_mint)
+    }
+
+    /// Raydium `SwapBaseIn` route from reward account into chamber token account.
+    pub fn get_swap_route(
+        &self,
+        source: Pubkey,
+        destination: Pubkey,
+        authority: Pubkey,
+    ) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new(self.amm_id, false),
+            AccountMeta::new_readonly(self.amm_authority, false),
+            AccountMeta::new(self.amm_open_orders, false),
+            AccountMeta::new(self.amm_target_orders, false),
+            AccountMeta::new(self.amm_coin_account, false),
+            AccountMeta::new(self.amm_pc_account, false),
+            AccountMeta::new_readonly(serum_program::id(), false),
+            AccountMeta::new(self.serum_market, false),
+            AccountMeta::new(self.serum_bids, false),
+            AccountMeta::new(self.serum_asks, false),
+            AccountMeta::new(self.serum_event_queue, false),
+            AccountMeta::new(self.serum_coin_vault, false),
+            AccountMeta::new(self.serum_pc_vault, false),
+            AccountMeta::new_readonly(self.serum_vault_signer, false),
+            AccountMeta::new(source, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(authority, false),
+        ]
+    }
+
+    /// `SwapChamber` of `amount` reward signed by chamber keeper, `reserves` of reward and
+    /// destination tokens in pool bound received amount.
+    pub fn swap_chamber_ix(
+        &self,
+        chamber: &TulipChamber,
+        state: &Chamber,
+        keeper: &Pubkey,
+        amount: u64,
+        reserves: (u64, u64),
+    ) -> Result<Instruction> {
+        // Program checks route against chamber swap program only
+        if state.config.swap_program != RAYDIUM_LIQUIDITY_V4 {
+            bail!("chamber swap program isn't Raydium AMM v4");
+        }
+
+        let destination = if self.destination_mint == state.base_mint {
+            state.base_ata
+        } else if self.destination_mint == state.quote_mint {
+            state.quote_ata
+        } else {
+            bail!("reward pool destination isn't chamber token");
+        };
+
+        let minimum_amount_out = calculate_minimum_amount_out(amount, reserves, self.slippage_bps)?;
+        let source = self.get_reward_account(chamber);
+
+        let mut data = vec![RAYDIUM_SWAP_BASE_IN];
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+
+        Ok(instruction::swap_chamber_ix(
+            chamber,
+            keeper,
+            state.epoch,
+            &source,
+            &destination,
+            &RAYDIUM_LIQUIDITY_V4,
+            self.get_swap_route(source, destination, chamber.authority),
+            amount,
+            minimum_amount_out,
+            data,
+        ))
+    }
+}
+
+/// Constant product output of `amount_in` net of Raydium fee, reduced by `slippage_bps`.
+/// `reserves` are amounts of input and output tokens in pool.
+pub fn calculate_minimum_amount_out(
+    amount_in: u64,
+    reserves: (u64, u64),
+    slippage_bps: u64,
+) -> Result<u64> {
+    let (reserve_in, reserve_out) = (reserves.0 as u128, reserves.1 as u128);
+    let amount_in = amount_in as u128 * (10000 - RAYDIUM_FEE_BPS as u128) / 10000;
+
+    let amount_out = reserve_out
+        .checked_mul(amount_in)
+        .and_then(|value| value.checked_div(reserve_in.checked_add(amount_in)?))
+        .filter(|amount_out| *amount_out > 0)
+        .ok_or_else(|| anyhow!("reward pool too shallow"))?;
+
+    let minimum_amount_out = amount_out * (10000 - slippage_bps.min(10000) as u128) / 10000;
+
+    Ok(u64::try_from(minimum_amount_out)?)
+}
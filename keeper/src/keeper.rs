@@ -0,0 +1,191 @@
+//! Module run polling loop and submit work transactions.
+
+use crate::{
+    classify_error, detect_work, increase_compute_units, ChamberSnapshot, Retry, RewardPool, Work,
+    WorkConfig,
+};
+use anyhow::{bail, Result};
+use cetra_chamber::state::ChamberOperation;
+use cetra_chamber_client::{instruction, TulipChamber};
+use log::{error, info, warn};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    compute_budget::ComputeBudgetInstruction,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+use std::{thread, time::Duration};
+
+/// Bounds work of single chamber per poll, e.g. rebalance which doesn't converge.
+const MAX_WORK_PER_TICK: usize = 8;
+
+pub struct KeeperConfig {
+    /// Delay between polls of all chambers.
+    pub interval: Duration,
+
+    /// Attempts to send single work transaction.
+    pub max_retries: u32,
+
+    /// Percent of riskier obligation unwound by single deleverage.
+    pub withdraw_percent: u8,
+
+    /// Additional fee in lamports paid for requested compute units.
+    pub priority_fee: u32,
+
+    /// Minimum value of idle funds in quote token units worth deploying.
+    pub min_idle_value: u64,
+
+    /// Pools converting farm rewards into chamber tokens.
+    pub reward_pools: Vec<RewardPool>,
+}
+
+pub struct Keeper {
+    pub client: RpcClient,
+    pub payer: Keypair,
+    pub chambers: Vec<TulipChamber>,
+    pub config: KeeperConfig,
+}
+
+impl Keeper {
+    pub fn new(
+        url: String,
+        payer: Keypair,
+        chambers: Vec<TulipChamber>,
+        config: KeeperConfig,
+    ) -> Self {
+        Keeper {
+            client: RpcClient::new_with_commitment(url, CommitmentConfig::confirmed()),
+            payer,
+            chambers,
+            config,
+        }
+    }
+
+    pub fn run(&self) -> ! {
+        loop {
+            for chamber in &self.chambers {
+                if let Err(e) = self.tick(chamber) {
+                    error!("chamber {}: {:?}", chamber.pubkey, e);
+                }
+            }
+
+            thread::sleep(self.config.interval);
+        }
+    }
+
+    /// Submit pending work of chamber, each work is re-detected from fresh snapshot.
+    pub fn tick(&self, chamber: &TulipChamber) -> Result<()> {
+        let work_config = WorkConfig {
+            min_idle_value: self.config.min_idle_value,
+            min_reward_amounts: self
+                .config
+                .reward_pools
+                .iter()
+                .map(|pool| pool.minimum_amount)
+                .collect(),
+        };
+
+        for _ in 0..MAX_WORK_PER_TICK {
+            let snapshot =
+                ChamberSnapshot::fetch(&self.client, chamber, &self.config.reward_pools)?;
+
+            let work = match detect_work(&snapshot, &work_config)? {
+                Some(work) => work,
+                None => return Ok(()),
+            };
+
+            let signature = self.submit(chamber, &snapshot, work)?;
+            info!("chamber {}: {:?} done: {}", chamber.pubkey, work, signature);
+
+            // Deleverage and repayment are re-checked on the next poll with updated prices,
+            // while epoch processing and borrowing start deploy flow, which is cranked right away
+            if let Work::Deleverage { .. } | Work::Rebalance { repay: true, .. } = work {
+                return Ok(());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn submit(
+        &self,
+        chamber: &TulipChamber,
+        snapshot: &ChamberSnapshot,
+        work: Work,
+    ) -> Result<Signature> {
+        let payer = self.payer.pubkey();
+
+        let ix = match work {
+            Work::Crank(operation) => instruction::crank_chamber_ix(chamber, &payer, operation),
+            Work::Deleverage { .. } => {
+                instruction::deleverage_chamber_ix(chamber, &payer, self.config.withdraw_percent)
+            }
+            Work::Restake { .. } => {
+                instruction::crank_chamber_ix(chamber, &payer, ChamberOperation::Staked)
+            }
+            Work::ProcessEpoch | Work::DeployIdle { .. } => {
+                instruction::process_chamber_epoch_ix(chamber, &payer, snapshot.chamber.epoch)
+            }
+            Work::Rebalance { repay, .. } => {
+                instruction::rebalance_chamber_ix(chamber, &payer, repay)
+            }
+            Work::Harvest { pool, amount } => {
+                let pool = &self.config.reward_pools[pool];
+                let reserves = pool.fetch_reserves(&self.client)?;
+
+                pool.swap_chamber_ix(chamber, &snapshot.chamber, &payer, amount, reserves)?
+            }
+        };
+
+        let mut compute_units = work.compute_units();
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+
+            let instructions = [
+                ComputeBudgetInstruction::request_units(compute_units, self.config.priority_fee),
+                ix.clone(),
+            ];
+
+            // Fresh blockhash on every attempt, expired one is the usual failure
+            let result = self.client.get_latest_blockhash().and_then(|blockhash| {
+                let tx = Transaction::new_signed_with_payer(
+                    &instructions,
+                    Some(&payer),
+                    &[&self.payer],
+                    blockhash,
+                );
+
+                self.client.send_and_confirm_transaction(&tx)
+            });
+
+            let e = match result {
+                Ok(signature) => return Ok(signature),
+                Err(e) => e,
+            };
+
+            // Anything else fails the same way until the next snapshot
+            let retry = match classify_error(&e) {
+                Some(retry) if attempt < self.config.max_retries => retry,
+                _ => bail!("{:?} failed after {} attempts: {}", work, attempt, e),
+            };
+
+            warn!(
+                "chamber {}: {:?} attempt {} failed: {}",
+                chamber.pubkey, work, attempt, e
+            );
+
+            match retry {
+                Retry::Blockhash => {
+                    thread::sleep(Duration::from_millis(500 * 2u64.pow(attempt - 1)));
+                }
+                Retry::ComputeBudget => match increase_compute_units(compute_units) {
+                    Some(units) => compute_units = units,
+                    None => bail!("{:?} exceeds {} compute units", work, compute_units),
+                },
+            }
+        }
+    }
+}
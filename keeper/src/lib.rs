@@ -0,0 +1,17 @@
+//! Keeper service, which polls chambers and submits pending cranks.
+//!
+//! Detection of pending work is pure function of `ChamberSnapshot`, so it can be tested
+//! without RPC, while `Keeper` fetches snapshots and sends transactions, retrying expired
+//! blockhash and exhausted compute budget.
+
+mod harvest;
+mod keeper;
+mod retry;
+mod snapshot;
+mod work;
+
+pub use harvest::*;
+pub use keeper::*;
+pub use retry::*;
+pub use snapshot::*;
+pub use work::*;
@@ -0,0 +1,103 @@
+//! Keeper daemon for `Chamber` settlement, epoch and rebalance cranks.
+
+use anyhow::{bail, Result};
+use cetra_chamber_client::TulipChamber;
+use chamber_keeper::{Keeper, KeeperConfig, RewardPool};
+use clap::{ArgEnum, Parser};
+use solana_sdk::{pubkey::Pubkey, signature::read_keypair_file};
+use std::time::Duration;
+
+#[derive(Parser)]
+#[clap(name = "chamber-keeper", version, about)]
+struct Cli {
+    /// RPC URL, e.g. `solana-test-validator`.
+    #[clap(long, short, default_value = "http://localhost:8899")]
+    url: String,
+
+    /// Keeper keypair, which pays for cranks.
+    #[clap(long, short, default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// Leveraged farm pair.
+    #[clap(long, arg_enum, default_value = "sol-usdc-raydium")]
+    farm: Farm,
+
+    /// Chamber indexes on leveraged farm to serve.
+    #[clap(long = "index", default_value = "0")]
+    indexes: Vec<u8>,
+
+    /// Poll interval in seconds.
+    #[clap(long, default_value = "30")]
+    interval: u64,
+
+    #[clap(long, default_value = "5")]
+    max_retries: u32,
+
+    #[clap(long, default_value = "25")]
+    withdraw_percent: u8,
+
+    #[clap(long, default_value = "0")]
+    priority_fee: u32,
+
+    /// Minimum value of idle chamber funds in quote token units worth deploying.
+    #[clap(long, default_value = "10000000")]
+    min_idle_value: u64,
+
+    /// JSON description of Raydium pool converting reward into chamber token.
+    #[clap(long = "reward-pool")]
+    reward_pools: Vec<String>,
+}
+
+#[derive(Clone, Copy, ArgEnum)]
+enum Farm {
+    SolUsdcRaydium,
+}
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let cli = Cli::parse();
+
+    let keypair_path = match cli.keypair.strip_prefix("~/") {
+        Some(path) => format!("{}/{}", std::env::var("HOME")?, path),
+        None => cli.keypair.clone(),
+    };
+    let payer = match read_keypair_file(&keypair_path) {
+        Ok(payer) => payer,
+        Err(e) => bail!("failed to read keypair {}: {}", keypair_path, e),
+    };
+
+    let reward_pools = cli
+        .reward_pools
+        .iter()
+        .map(|path| match RewardPool::load(path) {
+            Ok(pool) => Ok(pool),
+            Err(e) => bail!("failed to read reward pool {}: {}", path, e),
+        })
+        .collect::<Result<_>>()?;
+
+    // Treasury isn't used by keeper instructions
+    let chambers = cli
+        .indexes
+        .iter()
+        .map(|index| match cli.farm {
+            Farm::SolUsdcRaydium => TulipChamber::new_sol_usdc_raydium(*index, Pubkey::default()),
+        })
+        .collect();
+
+    let keeper = Keeper::new(
+        cli.url,
+        payer,
+        chambers,
+        KeeperConfig {
+            interval: Duration::from_secs(cli.interval),
+            max_retries: cli.max_retries,
+            withdraw_percent: cli.withdraw_percent,
+            priority_fee: cli.priority_fee,
+            min_idle_value: cli.min_idle_value,
+            reward_pools,
+        },
+    );
+
+    keeper.run()
+}
@@ -0,0 +1,52 @@
+//! Module classify send errors, which are worth another attempt.
+
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_request::RpcError,
+};
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+/// Compute units can't be requested over this limit.
+pub const MAX_COMPUTE_UNITS: u32 = 1400000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Retry {
+    /// Blockhash expired before transaction landed, resent with fresh one.
+    Blockhash,
+
+    /// Requested compute units ran out, resent with more of them.
+    ComputeBudget,
+}
+
+/// Program errors, e.g. stale oracle or already processed work, fail the same way on
+/// every attempt, so only expiry and budget are retried.
+pub fn classify_error(error: &ClientError) -> Option<Retry> {
+    match error.get_transaction_error() {
+        Some(TransactionError::BlockhashNotFound) => return Some(Retry::Blockhash),
+        Some(TransactionError::InstructionError(
+            _,
+            InstructionError::ComputationalBudgetExceeded,
+        )) => return Some(Retry::ComputeBudget),
+        Some(_) => return None,
+        None => {}
+    }
+
+    // Confirmation gives up once blockhash of sent transaction expires
+    match error.kind() {
+        ClientErrorKind::RpcError(RpcError::ForUser(message))
+            if message.starts_with("unable to confirm transaction") =>
+        {
+            Some(Retry::Blockhash)
+        }
+        _ => None,
+    }
+}
+
+/// Compute units for the next attempt after budget error, `None` when already at limit.
+pub fn increase_compute_units(units: u32) -> Option<u32> {
+    if units >= MAX_COMPUTE_UNITS {
+        return None;
+    }
+
+    Some((units.saturating_mul(3) / 2).min(MAX_COMPUTE_UNITS))
+}
@@ -0,0 +1,102 @@
+//! Module fetch on-chain state required to detect pending work.
+
+use crate::RewardPool;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::{Mint, TokenAccount};
+use anyhow::Result;
+use cetra_chamber::{
+    state::{Chamber, ChamberEpoch, TulipObligation},
+    utils::derive_chamber_epoch,
+};
+use cetra_chamber_client::TulipChamber;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+
+pub struct ChamberSnapshot {
+    pub chamber: Chamber,
+
+    /// Current epoch of `chamber`.
+    pub epoch: ChamberEpoch,
+
+    /// LP amount held by obligation vaults outside of Raydium staking vault.
+    pub lp_amounts: [u64; 2],
+
+    /// Chamber base token account balance, including reserved user balances.
+    pub base_ata_amount: u64,
+
+    /// Chamber quote token account balance, including reserved user balances and claims.
+    pub quote_ata_amount: u64,
+
+    /// Base reserve of chamber pool.
+    pub pool_base_amount: u64,
+
+    /// Quote reserve of chamber pool, prices base of idle funds.
+    pub pool_quote_amount: u64,
+
+    /// LP supply of chamber pool.
+    pub lp_supply: u64,
+
+    pub obligations: [TulipObligation; 2],
+
+    /// Reward token balances of chamber authority, one per configured reward pool.
+    pub reward_amounts: Vec<u64>,
+}
+
+fn load<T: AccountDeserialize>(client: &RpcClient, pubkey: &Pubkey) -> Result<T> {
+    let data = client.get_account_data(pubkey)?;
+
+    Ok(T::try_deserialize(&mut data.as_ref())?)
+}
+
+/// Token account is created by levfarm lazily, so missing one holds nothing.
+fn load_token_amount(client: &RpcClient, pubkey: &Pubkey) -> Result<u64> {
+    match client.get_account_data(pubkey) {
+        Ok(data) => Ok(TokenAccount::try_deserialize(&mut data.as_ref())?.amount),
+        Err(_) => Ok(0),
+    }
+}
+
+impl ChamberSnapshot {
+    pub fn fetch(
+        client: &RpcClient,
+        chamber: &TulipChamber,
+        reward_pools: &[RewardPool],
+    ) -> Result<Self> {
+        let state: Chamber = load(client, &chamber.pubkey)?;
+        let epoch: ChamberEpoch = load(
+            client,
+            &derive_chamber_epoch(&chamber.pubkey, state.epoch).0,
+        )?;
+
+        let obligation_0 = client.get_account_data(&chamber.derive_chamber_obligation(0).0)?;
+        let obligation_1 = client.get_account_data(&chamber.derive_chamber_obligation(1).0)?;
+
+        let base_ata_amount = load_token_amount(client, &state.base_ata)?;
+        let quote_ata_amount = load_token_amount(client, &state.quote_ata)?;
+        let pool_base_amount = load_token_amount(client, &state.pool.coin_token_account)?;
+        let pool_quote_amount = load_token_amount(client, &state.pool.pc_token_account)?;
+        let lp_mint: Mint = load(client, &state.pool.lp_mint)?;
+
+        Ok(ChamberSnapshot {
+            chamber: state,
+            epoch,
+            lp_amounts: [
+                load_token_amount(client, &chamber.get_lp_ata(0))?,
+                load_token_amount(client, &chamber.get_lp_ata(1))?,
+            ],
+            base_ata_amount,
+            quote_ata_amount,
+            pool_base_amount,
+            pool_quote_amount,
+            lp_supply: lp_mint.supply,
+            obligations: [
+                TulipObligation::unpack(&obligation_0)?,
+                TulipObligation::unpack(&obligation_1)?,
+            ],
+            reward_amounts: reward_pools
+                .iter()
+                .map(|pool| load_token_amount(client, &pool.get_reward_account(chamber)))
+                .collect::<Result<_>>()?,
+        })
+    }
+}
@@ -0,0 +1,181 @@
+//! Module detect pending chamber work.
+
+use crate::ChamberSnapshot;
+use anyhow::{anyhow, Result};
+use cetra_chamber::{math, state::ChamberOperation};
+use tulipv2_sdk_common::config::RAYDIUM_LIQUIDITY_V4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Work {
+    /// Deploy flow is in-flight, borrowed funds or LP wait for the next step.
+    Crank(ChamberOperation),
+
+    /// Riskier obligation crossed configured LTV.
+    Deleverage { obligation: usize, ltv_bps: u64 },
+
+    /// LP left outside of vault of staked chamber, e.g. by unwinding.
+    Restake { lp_amount: u64 },
+
+    /// Current epoch holds queued deposits or withdrawals.
+    ProcessEpoch,
+
+    /// Settled chamber token accounts hold funds beyond reserved balances, which epoch
+    /// processing deploys.
+    DeployIdle { base_amount: u64, quote_amount: u64 },
+
+    /// Net base exposure drifted over configured threshold, `repay` when base debt
+    /// outgrows base liquidity.
+    Rebalance { drift_bps: u64, repay: bool },
+
+    /// Reward balance of configured reward `pool` is worth converting into chamber token.
+    Harvest { pool: usize, amount: u64 },
+}
+
+pub struct WorkConfig {
+    /// Minimum value of idle funds in quote token units worth deploying. Program deploys
+    /// only `math::MIN_IDLE_DEPLOY_VALUE`, so lower values are raised to it.
+    pub min_idle_value: u64,
+
+    /// Minimum reward amount worth harvesting, one per configured reward pool.
+    pub min_reward_amounts: Vec<u64>,
+}
+
+impl Work {
+    /// Compute units requested by work transaction, all of them CPI into levfarm.
+    pub fn compute_units(&self) -> u32 {
+        match self {
+            Work::Crank(_)
+            | Work::Deleverage { .. }
+            | Work::Restake { .. }
+            | Work::Rebalance { repay: true, .. } => 530000,
+            Work::ProcessEpoch | Work::DeployIdle { .. } => 400000,
+            Work::Rebalance { repay: false, .. } => 350000,
+            Work::Harvest { .. } => 250000,
+        }
+    }
+}
+
+/// Chamber token account balances beyond user balances, queued deposits and claims.
+fn free_amounts(snapshot: &ChamberSnapshot) -> (u64, u64) {
+    let chamber = &snapshot.chamber;
+    let epoch = &snapshot.epoch;
+
+    let base_amount = snapshot
+        .base_ata_amount
+        .saturating_sub(chamber.total_base_amount)
+        .saturating_sub(epoch.pending_base_amount);
+    let quote_amount = snapshot
+        .quote_ata_amount
+        .saturating_sub(chamber.total_quote_amount)
+        .saturating_sub(epoch.pending_quote_amount)
+        .saturating_sub(chamber.claimable_quote_amount);
+
+    (base_amount, quote_amount)
+}
+
+/// Value of `base_amount` and `quote_amount` in quote token units, base is priced by pool.
+fn calculate_idle_value(snapshot: &ChamberSnapshot, base_amount: u64, quote_amount: u64) -> u64 {
+    let base_value = if snapshot.pool_base_amount > 0 {
+        (base_amount as u128 * snapshot.pool_quote_amount as u128
+            / snapshot.pool_base_amount as u128)
+            .min(u64::MAX as u128) as u64
+    } else {
+        0
+    };
+
+    base_value.saturating_add(quote_amount)
+}
+
+/// Detect the most urgent work, program accepts only one deploy flow at a time.
+///
+/// Priority: finish in-flight flow, then protect obligations, then put stranded funds back
+/// to work and serve users, then restore hedge and finally harvest rewards.
+pub fn detect_work(snapshot: &ChamberSnapshot, config: &WorkConfig) -> Result<Option<Work>> {
+    let chamber = &snapshot.chamber;
+
+    let operation = chamber.operation().map_err(|e| anyhow!("{:?}", e))?;
+    if !operation.is_settled() {
+        return Ok(Some(Work::Crank(operation)));
+    }
+
+    if chamber.config.deleverage_ltv_bps > 0 {
+        let ltv_0 = snapshot.obligations[0].ltv_bps()?;
+        let ltv_1 = snapshot.obligations[1].ltv_bps()?;
+        let (obligation, ltv_bps) = if ltv_0 >= ltv_1 {
+            (0, ltv_0)
+        } else {
+            (1, ltv_1)
+        };
+
+        if ltv_bps > chamber.config.deleverage_ltv_bps as u64 {
+            return Ok(Some(Work::Deleverage {
+                obligation,
+                ltv_bps,
+            }));
+        }
+    }
+
+    let lp_amount: u64 = snapshot.lp_amounts.iter().sum();
+    if operation == ChamberOperation::Staked && lp_amount > 0 {
+        return Ok(Some(Work::Restake { lp_amount }));
+    }
+
+    let epoch = &snapshot.epoch;
+    if epoch.pending_base_amount > 0
+        || epoch.pending_quote_amount > 0
+        || epoch.pending_withdraw_shares > 0
+    {
+        return Ok(Some(Work::ProcessEpoch));
+    }
+
+    let (base_amount, quote_amount) = free_amounts(snapshot);
+    let min_idle_value = config
+        .min_idle_value
+        .max(math::MIN_IDLE_DEPLOY_VALUE * 10u64.pow(chamber.quote_decimals as u32));
+    if (base_amount > 0 || quote_amount > 0)
+        && calculate_idle_value(snapshot, base_amount, quote_amount) >= min_idle_value
+    {
+        return Ok(Some(Work::DeployIdle {
+            base_amount,
+            quote_amount,
+        }));
+    }
+
+    let (base_liquidity, base_debt) = math::calculate_base_exposure(
+        &chamber.legs,
+        &chamber.lp_amounts,
+        snapshot.pool_base_amount,
+        snapshot.lp_supply,
+    )
+    .map_err(|e| anyhow!("{:?}", e))?;
+    let drift_bps = math::calculate_exposure_drift_bps(base_liquidity, base_debt)
+        .map_err(|e| anyhow!("{:?}", e))?;
+
+    // Zero threshold disables rebalance, it would run on every rounding error
+    if chamber.config.rebalance_threshold_bps > 0
+        && drift_bps > chamber.config.rebalance_threshold_bps as u64
+    {
+        return Ok(Some(Work::Rebalance {
+            drift_bps,
+            repay: base_debt > base_liquidity,
+        }));
+    }
+
+    // Keeper swaps rewards only through chamber swap program
+    if chamber.config.swap_program == RAYDIUM_LIQUIDITY_V4 {
+        let pool = snapshot
+            .reward_amounts
+            .iter()
+            .zip(&config.min_reward_amounts)
+            .position(|(amount, min_amount)| *amount > 0 && amount >= min_amount);
+
+        if let Some(pool) = pool {
+            return Ok(Some(Work::Harvest {
+                pool,
+                amount: snapshot.reward_amounts[pool],
+            }));
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,59 @@
+use chamber_keeper::{
+    calculate_minimum_amount_out, classify_error, increase_compute_units, Retry, MAX_COMPUTE_UNITS,
+};
+use solana_client::{client_error::ClientError, rpc_request::RpcError};
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+#[test]
+fn retry_expired_blockhash() {
+    assert_eq!(
+        classify_error(&TransactionError::BlockhashNotFound.into()),
+        Some(Retry::Blockhash)
+    );
+    assert_eq!(
+        classify_error(
+            &RpcError::ForUser("unable to confirm transaction. This can happen in situations such as transaction expiration and insufficient fee-payer funds".to_string())
+                .into()
+        ),
+        Some(Retry::Blockhash)
+    );
+}
+
+#[test]
+fn retry_exceeded_compute_budget() {
+    let error: ClientError =
+        TransactionError::InstructionError(1, InstructionError::ComputationalBudgetExceeded).into();
+    assert_eq!(classify_error(&error), Some(Retry::ComputeBudget));
+
+    assert_eq!(increase_compute_units(400000), Some(600000));
+    assert_eq!(increase_compute_units(1000000), Some(MAX_COMPUTE_UNITS));
+    assert_eq!(increase_compute_units(MAX_COMPUTE_UNITS), None);
+}
+
+#[test]
+fn fail_program_errors() {
+    // `ChamberError::InvalidOperation`
+    let error: ClientError =
+        TransactionError::InstructionError(1, InstructionError::Custom(6015)).into();
+    assert_eq!(classify_error(&error), None);
+
+    assert_eq!(
+        classify_error(&TransactionError::InsufficientFundsForFee.into()),
+        None
+    );
+    assert_eq!(
+        classify_error(&RpcError::ForUser("AccountNotFound".to_string()).into()),
+        None
+    );
+}
+
+#[test]
+fn harvest_minimum_amount_out() {
+    // 1 RAY into pool of 1000 RAY and 2000 USDC, 0.25% fee and 1% slippage
+    assert_eq!(
+        calculate_minimum_amount_out(1000000, (1000000000, 2000000000), 100).unwrap(),
+        1973080
+    );
+
+    assert!(calculate_minimum_amount_out(1000000, (0, 0), 100).is_err());
+}
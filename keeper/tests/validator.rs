@@ -0,0 +1,325 @@
+//! Runs keeper against `solana-test-validator` with chamber program from `target/deploy`
+//! and offline fixtures of leveraged farm. Requires `anchor build` and validator binary:
+//! `cargo test -p chamber-keeper --test validator -- --ignored`.
+
+use anchor_lang::AccountDeserialize;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::spl_token::{
+        self,
+        state::{Account as TokenAccount, AccountState},
+    },
+};
+use cetra_chamber::state::{Chamber, ChamberConfig, ChamberOperation};
+use cetra_chamber_client::{instruction, TulipChamber};
+use chamber_keeper::{Keeper, KeeperConfig};
+use serde::Deserialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account, commitment_config::CommitmentConfig, instruction::Instruction,
+    program_pack::Pack, pubkey::Pubkey, rent::Rent, signature::Keypair, signer::Signer,
+    system_program, transaction::Transaction,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    str::FromStr,
+    thread,
+    time::Duration,
+};
+
+const RPC_URL: &str = "http://127.0.0.1:8899";
+
+/// Account snapshot as stored in `tests/fixtures/account_<pubkey>.json`.
+#[derive(Deserialize)]
+struct FixtureAccount {
+    lamports: u64,
+    data: Vec<u8>,
+    owner: [u8; 32],
+    executable: bool,
+    rent_epoch: u64,
+}
+
+impl From<FixtureAccount> for Account {
+    fn from(fixture: FixtureAccount) -> Self {
+        Account {
+            lamports: fixture.lamports,
+            data: fixture.data,
+            owner: Pubkey::new_from_array(fixture.owner),
+            executable: fixture.executable,
+            rent_epoch: fixture.rent_epoch,
+        }
+    }
+}
+
+/// Upgradeable program snapshot as stored in `tests/fixtures/program_<pubkey>.json`.
+#[derive(Deserialize)]
+struct FixtureProgram {
+    account: FixtureAccount,
+    buffer_pubkey: [u8; 32],
+    buffer: Vec<u8>,
+}
+
+/// Kills validator when test ends, even by panic.
+struct Validator(Child);
+
+impl Drop for Validator {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+    }
+}
+
+fn repository_path(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(path)
+}
+
+/// Offline fixtures with programs split into program and programdata accounts.
+fn load_fixtures() -> Vec<(Pubkey, Account)> {
+    let mut accounts = vec![];
+
+    for entry in fs::read_dir(repository_path("tests/fixtures")).unwrap() {
+        let path = entry.unwrap().path();
+        let file_stem = path.file_stem().unwrap().to_str().unwrap().to_string();
+        let data = fs::read(&path).unwrap();
+
+        if let Some(pubkey) = file_stem.strip_prefix("account_") {
+            let fixture: FixtureAccount = serde_json::from_slice(&data).unwrap();
+
+            accounts.push((Pubkey::from_str(pubkey).unwrap(), fixture.into()));
+        } else if let Some(pubkey) = file_stem.strip_prefix("program_") {
+            let fixture: FixtureProgram = serde_json::from_slice(&data).unwrap();
+            let program_account: Account = fixture.account.into();
+
+            accounts.push((
+                Pubkey::new_from_array(fixture.buffer_pubkey),
+                Account {
+                    lamports: Rent::default().minimum_balance(fixture.buffer.len()),
+                    data: fixture.buffer,
+                    owner: program_account.owner,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            ));
+            accounts.push((Pubkey::from_str(pubkey).unwrap(), program_account));
+        }
+    }
+
+    accounts
+}
+
+fn system_account(lamports: u64) -> Account {
+    Account {
+        lamports,
+        data: vec![],
+        owner: system_program::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Associated token account of `owner`, mint authority of fixture mints isn't available.
+fn token_account(owner: &Pubkey, mint: &Pubkey, amount: u64) -> (Pubkey, Account) {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        state: AccountState::Initialized,
+        ..TokenAccount::default()
+    }
+    .pack_into_slice(&mut data);
+
+    (
+        get_associated_token_address(owner, mint),
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: spl_token::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+}
+
+/// Starts validator at `slot` with `accounts` in `solana account --output json` format.
+fn start_validator(accounts: &[(Pubkey, Account)], slot: u64) -> (Validator, RpcClient) {
+    let dir = std::env::temp_dir().join("chamber-keeper-validator");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("accounts")).unwrap();
+
+    let mut command = Command::new("solana-test-validator");
+    command
+        .arg("--reset")
+        .arg("--quiet")
+        .arg("--ledger")
+        .arg(dir.join("ledger"))
+        .arg("--warp-slot")
+        .arg(slot.to_string())
+        .arg("--bpf-program")
+        .arg(cetra_chamber::id().to_string())
+        .arg(repository_path("target/deploy/cetra_chamber.so"));
+
+    for (pubkey, account) in accounts {
+        let path = dir.join("accounts").join(format!("{}.json", pubkey));
+        let json = serde_json::json!({
+            "pubkey": pubkey.to_string(),
+            "account": {
+                "lamports": account.lamports,
+                "data": [base64::encode(&account.data), "base64"],
+                "owner": account.owner.to_string(),
+                "executable": account.executable,
+                "rentEpoch": account.rent_epoch,
+            },
+        });
+        fs::write(&path, json.to_string()).unwrap();
+
+        command.arg("--account").arg(pubkey.to_string()).arg(path);
+    }
+
+    let validator = Validator(command.stdout(Stdio::null()).spawn().unwrap());
+    let client = RpcClient::new_with_commitment(RPC_URL.to_string(), CommitmentConfig::confirmed());
+
+    for _ in 0..120 {
+        if client.get_latest_blockhash().is_ok() {
+            return (validator, client);
+        }
+
+        thread::sleep(Duration::from_millis(500));
+    }
+
+    panic!("validator didn't start");
+}
+
+fn send(client: &RpcClient, signer: &Keypair, instructions: &[Instruction]) {
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&signer.pubkey()),
+        &[signer],
+        client.get_latest_blockhash().unwrap(),
+    );
+
+    client.send_and_confirm_transaction(&tx).unwrap();
+}
+
+fn load_chamber(client: &RpcClient, chamber: &TulipChamber) -> Chamber {
+    let data = client.get_account_data(&chamber.pubkey).unwrap();
+
+    Chamber::try_deserialize(&mut data.as_ref()).unwrap()
+}
+
+#[test]
+#[ignore]
+fn keeper_deploys_deposits_and_idle_funds() {
+    let keeper = Keypair::new();
+    let user = Keypair::new();
+    let treasury = Keypair::new();
+    let chamber = TulipChamber::new_sol_usdc_raydium(0, treasury.pubkey());
+    let base_mint = chamber.farm_config.base_token_mint;
+    let quote_mint = chamber.farm_config.quote_token_mint;
+
+    let mut accounts = load_fixtures();
+
+    // Oracles are valid only around slot of fixtures snapshot
+    let (_, pyth_price_account) = accounts
+        .iter()
+        .find(|(pubkey, _)| *pubkey == chamber.farm_config.coin_price_account)
+        .unwrap();
+    let pyth_price =
+        tulipv2_sdk_common::pyth::load::<tulipv2_sdk_common::pyth::Price>(&pyth_price_account.data)
+            .unwrap();
+    let slot = pyth_price.valid_slot;
+
+    accounts.push((keeper.pubkey(), system_account(100000000000)));
+    accounts.push((user.pubkey(), system_account(1000000000)));
+    // SOL with 100$ (2.5) and USDC with 100$ plus 50$ transferred to chamber later
+    accounts.push(token_account(&user.pubkey(), &base_mint, 2500000000));
+    accounts.push(token_account(&user.pubkey(), &quote_mint, 150000000));
+    accounts.push(token_account(&treasury.pubkey(), &base_mint, 0));
+    accounts.push(token_account(&treasury.pubkey(), &quote_mint, 0));
+
+    let (_validator, client) = start_validator(&accounts, slot);
+
+    send(
+        &client,
+        &keeper,
+        &[instruction::initialize_chamber_ix(
+            &chamber,
+            &keeper.pubkey(),
+            &keeper.pubkey(),
+            ChamberConfig::default(),
+        )],
+    );
+    send(
+        &client,
+        &keeper,
+        &[instruction::initialize_chamber_strategy_ix(
+            &chamber,
+            &keeper.pubkey(),
+        )],
+    );
+    send(
+        &client,
+        &user,
+        &[
+            instruction::initialize_user_position_ix(
+                &chamber,
+                &user.pubkey(),
+                2500000000,
+                100000000,
+            ),
+            instruction::queue_deposit_ix(&chamber, &user.pubkey(), 0, 2500000000, 100000000),
+        ],
+    );
+
+    let keeper = Keeper::new(
+        RPC_URL.to_string(),
+        keeper,
+        vec![],
+        KeeperConfig {
+            interval: Duration::from_secs(1),
+            max_retries: 5,
+            withdraw_percent: 25,
+            priority_fee: 0,
+            min_idle_value: 10000000,
+            reward_pools: vec![],
+        },
+    );
+
+    // Queued deposit is processed and cranked through the whole deploy flow
+    keeper.tick(&chamber).unwrap();
+
+    let state = load_chamber(&client, &chamber);
+    assert_eq!({ state.epoch }, 1);
+    assert_eq!(state.operation().unwrap(), ChamberOperation::Staked);
+    let quote_collateral = { state.legs[0].quote_collateral } + { state.legs[1].quote_collateral };
+
+    // Funds which don't belong to any user balance are deployed in the next epoch
+    send(
+        &client,
+        &user,
+        &[spl_token::instruction::transfer(
+            &spl_token::id(),
+            &get_associated_token_address(&user.pubkey(), &quote_mint),
+            &chamber.get_quote_ata(),
+            &user.pubkey(),
+            &[],
+            50000000,
+        )
+        .unwrap()],
+    );
+
+    keeper.tick(&chamber).unwrap();
+
+    let state = load_chamber(&client, &chamber);
+    assert_eq!({ state.epoch }, 2);
+    assert_eq!(state.operation().unwrap(), ChamberOperation::Staked);
+    assert_eq!(
+        { state.legs[0].quote_collateral } + { state.legs[1].quote_collateral },
+        quote_collateral + 50000000
+    );
+
+    // Nothing left to do
+    keeper.tick(&chamber).unwrap();
+    assert_eq!({ load_chamber(&client, &chamber).epoch }, 2);
+}
@@ -0,0 +1,231 @@
+use anchor_lang::AccountDeserialize;
+use cetra_chamber::state::{Chamber, ChamberEpoch, ChamberOperation, StrategyLeg, TulipObligation};
+use chamber_keeper::{detect_work, ChamberSnapshot, Work, WorkConfig};
+use solana_sdk::pubkey::Pubkey;
+use tulipv2_sdk_common::{config::RAYDIUM_LIQUIDITY_V4, math::decimal::Decimal};
+
+fn obligation(deposited_value: u64, borrowed_value: u64) -> TulipObligation {
+    TulipObligation {
+        deposited_value: Decimal::from(deposited_value),
+        borrowed_value: Decimal::from(borrowed_value),
+        allowed_borrow_value: Decimal::zero(),
+        unhealthy_borrow_value: Decimal::zero(),
+    }
+}
+
+/// Settled SOL-USDC chamber without positions, pool holds 1000 SOL at 40$.
+fn snapshot() -> ChamberSnapshot {
+    let mut chamber =
+        Chamber::try_deserialize_unchecked(&mut &vec![0u8; Chamber::LEN][..]).unwrap();
+    chamber.base_decimals = 9;
+    chamber.quote_decimals = 6;
+    chamber.operation = ChamberOperation::Idle as u8;
+
+    let epoch =
+        ChamberEpoch::try_deserialize_unchecked(&mut &vec![0u8; ChamberEpoch::LEN][..]).unwrap();
+
+    ChamberSnapshot {
+        chamber,
+        epoch,
+        lp_amounts: [0, 0],
+        base_ata_amount: 0,
+        quote_ata_amount: 0,
+        pool_base_amount: 1000000000000,
+        pool_quote_amount: 40000000000,
+        lp_supply: 1000000000,
+        obligations: [obligation(0, 0), obligation(0, 0)],
+        reward_amounts: vec![0],
+    }
+}
+
+/// Deploys idle funds from 10$, harvests single reward pool from 1 token.
+fn config() -> WorkConfig {
+    WorkConfig {
+        min_idle_value: 10000000,
+        min_reward_amounts: vec![1000000],
+    }
+}
+
+#[test]
+fn nothing_to_do() {
+    let snapshot = snapshot();
+
+    assert_eq!(detect_work(&snapshot, &config()).unwrap(), None);
+}
+
+#[test]
+fn crank_in_flight_operation_first() {
+    let mut snapshot = snapshot();
+    snapshot.chamber.operation = ChamberOperation::Swapped as u8;
+    snapshot.epoch.pending_quote_amount = 100000000;
+
+    assert_eq!(
+        detect_work(&snapshot, &config()).unwrap(),
+        Some(Work::Crank(ChamberOperation::Swapped))
+    );
+}
+
+#[test]
+fn deleverage_riskier_obligation() {
+    let mut snapshot = snapshot();
+    snapshot.chamber.config.deleverage_ltv_bps = 7500;
+    snapshot.obligations = [obligation(100, 50), obligation(100, 80)];
+    snapshot.epoch.pending_quote_amount = 100000000;
+
+    assert_eq!(
+        detect_work(&snapshot, &config()).unwrap(),
+        Some(Work::Deleverage {
+            obligation: 1,
+            ltv_bps: 8000
+        })
+    );
+
+    // Disabled by default
+    snapshot.chamber.config.deleverage_ltv_bps = 0;
+    assert_eq!(
+        detect_work(&snapshot, &config()).unwrap(),
+        Some(Work::ProcessEpoch)
+    );
+}
+
+#[test]
+fn process_queued_epoch() {
+    let mut snapshot = snapshot();
+    snapshot.epoch.pending_withdraw_shares = 1;
+
+    assert_eq!(
+        detect_work(&snapshot, &config()).unwrap(),
+        Some(Work::ProcessEpoch)
+    );
+}
+
+#[test]
+fn rebalance_drifted_hedge() {
+    let mut snapshot = snapshot();
+    snapshot.chamber.config.rebalance_threshold_bps = 500;
+
+    // LP owns 3.75 SOL of pool reserve, which is not hedged by base debt at all
+    snapshot.chamber.lp_amounts = [1000000, 2750000];
+    assert_eq!(
+        detect_work(&snapshot, &config()).unwrap(),
+        Some(Work::Rebalance {
+            drift_bps: 10000,
+            repay: false
+        })
+    );
+
+    // Fully hedged by second leg
+    snapshot.chamber.legs[1] = StrategyLeg {
+        base_collateral: 0,
+        quote_collateral: 0,
+        base_debt: 3750000000,
+        quote_debt: 0,
+    };
+    assert_eq!(detect_work(&snapshot, &config()).unwrap(), None);
+
+    // Pool base reserve drops as base price rises, so debt outgrows liquidity
+    snapshot.pool_base_amount = 800000000000;
+    assert_eq!(
+        detect_work(&snapshot, &config()).unwrap(),
+        Some(Work::Rebalance {
+            drift_bps: 2500,
+            repay: true
+        })
+    );
+
+    // Disabled by zero threshold
+    snapshot.chamber.config.rebalance_threshold_bps = 0;
+    assert_eq!(detect_work(&snapshot, &config()).unwrap(), None);
+}
+
+#[test]
+fn restake_lp_outside_of_vault() {
+    let mut snapshot = snapshot();
+    snapshot.chamber.operation = ChamberOperation::Staked as u8;
+    snapshot.lp_amounts = [0, 1];
+    snapshot.epoch.pending_quote_amount = 100000000;
+
+    assert_eq!(
+        detect_work(&snapshot, &config()).unwrap(),
+        Some(Work::Restake { lp_amount: 1 })
+    );
+
+    // In-flight flow stakes it anyway
+    snapshot.chamber.operation = ChamberOperation::LiquidityAdded as u8;
+    assert_eq!(
+        detect_work(&snapshot, &config()).unwrap(),
+        Some(Work::Crank(ChamberOperation::LiquidityAdded))
+    );
+
+    // Idle chamber has no vault deposit to restake into
+    snapshot.chamber.operation = ChamberOperation::Idle as u8;
+    assert_eq!(
+        detect_work(&snapshot, &config()).unwrap(),
+        Some(Work::ProcessEpoch)
+    );
+}
+
+#[test]
+fn deploy_free_idle_funds() {
+    let mut snapshot = snapshot();
+    snapshot.chamber.total_base_amount = 1000000000;
+    snapshot.chamber.total_quote_amount = 50000000;
+    snapshot.chamber.claimable_quote_amount = 20000000;
+
+    // Reserved balances only
+    snapshot.base_ata_amount = 1000000000;
+    snapshot.quote_ata_amount = 70000000;
+    assert_eq!(detect_work(&snapshot, &config()).unwrap(), None);
+
+    // 0.2 SOL is worth 8$, which is dust
+    snapshot.base_ata_amount = 1200000000;
+    assert_eq!(detect_work(&snapshot, &config()).unwrap(), None);
+
+    // Along with 2 USDC leftovers it's worth 10$
+    snapshot.quote_ata_amount = 72000000;
+    assert_eq!(
+        detect_work(&snapshot, &config()).unwrap(),
+        Some(Work::DeployIdle {
+            base_amount: 200000000,
+            quote_amount: 2000000
+        })
+    );
+
+    // Threshold below program minimum is raised to it
+    let mut config = config();
+    config.min_idle_value = 1;
+    snapshot.base_ata_amount = 1000000000;
+    assert_eq!(detect_work(&snapshot, &config).unwrap(), None);
+}
+
+#[test]
+fn harvest_rewards_last() {
+    let mut snapshot = snapshot();
+    snapshot.chamber.config.swap_program = RAYDIUM_LIQUIDITY_V4;
+
+    snapshot.reward_amounts = vec![999999];
+    assert_eq!(detect_work(&snapshot, &config()).unwrap(), None);
+
+    snapshot.reward_amounts = vec![1000000];
+    assert_eq!(
+        detect_work(&snapshot, &config()).unwrap(),
+        Some(Work::Harvest {
+            pool: 0,
+            amount: 1000000
+        })
+    );
+
+    snapshot.quote_ata_amount = 10000000;
+    assert_eq!(
+        detect_work(&snapshot, &config()).unwrap(),
+        Some(Work::DeployIdle {
+            base_amount: 0,
+            quote_amount: 10000000
+        })
+    );
+
+    // Route of other swap programs can't be built by keeper
+    snapshot.quote_ata_amount = 0;
+    snapshot.chamber.config.swap_program = Pubkey::default();
+    assert_eq!(detect_work(&snapshot, &config()).unwrap(), None);
+}
@@ -0,0 +1,80 @@
+//! Module provide Tulip levfarm instructions recognised by mock.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{hash::hash, program_error::ProgramError},
+};
+
+/// Tulip levfarm instructions called by `cetra-chamber` strategy.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MockInstruction {
+    CreateUserFarm,
+    CreateUserFarmObligation,
+    DepositBorrowDual,
+    SwapTokensRaydiumStats,
+    AddLiquidityStats,
+    DepositRaydiumVault,
+}
+
+impl MockInstruction {
+    pub const ALL: [MockInstruction; 6] = [
+        MockInstruction::CreateUserFarm,
+        MockInstruction::CreateUserFarmObligation,
+        MockInstruction::DepositBorrowDual,
+        MockInstruction::SwapTokensRaydiumStats,
+        MockInstruction::AddLiquidityStats,
+        MockInstruction::DepositRaydiumVault,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MockInstruction::CreateUserFarm => "create_user_farm",
+            MockInstruction::CreateUserFarmObligation => "create_user_farm_obligation",
+            MockInstruction::DepositBorrowDual => "deposit_borrow_dual",
+            MockInstruction::SwapTokensRaydiumStats => "swap_tokens_raydium_stats",
+            MockInstruction::AddLiquidityStats => "add_liquidity_stats",
+            MockInstruction::DepositRaydiumVault => "deposit_raydium_vault",
+        }
+    }
+
+    /// Anchor sighash of instruction, same as in Tulip SDK builders.
+    pub fn discriminator(&self) -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator
+            .copy_from_slice(&hash(format!("global:{}", self.name()).as_bytes()).to_bytes()[..8]);
+        discriminator
+    }
+
+    /// Splits instruction data into instruction and its arguments.
+    pub fn unpack(data: &[u8]) -> std::result::Result<(Self, &[u8]), ProgramError> {
+        if data.len() < 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (discriminator, args) = data.split_at(8);
+
+        Self::ALL
+            .iter()
+            .find(|instruction| instruction.discriminator() == discriminator)
+            .map(|instruction| (*instruction, args))
+            .ok_or(ProgramError::InvalidInstructionData)
+    }
+
+    /// Token amounts passed in arguments, only `deposit_borrow_dual` moves funds:
+    /// `[coin_amount, pc_amount, coin_borrow_amount, pc_borrow_amount]`.
+    pub fn unpack_amounts(&self, args: &[u8]) -> std::result::Result<Vec<u64>, ProgramError> {
+        match self {
+            MockInstruction::DepositBorrowDual => {
+                if args.len() < 32 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                Ok(args[..32]
+                    .chunks(8)
+                    .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+                    .collect())
+            }
+            _ => Ok(vec![]),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Module provide journal account, where mock records calls.
+
+use crate::{MockInstruction, MockLevfarmError};
+use anchor_lang::{prelude::*, solana_program::program_error::ProgramError};
+use std::io::Write;
+
+/// Single levfarm call, received by mock.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecordedCall {
+    pub instruction: MockInstruction,
+    /// Decoded token amounts, see `MockInstruction::unpack_amounts`.
+    pub amounts: Vec<u64>,
+    /// Raw instruction arguments without discriminator.
+    pub args: Vec<u8>,
+}
+
+/// Journal state, which is stored in mock owned account (e.g. chamber `UserFarm`).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Journal {
+    /// Instruction, which mock fails with `MockLevfarmError::InjectedFailure`.
+    pub fail_on: Option<MockInstruction>,
+    pub calls: Vec<RecordedCall>,
+}
+
+impl Journal {
+    pub const MAGIC: [u8; 8] = *b"mocklevf";
+    pub const LEN: usize = 4096;
+
+    pub fn new(fail_on: Option<MockInstruction>) -> Self {
+        Self {
+            fail_on,
+            calls: vec![],
+        }
+    }
+
+    pub fn is_journal(data: &[u8]) -> bool {
+        data.len() == Self::LEN && data[..8] == Self::MAGIC
+    }
+
+    /// Serializes journal into account data of `Journal::LEN` size.
+    pub fn pack(&self) -> std::result::Result<Vec<u8>, ProgramError> {
+        let mut data = vec![0u8; Self::LEN];
+        self.pack_into(&mut data)?;
+
+        Ok(data)
+    }
+
+    pub fn pack_into(&self, data: &mut [u8]) -> std::result::Result<(), ProgramError> {
+        let (magic, mut state) = data.split_at_mut(8);
+        magic.copy_from_slice(&Self::MAGIC);
+
+        let state_data = self.try_to_vec()?;
+        state
+            .write_all(&state_data)
+            .map_err(|_| MockLevfarmError::JournalFull.into())
+    }
+
+    pub fn unpack(data: &[u8]) -> std::result::Result<Self, ProgramError> {
+        if !Self::is_journal(data) {
+            return Err(MockLevfarmError::JournalNotFound.into());
+        }
+
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+
+    pub fn calls_of(&self, instruction: MockInstruction) -> Vec<&RecordedCall> {
+        self.calls
+            .iter()
+            .filter(|call| call.instruction == instruction)
+            .collect()
+    }
+}
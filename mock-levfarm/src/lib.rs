@@ -0,0 +1,71 @@
+//! Test-only mock of Tulip leveraged farm program.
+//!
+//! Mock is registered in `ProgramTest` under Tulip levfarm program id instead of real program,
+//! so chamber logic can be tested without levfarm, lending and vault account graph. It
+//! recognises instructions called by chamber, records them together with amounts into the
+//! first writable `Journal` account passed to instruction, and fails on request.
+
+mod instruction;
+mod journal;
+
+pub use instruction::*;
+pub use journal::*;
+
+use anchor_lang::solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Address of real Tulip levfarm program, which mock replaces.
+pub fn id() -> Pubkey {
+    tulipv2_sdk_levfarm::ID
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MockLevfarmError {
+    /// 0. Instruction is configured to fail in journal.
+    InjectedFailure,
+    /// 1. No writable journal account in instruction accounts.
+    JournalNotFound,
+    /// 2. Journal account has no space for one more call.
+    JournalFull,
+}
+
+impl From<MockLevfarmError> for ProgramError {
+    fn from(e: MockLevfarmError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let (instruction, args) = MockInstruction::unpack(data)?;
+    msg!("Mock levfarm: {}", instruction.name());
+
+    let journal_account = accounts
+        .iter()
+        .find(|account| {
+            account.owner == program_id
+                && account.is_writable
+                && Journal::is_journal(&account.data.borrow())
+        })
+        .ok_or(MockLevfarmError::JournalNotFound)?;
+
+    let mut journal = Journal::unpack(&journal_account.data.borrow())?;
+
+    if journal.fail_on == Some(instruction) {
+        return Err(MockLevfarmError::InjectedFailure.into());
+    }
+
+    journal.calls.push(RecordedCall {
+        instruction,
+        amounts: instruction.unpack_amounts(args)?,
+        args: args.to_vec(),
+    });
+    journal.pack_into(&mut journal_account.data.borrow_mut())?;
+
+    Ok(())
+}
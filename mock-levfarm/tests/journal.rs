@@ -0,0 +1,66 @@
+use mock_levfarm::{Journal, MockInstruction, RecordedCall};
+
+#[test]
+fn unpack_instruction_by_discriminator() {
+    for instruction in MockInstruction::ALL {
+        let mut data = instruction.discriminator().to_vec();
+        data.push(1);
+
+        let (unpacked, args) = MockInstruction::unpack(&data).unwrap();
+        assert_eq!(unpacked, instruction);
+        assert_eq!(args, [1]);
+    }
+
+    assert!(MockInstruction::unpack(&[0u8; 8]).is_err());
+    assert!(MockInstruction::unpack(&[0u8; 4]).is_err());
+}
+
+#[test]
+fn unpack_deposit_borrow_dual_amounts() {
+    let mut args = vec![];
+    for amount in [1u64, 2, 3, 4] {
+        args.extend_from_slice(&amount.to_le_bytes());
+    }
+    args.push(1);
+
+    let amounts = MockInstruction::DepositBorrowDual
+        .unpack_amounts(&args)
+        .unwrap();
+    assert_eq!(amounts, vec![1, 2, 3, 4]);
+
+    assert!(MockInstruction::DepositBorrowDual
+        .unpack_amounts(&args[..16])
+        .is_err());
+    assert!(MockInstruction::AddLiquidityStats
+        .unpack_amounts(&[0])
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn journal_pack_unpack() {
+    let mut journal = Journal::new(Some(MockInstruction::DepositRaydiumVault));
+    journal.calls.push(RecordedCall {
+        instruction: MockInstruction::DepositBorrowDual,
+        amounts: vec![1, 2, 3, 4],
+        args: vec![0; 33],
+    });
+
+    let data = journal.pack().unwrap();
+    assert_eq!(data.len(), Journal::LEN);
+    assert_eq!(Journal::unpack(&data).unwrap(), journal);
+
+    assert!(Journal::unpack(&vec![0u8; Journal::LEN]).is_err());
+}
+
+#[test]
+fn journal_full() {
+    let mut journal = Journal::new(None);
+    journal.calls.push(RecordedCall {
+        instruction: MockInstruction::CreateUserFarm,
+        amounts: vec![],
+        args: vec![0; Journal::LEN],
+    });
+
+    assert!(journal.pack().is_err());
+}
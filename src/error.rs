@@ -13,4 +13,104 @@ pub enum ChamberError {
     /// 6002.
     #[msg("Insufficient user position funds error.")]
     InsufficientUserPositionFunds,
+
+    /// 6003.
+    #[msg("Invalid fee rate error.")]
+    InvalidFeeRate,
+
+    /// 6004.
+    #[msg("Invalid config error.")]
+    InvalidConfig,
+
+    /// 6005.
+    #[msg("Rebalance threshold not reached error.")]
+    RebalanceNotRequired,
+
+    /// 6006.
+    #[msg("Invalid obligation account error.")]
+    InvalidObligation,
+
+    /// 6007.
+    #[msg("Deleverage threshold not reached error.")]
+    DeleverageNotRequired,
+
+    /// 6008.
+    #[msg("Invalid amount error.")]
+    InvalidAmount,
+
+    /// 6009.
+    #[msg("Swap output below minimum amount error.")]
+    SlippageExceeded,
+
+    /// 6010.
+    #[msg("Insufficient chamber liquidity error.")]
+    InsufficientLiquidity,
+
+    /// 6011.
+    #[msg("User position has unclaimed epoch error.")]
+    UnclaimedEpoch,
+
+    /// 6012.
+    #[msg("Epoch is not processed error.")]
+    EpochNotProcessed,
+
+    /// 6013.
+    #[msg("Withdrawal cooldown not passed error.")]
+    WithdrawalCooldown,
+
+    /// 6014.
+    #[msg("Withdrawal request already processed error.")]
+    WithdrawalProcessed,
+
+    /// 6015.
+    #[msg("Chamber operation out of order error.")]
+    InvalidOperation,
+
+    /// 6016.
+    #[msg("User position status transition error.")]
+    InvalidPositionStatus,
+
+    /// 6017.
+    #[msg("Unsupported AMM error.")]
+    UnsupportedAmm,
+
+    /// 6018.
+    #[msg("Invalid swap program error.")]
+    InvalidSwapProgram,
+
+    /// 6019.
+    #[msg("Swap input above maximum amount error.")]
+    SwapInputExceeded,
+
+    /// 6020.
+    #[msg("Unknown account layout error.")]
+    InvalidAccountLayout,
+
+    /// 6021.
+    #[msg("Account already migrated error.")]
+    AlreadyMigrated,
+
+    /// 6022.
+    #[msg("Signer is neither chamber admin nor keeper error.")]
+    InvalidKeeper,
+
+    /// 6023.
+    #[msg("Pool account doesn't match chamber pool error.")]
+    InvalidPool,
+
+    /// 6024.
+    #[msg("Oracle account doesn't match chamber oracle error.")]
+    InvalidOracle,
+
+    /// 6025.
+    #[msg("Pool price deviates from oracle price error.")]
+    PoolPriceDeviation,
+
+    /// 6026.
+    #[msg("Swap spends reserved chamber balance error.")]
+    ReservedBalanceSpent,
+
+    /// 6027.
+    #[msg("Signer isn't program upgrade authority error.")]
+    InvalidUpgradeAuthority,
 }
@@ -0,0 +1,210 @@
+//! Module define events emitted by program instructions.
+
+use crate::state::{ChamberConfig, ChamberOperation};
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct ChamberInitialized {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub leveraged_farm: Pubkey,
+    pub index: u8,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub config: ChamberConfig,
+}
+
+#[event]
+pub struct ChamberConfigUpdated {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub config: ChamberConfig,
+}
+
+#[event]
+pub struct ManagementFeeCollected {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub base_fee: u64,
+    pub quote_fee: u64,
+}
+
+#[event]
+pub struct StrategyInitialized {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct PositionOpened {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub base_fee: u64,
+    pub quote_fee: u64,
+}
+
+#[event]
+pub struct PositionWithdrawn {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub base_fee: u64,
+    pub quote_fee: u64,
+}
+
+#[event]
+pub struct ChamberDeposited {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub quote_borrow_amount: u64,
+    pub base_borrow_amount: u64,
+
+    /// Pyth base price, scaled by 10^18.
+    pub base_price: u128,
+
+    /// Pyth quote price, scaled by 10^18.
+    pub quote_price: u128,
+
+    /// Shares minted for deposit at chamber NAV.
+    pub minted_shares: u64,
+}
+
+#[event]
+pub struct ChamberRebalanced {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub obligation_index: u8,
+    pub base_borrow_amount: u64,
+    pub withdraw_percent: u8,
+    pub drift_bps: u64,
+}
+
+#[event]
+pub struct ChamberDeleveraged {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub obligation_index: u8,
+    pub withdraw_percent: u8,
+    pub ltv_bps: u64,
+}
+
+#[event]
+pub struct ChamberCranked {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub operation: ChamberOperation,
+}
+
+#[event]
+pub struct PositionSettled {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+
+    /// Base and quote provided to liquidity by both obligations.
+    pub base_amount: u64,
+    pub quote_amount: u64,
+
+    /// LP minted into both obligations.
+    pub lp_amount: u64,
+}
+
+#[event]
+pub struct VaultDeposited {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+
+    /// LP deposited into vault by both obligations.
+    pub lp_amount: u64,
+}
+
+#[event]
+pub struct DepositQueued {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+
+#[event]
+pub struct WithdrawalRequested {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub shares: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalCancelled {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct WithdrawalClaimed {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub shares: u64,
+    pub quote_amount: u64,
+    pub quote_fee: u64,
+}
+
+#[event]
+pub struct EpochProcessed {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub epoch: u64,
+    /// Scaled `Decimal`.
+    pub nav: u128,
+    pub minted_shares: u64,
+    pub burned_shares: u64,
+    pub withdraw_quote_amount: u64,
+    /// Quote value of management fee shares burned, added to chamber accrued fee.
+    pub fee_quote_amount: u64,
+    /// Free idle funds deployed along with queued deposits.
+    pub free_base_amount: u64,
+    pub free_quote_amount: u64,
+}
+
+#[event]
+pub struct DepositClaimed {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct ChamberSwapped {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub source_mint: Pubkey,
+    pub destination_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+#[event]
+pub struct ChamberMigrated {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub previous_version: u8,
+    pub version: u8,
+}
+
+#[event]
+pub struct PositionMigrated {
+    pub chamber: Pubkey,
+    pub user: Pubkey,
+    pub previous_version: u8,
+    pub version: u8,
+}
@@ -0,0 +1,96 @@
+use crate::{state, utils};
+use anchor_lang::{prelude::*, solana_program::program::invoke_signed};
+use anchor_spl::token::Token;
+use tulipv2_sdk_levfarm::instructions::orca_add_liquidity_queue::{
+    orca_add_liquidity_queue, OrcaAddLiquidityQueue,
+};
+
+pub struct AddLiquidityOrcaTulipLevfarmAccounts<'c, 'info> {
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
+    pub chamber_authority: &'c AccountInfo<'info>,
+    pub chamber_farm: &'c AccountInfo<'info>,
+    pub leveraged_farm: &'c AccountInfo<'info>,
+    pub swap_program_id: &'c AccountInfo<'info>,
+    pub swap_account: &'c AccountInfo<'info>,
+    pub swap_authority: &'c AccountInfo<'info>,
+    pub pool_token_mint: &'c AccountInfo<'info>,
+    pub pool_coin_token_account: &'c AccountInfo<'info>,
+    pub pool_pc_token_account: &'c AccountInfo<'info>,
+    pub token_program: &'c Program<'info, Token>,
+    pub lev_farm_coin_token_account: &'c AccountInfo<'info>,
+    pub lev_farm_pc_token_account: &'c AccountInfo<'info>,
+    pub lp_token_account: &'c AccountInfo<'info>,
+    pub pyth_price_account: &'c AccountInfo<'info>,
+    pub lending_market: &'c AccountInfo<'info>,
+    pub chamber_farm_obligation: &'c AccountInfo<'info>,
+    pub lending_market_authority: &'c AccountInfo<'info>,
+    pub lending_program: &'c AccountInfo<'info>,
+    pub clock_sysvar: &'c Sysvar<'info, Clock>,
+    pub position_info: &'c AccountInfo<'info>,
+    pub levfarm_program: &'c AccountInfo<'info>,
+}
+
+#[inline(always)]
+pub fn add_liquidity_orca_tulip_levfarm<'c, 'info>(
+    accounts: Box<AddLiquidityOrcaTulipLevfarmAccounts>,
+    obligation_index: u8,
+) -> Result<()> {
+    invoke_signed(
+        &orca_add_liquidity_queue(
+            Box::new(OrcaAddLiquidityQueue {
+                authority: accounts.chamber_authority.key(),
+                user_farm: accounts.chamber_farm.key(),
+                leveraged_farm: accounts.leveraged_farm.key(),
+                swap_program_id: accounts.swap_program_id.key(),
+                swap_account: accounts.swap_account.key(),
+                swap_authority: accounts.swap_authority.key(),
+                pool_token_mint: accounts.pool_token_mint.key(),
+                pool_coin_token_account: accounts.pool_coin_token_account.key(),
+                pool_pc_token_account: accounts.pool_pc_token_account.key(),
+                token_program: accounts.token_program.key(),
+                lev_farm_coin_token_account: accounts.lev_farm_coin_token_account.key(),
+                lev_farm_pc_token_account: accounts.lev_farm_pc_token_account.key(),
+                user_lp_token_account: accounts.lp_token_account.key(),
+                pyth_price_account: accounts.pyth_price_account.key(),
+                lending_market_account: accounts.lending_market.key(),
+                user_farm_obligation: accounts.chamber_farm_obligation.key(),
+                derived_lending_market_authority: accounts.lending_market_authority.key(),
+                lending_program: accounts.lending_program.key(),
+                clock: accounts.clock_sysvar.key(),
+            }),
+            accounts.position_info.key(),
+            obligation_index,
+        )
+        .unwrap(),
+        &Box::new(vec![
+            accounts.chamber_authority.to_account_info(),
+            accounts.chamber_farm.to_account_info(),
+            accounts.leveraged_farm.to_account_info(),
+            accounts.swap_program_id.to_account_info(),
+            accounts.swap_account.to_account_info(),
+            accounts.swap_authority.to_account_info(),
+            accounts.pool_token_mint.to_account_info(),
+            accounts.pool_coin_token_account.to_account_info(),
+            accounts.pool_pc_token_account.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.lev_farm_coin_token_account.to_account_info(),
+            accounts.lev_farm_pc_token_account.to_account_info(),
+            accounts.lp_token_account.to_account_info(),
+            accounts.pyth_price_account.to_account_info(),
+            accounts.lending_market.to_account_info(),
+            accounts.chamber_farm_obligation.to_account_info(),
+            accounts.lending_market_authority.to_account_info(),
+            accounts.lending_program.to_account_info(),
+            accounts.clock_sysvar.to_account_info(),
+            accounts.position_info.to_account_info(),
+        ]),
+        &[&[
+            utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+            accounts.chamber.key().as_ref(),
+            &[accounts.authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
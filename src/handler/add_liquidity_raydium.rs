@@ -0,0 +1,90 @@
+use crate::{state, utils};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+};
+use anchor_spl::token::Token;
+
+/// Raydium AMM v4 `Deposit` instruction tag.
+pub const RAYDIUM_DEPOSIT_TAG: u8 = 3;
+
+/// Raydium AMM v4 `base_side` selecting coin as fixed side of deposit.
+pub const RAYDIUM_BASE_SIDE_COIN: u64 = 0;
+
+pub struct AddLiquidityRaydiumAccounts<'c, 'info> {
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
+    pub chamber_authority: &'c AccountInfo<'info>,
+    pub token_program: &'c Program<'info, Token>,
+    pub amm_program: &'c AccountInfo<'info>,
+    pub amm_id: &'c AccountInfo<'info>,
+    pub amm_authority: &'c AccountInfo<'info>,
+    pub amm_open_orders: &'c AccountInfo<'info>,
+    pub amm_quantities_or_target_orders: &'c AccountInfo<'info>,
+    pub lp_mint: &'c AccountInfo<'info>,
+    pub pool_coin_token_account: &'c AccountInfo<'info>,
+    pub pool_pc_token_account: &'c AccountInfo<'info>,
+    pub serum_market: &'c AccountInfo<'info>,
+    pub coin_token_account: &'c AccountInfo<'info>,
+    pub pc_token_account: &'c AccountInfo<'info>,
+    pub lp_token_account: &'c AccountInfo<'info>,
+}
+
+/// Provide up to `max_coin_amount` and `max_pc_amount` to Raydium pool, owned by chamber authority.
+#[inline(always)]
+pub fn add_liquidity_raydium<'c, 'info>(
+    accounts: Box<AddLiquidityRaydiumAccounts<'c, 'info>>,
+    max_coin_amount: u64,
+    max_pc_amount: u64,
+) -> Result<()> {
+    let mut data = Vec::with_capacity(25);
+    data.push(RAYDIUM_DEPOSIT_TAG);
+    data.extend_from_slice(&max_coin_amount.to_le_bytes());
+    data.extend_from_slice(&max_pc_amount.to_le_bytes());
+    data.extend_from_slice(&RAYDIUM_BASE_SIDE_COIN.to_le_bytes());
+
+    invoke_signed(
+        &Instruction {
+            program_id: accounts.amm_program.key(),
+            accounts: vec![
+                AccountMeta::new_readonly(accounts.token_program.key(), false),
+                AccountMeta::new(accounts.amm_id.key(), false),
+                AccountMeta::new_readonly(accounts.amm_authority.key(), false),
+                AccountMeta::new_readonly(accounts.amm_open_orders.key(), false),
+                AccountMeta::new(accounts.amm_quantities_or_target_orders.key(), false),
+                AccountMeta::new(accounts.lp_mint.key(), false),
+                AccountMeta::new(accounts.pool_coin_token_account.key(), false),
+                AccountMeta::new(accounts.pool_pc_token_account.key(), false),
+                AccountMeta::new_readonly(accounts.serum_market.key(), false),
+                AccountMeta::new(accounts.coin_token_account.key(), false),
+                AccountMeta::new(accounts.pc_token_account.key(), false),
+                AccountMeta::new(accounts.lp_token_account.key(), false),
+                AccountMeta::new_readonly(accounts.chamber_authority.key(), true),
+            ],
+            data,
+        },
+        &Box::new(vec![
+            accounts.token_program.to_account_info(),
+            accounts.amm_id.to_account_info(),
+            accounts.amm_authority.to_account_info(),
+            accounts.amm_open_orders.to_account_info(),
+            accounts.amm_quantities_or_target_orders.to_account_info(),
+            accounts.lp_mint.to_account_info(),
+            accounts.pool_coin_token_account.to_account_info(),
+            accounts.pool_pc_token_account.to_account_info(),
+            accounts.serum_market.to_account_info(),
+            accounts.coin_token_account.to_account_info(),
+            accounts.pc_token_account.to_account_info(),
+            accounts.lp_token_account.to_account_info(),
+            accounts.chamber_authority.to_account_info(),
+            accounts.amm_program.to_account_info(),
+        ]),
+        &[&[
+            utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+            accounts.chamber.key().as_ref(),
+            &[accounts.authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
@@ -4,7 +4,8 @@ use anchor_spl::token::Token;
 use tulipv2_sdk_levfarm::instructions::add_liquidity_stats::{add_liquidity_stats, AddLiquidity};
 
 pub struct AddLiquidityRaydiumTulipLevfarmAccounts<'c, 'info> {
-    pub chamber: &'c Account<'info, state::Chamber>,
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
     pub chamber_authority: &'c AccountInfo<'info>,
     pub chamber_farm: &'c AccountInfo<'info>,
     pub leveraged_farm: &'c AccountInfo<'info>,
@@ -97,7 +98,7 @@ pub fn add_liquidity_raydium_tulip_levfarm<'c, 'info>(
         &[&[
             utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
             accounts.chamber.key().as_ref(),
-            &[accounts.chamber.authority_bump],
+            &[accounts.authority_bump],
         ]],
     )?;
 
@@ -0,0 +1,63 @@
+use crate::{state, utils};
+use anchor_lang::{prelude::*, solana_program::program::invoke_signed};
+use anchor_spl::token::Token;
+use spl_token_lending::instruction::borrow_obligation_liquidity;
+
+pub struct BorrowSolendAccounts<'c, 'info> {
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
+    pub chamber_authority: &'c AccountInfo<'info>,
+    pub reserve_liquidity_supply: &'c AccountInfo<'info>,
+    pub destination_liquidity: &'c AccountInfo<'info>,
+    pub reserve: &'c AccountInfo<'info>,
+    pub reserve_liquidity_fee_receiver: &'c AccountInfo<'info>,
+    pub obligation: &'c AccountInfo<'info>,
+    pub lending_market: &'c AccountInfo<'info>,
+    pub lending_market_authority: &'c AccountInfo<'info>,
+    pub clock_sysvar: &'c Sysvar<'info, Clock>,
+    pub token_program: &'c Program<'info, Token>,
+    pub lending_program: &'c AccountInfo<'info>,
+}
+
+/// Borrow `liquidity_amount` against obligation collateral.
+/// Reserve and obligation must be refreshed in the same transaction.
+#[inline(always)]
+pub fn borrow_solend<'c, 'info>(
+    accounts: Box<BorrowSolendAccounts<'c, 'info>>,
+    liquidity_amount: u64,
+) -> Result<()> {
+    invoke_signed(
+        &borrow_obligation_liquidity(
+            accounts.lending_program.key(),
+            liquidity_amount,
+            accounts.reserve_liquidity_supply.key(),
+            accounts.destination_liquidity.key(),
+            accounts.reserve.key(),
+            accounts.reserve_liquidity_fee_receiver.key(),
+            accounts.obligation.key(),
+            accounts.lending_market.key(),
+            accounts.chamber_authority.key(),
+            None,
+        ),
+        &Box::new(vec![
+            accounts.reserve_liquidity_supply.to_account_info(),
+            accounts.destination_liquidity.to_account_info(),
+            accounts.reserve.to_account_info(),
+            accounts.reserve_liquidity_fee_receiver.to_account_info(),
+            accounts.obligation.to_account_info(),
+            accounts.lending_market.to_account_info(),
+            accounts.lending_market_authority.to_account_info(),
+            accounts.chamber_authority.to_account_info(),
+            accounts.clock_sysvar.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.lending_program.to_account_info(),
+        ]),
+        &[&[
+            utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+            accounts.chamber.key().as_ref(),
+            &[accounts.authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
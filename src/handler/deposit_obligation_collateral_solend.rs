@@ -0,0 +1,56 @@
+use crate::{state, utils};
+use anchor_lang::{prelude::*, solana_program::program::invoke_signed};
+use anchor_spl::token::Token;
+use spl_token_lending::instruction::deposit_obligation_collateral;
+
+pub struct DepositObligationCollateralSolendAccounts<'c, 'info> {
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
+    pub chamber_authority: &'c AccountInfo<'info>,
+    pub source_collateral: &'c AccountInfo<'info>,
+    pub reserve_collateral_supply: &'c AccountInfo<'info>,
+    pub reserve: &'c AccountInfo<'info>,
+    pub obligation: &'c AccountInfo<'info>,
+    pub lending_market: &'c AccountInfo<'info>,
+    pub clock_sysvar: &'c Sysvar<'info, Clock>,
+    pub token_program: &'c Program<'info, Token>,
+    pub lending_program: &'c AccountInfo<'info>,
+}
+
+#[inline(always)]
+pub fn deposit_obligation_collateral_solend<'c, 'info>(
+    accounts: Box<DepositObligationCollateralSolendAccounts<'c, 'info>>,
+    collateral_amount: u64,
+) -> Result<()> {
+    invoke_signed(
+        &deposit_obligation_collateral(
+            accounts.lending_program.key(),
+            collateral_amount,
+            accounts.source_collateral.key(),
+            accounts.reserve_collateral_supply.key(),
+            accounts.reserve.key(),
+            accounts.obligation.key(),
+            accounts.lending_market.key(),
+            accounts.chamber_authority.key(),
+            accounts.chamber_authority.key(),
+        ),
+        &Box::new(vec![
+            accounts.source_collateral.to_account_info(),
+            accounts.reserve_collateral_supply.to_account_info(),
+            accounts.reserve.to_account_info(),
+            accounts.obligation.to_account_info(),
+            accounts.lending_market.to_account_info(),
+            accounts.chamber_authority.to_account_info(),
+            accounts.clock_sysvar.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.lending_program.to_account_info(),
+        ]),
+        &[&[
+            utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+            accounts.chamber.key().as_ref(),
+            &[accounts.authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
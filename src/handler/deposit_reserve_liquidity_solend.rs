@@ -0,0 +1,60 @@
+use crate::{state, utils};
+use anchor_lang::{prelude::*, solana_program::program::invoke_signed};
+use anchor_spl::token::Token;
+use spl_token_lending::instruction::deposit_reserve_liquidity;
+
+pub struct DepositReserveLiquiditySolendAccounts<'c, 'info> {
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
+    pub chamber_authority: &'c AccountInfo<'info>,
+    pub source_liquidity: &'c AccountInfo<'info>,
+    pub destination_collateral: &'c AccountInfo<'info>,
+    pub reserve: &'c AccountInfo<'info>,
+    pub reserve_liquidity_supply: &'c AccountInfo<'info>,
+    pub reserve_collateral_mint: &'c AccountInfo<'info>,
+    pub lending_market: &'c AccountInfo<'info>,
+    pub lending_market_authority: &'c AccountInfo<'info>,
+    pub clock_sysvar: &'c Sysvar<'info, Clock>,
+    pub token_program: &'c Program<'info, Token>,
+    pub lending_program: &'c AccountInfo<'info>,
+}
+
+#[inline(always)]
+pub fn deposit_reserve_liquidity_solend<'c, 'info>(
+    accounts: Box<DepositReserveLiquiditySolendAccounts<'c, 'info>>,
+    liquidity_amount: u64,
+) -> Result<()> {
+    invoke_signed(
+        &deposit_reserve_liquidity(
+            accounts.lending_program.key(),
+            liquidity_amount,
+            accounts.source_liquidity.key(),
+            accounts.destination_collateral.key(),
+            accounts.reserve.key(),
+            accounts.reserve_liquidity_supply.key(),
+            accounts.reserve_collateral_mint.key(),
+            accounts.lending_market.key(),
+            accounts.chamber_authority.key(),
+        ),
+        &Box::new(vec![
+            accounts.source_liquidity.to_account_info(),
+            accounts.destination_collateral.to_account_info(),
+            accounts.reserve.to_account_info(),
+            accounts.reserve_liquidity_supply.to_account_info(),
+            accounts.reserve_collateral_mint.to_account_info(),
+            accounts.lending_market.to_account_info(),
+            accounts.lending_market_authority.to_account_info(),
+            accounts.chamber_authority.to_account_info(),
+            accounts.clock_sysvar.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.lending_program.to_account_info(),
+        ]),
+        &[&[
+            utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+            accounts.chamber.key().as_ref(),
+            &[accounts.authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use crate::{state, utils};
+use anchor_lang::{prelude::*, solana_program::program::invoke_signed};
+use anchor_spl::token::Token;
+use tulipv2_sdk_levfarm::instructions::deposit_orca_vault::{deposit_orca_vault, DepositOrcaVault};
+
+pub struct DepositVaultOrcaTulipLevfarmAccounts<'c, 'info> {
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
+    pub chamber_authority: &'c AccountInfo<'info>,
+    pub chamber_farm: &'c AccountInfo<'info>,
+    pub chamber_farm_obligation_vault: &'c AccountInfo<'info>,
+    pub leveraged_farm: &'c AccountInfo<'info>,
+    pub vault_program: &'c AccountInfo<'info>,
+    pub vault: &'c AccountInfo<'info>,
+    pub vault_pda_account: &'c AccountInfo<'info>,
+    pub chamber_balance_account: &'c AccountInfo<'info>,
+    pub system_program: &'c Program<'info, System>,
+    pub lp_token_account: &'c AccountInfo<'info>,
+    pub farm_token_account: &'c AccountInfo<'info>,
+    pub reward_token_account: &'c AccountInfo<'info>,
+    pub global_base_token_vault: &'c AccountInfo<'info>,
+    pub farm_token_mint: &'c AccountInfo<'info>,
+    pub global_farm: &'c AccountInfo<'info>,
+    pub orca_user_farm: &'c AccountInfo<'info>,
+    pub global_reward_token_vault: &'c AccountInfo<'info>,
+    pub convert_authority: &'c AccountInfo<'info>,
+    pub aqua_farm_program: &'c AccountInfo<'info>,
+    pub clock_sysvar: &'c Sysvar<'info, Clock>,
+    pub rent_sysvar: &'c Sysvar<'info, Rent>,
+    pub token_program_id: &'c Program<'info, Token>,
+    pub chamber_balance_metadata: &'c AccountInfo<'info>,
+    pub lending_market: &'c AccountInfo<'info>,
+    pub chamber_farm_obligation: &'c AccountInfo<'info>,
+    pub lending_market_authority: &'c AccountInfo<'info>,
+    pub lending_program: &'c AccountInfo<'info>,
+    pub levfarm_program: &'c AccountInfo<'info>,
+}
+
+#[inline(always)]
+pub fn deposit_vault_orca_tulip_levfarm<'c, 'info>(
+    accounts: Box<DepositVaultOrcaTulipLevfarmAccounts>,
+    nonce: u8,
+    meta_nonce: u8,
+    obligation_index: u8,
+) -> Result<()> {
+    invoke_signed(
+        &deposit_orca_vault(
+            Box::new(DepositOrcaVault {
+                authority: accounts.chamber_authority.key(),
+                user_farm: accounts.chamber_farm.key(),
+                obligation_vault_address: accounts.chamber_farm_obligation_vault.key(),
+                leveraged_farm: accounts.leveraged_farm.key(),
+                vault_program: accounts.vault_program.key(),
+                vault_account: accounts.vault.key(),
+                vault_pda: accounts.vault_pda_account.key(),
+                vault_user_account: accounts.chamber_balance_account.key(),
+                system_program: accounts.system_program.key(),
+                user_base_token_account: accounts.lp_token_account.key(),
+                user_farm_token_account: accounts.farm_token_account.key(),
+                user_reward_token_account: accounts.reward_token_account.key(),
+                global_base_token_vault: accounts.global_base_token_vault.key(),
+                farm_token_mint: accounts.farm_token_mint.key(),
+                global_farm: accounts.global_farm.key(),
+                orca_user_farm: accounts.orca_user_farm.key(),
+                global_reward_token_vault: accounts.global_reward_token_vault.key(),
+                convert_authority: accounts.convert_authority.key(),
+                aqua_farm_program: accounts.aqua_farm_program.key(),
+                clock: accounts.clock_sysvar.key(),
+                rent: accounts.rent_sysvar.key(),
+                token_program_id: accounts.token_program_id.key(),
+                user_balance_metadata: accounts.chamber_balance_metadata.key(),
+            }),
+            accounts.lending_market.key(),
+            accounts.chamber_farm_obligation.key(),
+            accounts.lending_market_authority.key(),
+            accounts.lending_program.key(),
+            nonce,
+            meta_nonce,
+            obligation_index as u64,
+        )
+        .unwrap(),
+        &Box::new(vec![
+            accounts.chamber_authority.to_account_info(),
+            accounts.chamber_farm.to_account_info(),
+            accounts.chamber_farm_obligation_vault.to_account_info(),
+            accounts.leveraged_farm.to_account_info(),
+            accounts.vault_program.to_account_info(),
+            accounts.vault.to_account_info(),
+            accounts.vault_pda_account.to_account_info(),
+            accounts.chamber_balance_account.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.lp_token_account.to_account_info(),
+            accounts.farm_token_account.to_account_info(),
+            accounts.reward_token_account.to_account_info(),
+            accounts.global_base_token_vault.to_account_info(),
+            accounts.farm_token_mint.to_account_info(),
+            accounts.global_farm.to_account_info(),
+            accounts.orca_user_farm.to_account_info(),
+            accounts.global_reward_token_vault.to_account_info(),
+            accounts.convert_authority.to_account_info(),
+            accounts.aqua_farm_program.to_account_info(),
+            accounts.clock_sysvar.to_account_info(),
+            accounts.rent_sysvar.to_account_info(),
+            accounts.token_program_id.to_account_info(),
+            accounts.chamber_balance_metadata.to_account_info(),
+            accounts.lending_market.to_account_info(),
+            accounts.chamber_farm_obligation.to_account_info(),
+            accounts.lending_market_authority.to_account_info(),
+            accounts.lending_program.to_account_info(),
+        ]),
+        &[&[
+            utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+            accounts.chamber.key().as_ref(),
+            &[accounts.authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
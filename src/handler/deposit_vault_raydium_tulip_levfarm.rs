@@ -4,7 +4,8 @@ use anchor_spl::token::Token;
 use tulipv2_sdk_levfarm::instructions::deposit_raydium_vault::{deposit_vault, DepositFarm};
 
 pub struct DepositVaultRaydiumTulipLevfarmAccounts<'c, 'info> {
-    pub chamber: &'c Account<'info, state::Chamber>,
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
     pub chamber_authority: &'c AccountInfo<'info>,
     pub chamber_farm: &'c AccountInfo<'info>,
     pub chamber_farm_obligation_vault: &'c AccountInfo<'info>,
@@ -113,7 +114,7 @@ pub fn deposit_vault_raydium_tulip_levfarm<'c, 'info>(
         &[&[
             utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
             accounts.chamber.key().as_ref(),
-            &[accounts.chamber.authority_bump],
+            &[accounts.authority_bump],
         ]],
     )?;
 
@@ -0,0 +1,46 @@
+use crate::{state, utils};
+use anchor_lang::{prelude::*, solana_program::program::invoke_signed};
+use anchor_spl::token::Token;
+use spl_token_lending::instruction::init_obligation;
+
+pub struct InitObligationSolendAccounts<'c, 'info> {
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
+    pub chamber_authority: &'c AccountInfo<'info>,
+    pub obligation: &'c AccountInfo<'info>,
+    pub lending_market: &'c AccountInfo<'info>,
+    pub clock_sysvar: &'c Sysvar<'info, Clock>,
+    pub rent_sysvar: &'c Sysvar<'info, Rent>,
+    pub token_program: &'c Program<'info, Token>,
+    pub lending_program: &'c AccountInfo<'info>,
+}
+
+#[inline(always)]
+pub fn init_obligation_solend<'c, 'info>(
+    accounts: Box<InitObligationSolendAccounts<'c, 'info>>,
+) -> Result<()> {
+    invoke_signed(
+        &init_obligation(
+            accounts.lending_program.key(),
+            accounts.obligation.key(),
+            accounts.lending_market.key(),
+            accounts.chamber_authority.key(),
+        ),
+        &Box::new(vec![
+            accounts.obligation.to_account_info(),
+            accounts.lending_market.to_account_info(),
+            accounts.chamber_authority.to_account_info(),
+            accounts.clock_sysvar.to_account_info(),
+            accounts.rent_sysvar.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.lending_program.to_account_info(),
+        ]),
+        &[&[
+            utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+            accounts.chamber.key().as_ref(),
+            &[accounts.authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
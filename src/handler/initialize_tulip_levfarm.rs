@@ -4,7 +4,8 @@ use anchor_spl::{associated_token, token::Token};
 use tulipv2_sdk_levfarm::instructions::create_user_farm::{create_user_farm, CreateUserFarm};
 
 pub struct InitializeTulipLevfarmAccounts<'c, 'info> {
-    pub chamber: &'c Account<'info, state::Chamber>,
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
     pub chamber_authority: &'c AccountInfo<'info>,
     pub payer: &'c AccountInfo<'info>,
     pub global: &'c AccountInfo<'info>,
@@ -101,7 +102,7 @@ pub fn initialize_tulip_levfarm<'c, 'info>(
             &[&[
                 utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
                 accounts.chamber.key().as_ref(),
-                &[accounts.chamber.authority_bump],
+                &[accounts.authority_bump],
             ]],
         )?;
     }
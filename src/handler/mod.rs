@@ -1,18 +1,50 @@
 //! Module provide handlers for CPI's.
 
 /// TODO: Separate different AMM's and Protocol's implementations.
+mod add_liquidity_orca_tulip_levfarm;
+mod add_liquidity_raydium;
 mod add_liquidity_raydium_tulip_levfarm;
+mod borrow_solend;
 mod create_obligation_tulip_levfarm;
 mod deposit_borrow_tulip_levfarm;
+mod deposit_obligation_collateral_solend;
+mod deposit_reserve_liquidity_solend;
+mod deposit_vault_orca_tulip_levfarm;
 mod deposit_vault_raydium_tulip_levfarm;
+mod init_obligation_solend;
 mod initialize_tulip_levfarm;
+mod realloc_account;
+mod refresh_obligation_solend;
+mod refresh_reserve_solend;
+mod swap_aggregator;
+mod swap_base_in_raydium;
+mod swap_tokens_orca_tulip_levfarm;
 mod swap_tokens_raydium_tulip_levfarm;
+mod sync_native;
 mod transfer_lamports;
+mod withdraw_idle_balance;
+mod withdraw_vault_raydium_tulip_levfarm;
 
+pub use add_liquidity_orca_tulip_levfarm::*;
+pub use add_liquidity_raydium::*;
 pub use add_liquidity_raydium_tulip_levfarm::*;
+pub use borrow_solend::*;
 pub use create_obligation_tulip_levfarm::*;
 pub use deposit_borrow_tulip_levfarm::*;
+pub use deposit_obligation_collateral_solend::*;
+pub use deposit_reserve_liquidity_solend::*;
+pub use deposit_vault_orca_tulip_levfarm::*;
 pub use deposit_vault_raydium_tulip_levfarm::*;
+pub use init_obligation_solend::*;
 pub use initialize_tulip_levfarm::*;
+pub use realloc_account::*;
+pub use refresh_obligation_solend::*;
+pub use refresh_reserve_solend::*;
+pub use swap_aggregator::*;
+pub use swap_base_in_raydium::*;
+pub use swap_tokens_orca_tulip_levfarm::*;
 pub use swap_tokens_raydium_tulip_levfarm::*;
+pub use sync_native::*;
 pub use transfer_lamports::*;
+pub use withdraw_idle_balance::*;
+pub use withdraw_vault_raydium_tulip_levfarm::*;
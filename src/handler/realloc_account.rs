@@ -0,0 +1,34 @@
+use super::{transfer_lamports, TransferLamportsAccounts};
+use anchor_lang::prelude::*;
+
+pub struct ReallocAccountAccounts<'c, 'info> {
+    pub account: &'c AccountInfo<'info>,
+    pub payer: &'c AccountInfo<'info>,
+    pub system_program: &'c Program<'info, System>,
+}
+
+/// Grow program owned account to `new_len` zeroed bytes, payer funds rent exemption.
+#[inline(always)]
+pub fn realloc_account<'c, 'info>(
+    accounts: Box<ReallocAccountAccounts<'c, 'info>>,
+    new_len: usize,
+) -> Result<()> {
+    let rent_lamports = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(accounts.account.lamports());
+
+    if rent_lamports > 0 {
+        transfer_lamports(
+            Box::new(TransferLamportsAccounts {
+                from: accounts.payer,
+                to: accounts.account,
+                system_program: accounts.system_program,
+            }),
+            rent_lamports,
+        )?;
+    }
+
+    accounts.account.realloc(new_len, true)?;
+
+    Ok(())
+}
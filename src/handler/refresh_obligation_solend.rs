@@ -0,0 +1,33 @@
+use anchor_lang::{prelude::*, solana_program::program::invoke};
+use spl_token_lending::instruction::refresh_obligation;
+
+pub struct RefreshObligationSolendAccounts<'c, 'info> {
+    pub obligation: &'c AccountInfo<'info>,
+    pub clock_sysvar: &'c Sysvar<'info, Clock>,
+    /// Deposit reserves followed by borrow reserves, in obligation order.
+    pub reserves: Vec<&'c AccountInfo<'info>>,
+    pub lending_program: &'c AccountInfo<'info>,
+}
+
+#[inline(always)]
+pub fn refresh_obligation_solend<'c, 'info>(
+    accounts: Box<RefreshObligationSolendAccounts<'c, 'info>>,
+) -> Result<()> {
+    let mut account_infos = vec![
+        accounts.obligation.to_account_info(),
+        accounts.clock_sysvar.to_account_info(),
+    ];
+    account_infos.extend(accounts.reserves.iter().map(|r| r.to_account_info()));
+    account_infos.push(accounts.lending_program.to_account_info());
+
+    invoke(
+        &refresh_obligation(
+            accounts.lending_program.key(),
+            accounts.obligation.key(),
+            accounts.reserves.iter().map(|r| r.key()).collect(),
+        ),
+        &account_infos,
+    )?;
+
+    Ok(())
+}
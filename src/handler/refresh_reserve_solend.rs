@@ -0,0 +1,30 @@
+use anchor_lang::{prelude::*, solana_program::program::invoke};
+use spl_token_lending::instruction::refresh_reserve;
+
+pub struct RefreshReserveSolendAccounts<'c, 'info> {
+    pub reserve: &'c AccountInfo<'info>,
+    pub reserve_liquidity_oracle: &'c AccountInfo<'info>,
+    pub clock_sysvar: &'c Sysvar<'info, Clock>,
+    pub lending_program: &'c AccountInfo<'info>,
+}
+
+#[inline(always)]
+pub fn refresh_reserve_solend<'c, 'info>(
+    accounts: Box<RefreshReserveSolendAccounts<'c, 'info>>,
+) -> Result<()> {
+    invoke(
+        &refresh_reserve(
+            accounts.lending_program.key(),
+            accounts.reserve.key(),
+            accounts.reserve_liquidity_oracle.key(),
+        ),
+        &Box::new(vec![
+            accounts.reserve.to_account_info(),
+            accounts.reserve_liquidity_oracle.to_account_info(),
+            accounts.clock_sysvar.to_account_info(),
+            accounts.lending_program.to_account_info(),
+        ]),
+    )?;
+
+    Ok(())
+}
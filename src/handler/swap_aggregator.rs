@@ -0,0 +1,52 @@
+use crate::{state, utils};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+};
+
+pub struct SwapAggregatorAccounts<'c, 'info> {
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
+    pub chamber_authority: &'c AccountInfo<'info>,
+    pub swap_program: &'c AccountInfo<'info>,
+
+    /// Route accounts in order expected by aggregator program.
+    pub route_accounts: &'c [AccountInfo<'info>],
+}
+
+#[inline(always)]
+pub fn swap_aggregator<'c, 'info>(
+    accounts: Box<SwapAggregatorAccounts<'c, 'info>>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let authority_key = accounts.chamber_authority.key();
+
+    let account_metas = accounts
+        .route_accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key() == authority_key,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let mut account_infos = accounts.route_accounts.to_vec();
+    account_infos.push(accounts.swap_program.to_account_info());
+
+    invoke_signed(
+        &Instruction {
+            program_id: accounts.swap_program.key(),
+            accounts: account_metas,
+            data,
+        },
+        &account_infos,
+        &[&[
+            utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+            accounts.chamber.key().as_ref(),
+            &[accounts.authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use crate::{state, utils};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+};
+use anchor_spl::token::Token;
+
+/// Raydium AMM v4 `SwapBaseIn` instruction tag.
+pub const RAYDIUM_SWAP_BASE_IN_TAG: u8 = 9;
+
+pub struct SwapBaseInRaydiumAccounts<'c, 'info> {
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
+    pub chamber_authority: &'c AccountInfo<'info>,
+    pub token_program: &'c Program<'info, Token>,
+    pub amm_program: &'c AccountInfo<'info>,
+    pub amm_id: &'c AccountInfo<'info>,
+    pub amm_authority: &'c AccountInfo<'info>,
+    pub amm_open_orders: &'c AccountInfo<'info>,
+    pub amm_quantities_or_target_orders: &'c AccountInfo<'info>,
+    pub pool_coin_token_account: &'c AccountInfo<'info>,
+    pub pool_pc_token_account: &'c AccountInfo<'info>,
+    pub serum_program_id: &'c AccountInfo<'info>,
+    pub serum_market: &'c AccountInfo<'info>,
+    pub serum_bids: &'c AccountInfo<'info>,
+    pub serum_asks: &'c AccountInfo<'info>,
+    pub serum_event_queue: &'c AccountInfo<'info>,
+    pub serum_coin_vault_account: &'c AccountInfo<'info>,
+    pub serum_pc_vault_account: &'c AccountInfo<'info>,
+    pub serum_vault_signer: &'c AccountInfo<'info>,
+    pub source_token_account: &'c AccountInfo<'info>,
+    pub destination_token_account: &'c AccountInfo<'info>,
+}
+
+#[inline(always)]
+pub fn swap_base_in_raydium<'c, 'info>(
+    accounts: Box<SwapBaseInRaydiumAccounts<'c, 'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let mut data = Vec::with_capacity(17);
+    data.push(RAYDIUM_SWAP_BASE_IN_TAG);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+
+    invoke_signed(
+        &Instruction {
+            program_id: accounts.amm_program.key(),
+            accounts: vec![
+                AccountMeta::new_readonly(accounts.token_program.key(), false),
+                AccountMeta::new(accounts.amm_id.key(), false),
+                AccountMeta::new_readonly(accounts.amm_authority.key(), false),
+                AccountMeta::new(accounts.amm_open_orders.key(), false),
+                AccountMeta::new(accounts.amm_quantities_or_target_orders.key(), false),
+                AccountMeta::new(accounts.pool_coin_token_account.key(), false),
+                AccountMeta::new(accounts.pool_pc_token_account.key(), false),
+                AccountMeta::new_readonly(accounts.serum_program_id.key(), false),
+                AccountMeta::new(accounts.serum_market.key(), false),
+                AccountMeta::new(accounts.serum_bids.key(), false),
+                AccountMeta::new(accounts.serum_asks.key(), false),
+                AccountMeta::new(accounts.serum_event_queue.key(), false),
+                AccountMeta::new(accounts.serum_coin_vault_account.key(), false),
+                AccountMeta::new(accounts.serum_pc_vault_account.key(), false),
+                AccountMeta::new_readonly(accounts.serum_vault_signer.key(), false),
+                AccountMeta::new(accounts.source_token_account.key(), false),
+                AccountMeta::new(accounts.destination_token_account.key(), false),
+                AccountMeta::new_readonly(accounts.chamber_authority.key(), true),
+            ],
+            data,
+        },
+        &Box::new(vec![
+            accounts.token_program.to_account_info(),
+            accounts.amm_id.to_account_info(),
+            accounts.amm_authority.to_account_info(),
+            accounts.amm_open_orders.to_account_info(),
+            accounts.amm_quantities_or_target_orders.to_account_info(),
+            accounts.pool_coin_token_account.to_account_info(),
+            accounts.pool_pc_token_account.to_account_info(),
+            accounts.serum_program_id.to_account_info(),
+            accounts.serum_market.to_account_info(),
+            accounts.serum_bids.to_account_info(),
+            accounts.serum_asks.to_account_info(),
+            accounts.serum_event_queue.to_account_info(),
+            accounts.serum_coin_vault_account.to_account_info(),
+            accounts.serum_pc_vault_account.to_account_info(),
+            accounts.serum_vault_signer.to_account_info(),
+            accounts.source_token_account.to_account_info(),
+            accounts.destination_token_account.to_account_info(),
+            accounts.chamber_authority.to_account_info(),
+            accounts.amm_program.to_account_info(),
+        ]),
+        &[&[
+            utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+            accounts.chamber.key().as_ref(),
+            &[accounts.authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
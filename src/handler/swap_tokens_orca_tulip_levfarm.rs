@@ -0,0 +1,91 @@
+use crate::{state, utils};
+use anchor_lang::{prelude::*, solana_program::program::invoke_signed};
+use anchor_spl::token::Token;
+use tulipv2_sdk_levfarm::instructions::swap_tokens_orca_stats::{swap_tokens_orca_stats, OrcaSwap};
+
+pub struct SwapTokensOrcaTulipLevfarmAccounts<'c, 'info> {
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
+    pub chamber_authority: &'c AccountInfo<'info>,
+    pub leveraged_farm: &'c AccountInfo<'info>,
+    pub chamber_farm: &'c AccountInfo<'info>,
+    pub chamber_farm_obligation: &'c AccountInfo<'info>,
+    pub token_program: &'c Program<'info, Token>,
+    pub vault_signer: &'c AccountInfo<'info>,
+    pub swap_program_id: &'c AccountInfo<'info>,
+    pub swap_account: &'c AccountInfo<'info>,
+    pub swap_authority: &'c AccountInfo<'info>,
+    pub pool_coin_token_account: &'c AccountInfo<'info>,
+    pub pool_pc_token_account: &'c AccountInfo<'info>,
+    pub pool_token_mint: &'c AccountInfo<'info>,
+    pub pool_fee_account: &'c AccountInfo<'info>,
+    pub coin_wallet: &'c AccountInfo<'info>,
+    pub pc_wallet: &'c AccountInfo<'info>,
+    pub lending_market: &'c AccountInfo<'info>,
+    pub lending_market_authority: &'c AccountInfo<'info>,
+    pub lending_program: &'c AccountInfo<'info>,
+    pub position_info: &'c AccountInfo<'info>,
+    pub levfarm_program: &'c AccountInfo<'info>,
+}
+
+#[inline(always)]
+pub fn swap_tokens_orca_tulip_levfarm<'c, 'info>(
+    accounts: Box<SwapTokensOrcaTulipLevfarmAccounts>,
+    obligation_index: u8,
+) -> Result<()> {
+    invoke_signed(
+        &swap_tokens_orca_stats(
+            Box::new(OrcaSwap {
+                authority: accounts.chamber_authority.key(),
+                leveraged_farm: accounts.leveraged_farm.key(),
+                user_farm: accounts.chamber_farm.key(),
+                user_farm_obligation: accounts.chamber_farm_obligation.key(),
+                token_program: accounts.token_program.key(),
+                vault_signer: accounts.vault_signer.key(),
+                swap_program_id: accounts.swap_program_id.key(),
+                swap_account: accounts.swap_account.key(),
+                swap_authority: accounts.swap_authority.key(),
+                pool_coin_tokenaccount: accounts.pool_coin_token_account.key(),
+                pool_pc_tokenaccount: accounts.pool_pc_token_account.key(),
+                pool_token_mint: accounts.pool_token_mint.key(),
+                pool_fee_account: accounts.pool_fee_account.key(),
+                coin_wallet: accounts.coin_wallet.key(),
+                pc_wallet: accounts.pc_wallet.key(),
+            }),
+            accounts.lending_market.key(),
+            accounts.lending_market_authority.key(),
+            accounts.lending_program.key(),
+            accounts.position_info.key(),
+            obligation_index,
+        )
+        .unwrap(),
+        &Box::new(vec![
+            accounts.chamber_authority.to_account_info(),
+            accounts.leveraged_farm.to_account_info(),
+            accounts.chamber_farm.to_account_info(),
+            accounts.chamber_farm_obligation.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.vault_signer.to_account_info(),
+            accounts.swap_program_id.to_account_info(),
+            accounts.swap_account.to_account_info(),
+            accounts.swap_authority.to_account_info(),
+            accounts.pool_coin_token_account.to_account_info(),
+            accounts.pool_pc_token_account.to_account_info(),
+            accounts.pool_token_mint.to_account_info(),
+            accounts.pool_fee_account.to_account_info(),
+            accounts.coin_wallet.to_account_info(),
+            accounts.pc_wallet.to_account_info(),
+            accounts.lending_market.to_account_info(),
+            accounts.lending_market_authority.to_account_info(),
+            accounts.lending_program.to_account_info(),
+            accounts.position_info.to_account_info(),
+        ]),
+        &[&[
+            utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+            accounts.chamber.key().as_ref(),
+            &[accounts.authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
@@ -6,7 +6,8 @@ use tulipv2_sdk_levfarm::instructions::swap_tokens_raydium_stats::{
 };
 
 pub struct SwapTokensRaydiumTulipLevfarmAccounts<'c, 'info> {
-    pub chamber: &'c Account<'info, state::Chamber>,
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
     pub chamber_authority: &'c AccountInfo<'info>,
     pub leveraged_farm: &'c AccountInfo<'info>,
     pub chamber_farm: &'c AccountInfo<'info>,
@@ -107,7 +108,7 @@ pub fn swap_tokens_raydium_tulip_levfarm<'c, 'info>(
         &[&[
             utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
             accounts.chamber.key().as_ref(),
-            &[accounts.chamber.authority_bump],
+            &[accounts.authority_bump],
         ]],
     )?;
 
@@ -0,0 +1,23 @@
+use anchor_lang::{prelude::*, solana_program::program::invoke};
+use anchor_spl::token::{spl_token, Token};
+
+pub struct SyncNativeAccounts<'c, 'info> {
+    pub token_account: &'c AccountInfo<'info>,
+    pub token_program: &'c Program<'info, Token>,
+}
+
+#[inline(always)]
+pub fn sync_native<'c, 'info>(accounts: Box<SyncNativeAccounts<'c, 'info>>) -> Result<()> {
+    invoke(
+        &spl_token::instruction::sync_native(
+            &accounts.token_program.key(),
+            &accounts.token_account.key(),
+        )?,
+        &[
+            accounts.token_account.to_account_info(),
+            accounts.token_program.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::{state, utils};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token};
+
+pub struct WithdrawIdleBalanceAccounts<'c, 'info> {
+    pub chamber: &'c AccountLoader<'info, state::Chamber>,
+    pub authority_bump: u8,
+    pub chamber_authority: &'c AccountInfo<'info>,
+    pub chamber_base_ata: &'c AccountInfo<'info>,
+    pub chamber_quote_ata: &'c AccountInfo<'info>,
+    pub treasury_base_ata: &'c AccountInfo<'info>,
+    pub treasury_quote_ata: &'c AccountInfo<'info>,
+    pub base_destination: &'c AccountInfo<'info>,
+    pub quote_destination: &'c AccountInfo<'info>,
+    pub token_program: &'c Program<'info, Token>,
+}
+
+/// Transfer withdrawn idle balance out of chamber token accounts, exit fees to treasury
+/// and the rest to destinations.
+#[inline(always)]
+pub fn withdraw_idle_balance<'c, 'info>(
+    accounts: Box<WithdrawIdleBalanceAccounts<'c, 'info>>,
+    base_amount: u64,
+    quote_amount: u64,
+    base_fee: u64,
+    quote_fee: u64,
+) -> Result<()> {
+    let chamber_pubkey = accounts.chamber.key();
+    let seeds: &[&[&[u8]]] = &[&[
+        utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+        chamber_pubkey.as_ref(),
+        &[accounts.authority_bump],
+    ]];
+
+    for (from, to, amount) in [
+        (
+            accounts.chamber_base_ata,
+            accounts.treasury_base_ata,
+            base_fee,
+        ),
+        (
+            accounts.chamber_quote_ata,
+            accounts.treasury_quote_ata,
+            quote_fee,
+        ),
+        (
+            accounts.chamber_base_ata,
+            accounts.base_destination,
+            base_amount - base_fee,
+        ),
+        (
+            accounts.chamber_quote_ata,
+            accounts.quote_destination,
+            quote_amount - quote_fee,
+        ),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        let cpi_accounts = token::Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: accounts.chamber_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+    }
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
 pub mod error;
+pub mod event;
 mod handler;
+pub mod math;
 mod processor;
 pub mod state;
 pub mod utils;
@@ -7,7 +9,7 @@ pub mod utils;
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    token::{spl_token, Mint, Token, TokenAccount},
 };
 use error::ChamberError;
 
@@ -27,12 +29,45 @@ mod chamber {
     pub fn initialize_chamber<'a, 'b, 'c, 'info>(
         ctx: Context<'_, '_, 'c, 'info, InitializeChamber<'info>>,
         leveraged_farm: Pubkey,
+        index: u8,
         bump: u8,
         authority_bump: u8,
+        epoch_bump: u8,
         protocol_type: crate::state::ProtocolType,
+        amm_type: crate::state::AmmType,
+        config: crate::state::ChamberConfig,
+        keeper: Pubkey,
+        pool: crate::state::ChamberPool,
+        base_oracle: Pubkey,
+        quote_oracle: Pubkey,
     ) -> Result<()> {
-        ctx.accounts
-            .process(leveraged_farm, bump, authority_bump, protocol_type)
+        ctx.accounts.process(
+            leveraged_farm,
+            index,
+            bump,
+            authority_bump,
+            epoch_bump,
+            protocol_type,
+            amm_type,
+            config,
+            keeper,
+            pool,
+            base_oracle,
+            quote_oracle,
+        )
+    }
+
+    pub fn update_chamber_config<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, UpdateChamberConfig<'info>>,
+        config: crate::state::ChamberConfig,
+    ) -> Result<()> {
+        ctx.accounts.process(config)
+    }
+
+    pub fn collect_management_fee<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CollectManagementFee<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
     }
 
     pub fn initialize_chamber_strategy<'a, 'b, 'c, 'info>(
@@ -50,6 +85,82 @@ mod chamber {
         ctx.accounts.process(bump, base_amount, quote_amount)
     }
 
+    pub fn initialize_user_position_single_sided<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InitializeUserPositionSingleSided<'info>>,
+        bump: u8,
+        side: state::TokenSide,
+        amount: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(bump, side, amount, minimum_amount_out)
+    }
+
+    pub fn initialize_user_position_native<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InitializeUserPositionNative<'info>>,
+        bump: u8,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(bump, base_amount, quote_amount)
+    }
+
+    pub fn withdraw_user_position<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, WithdrawUserPosition<'info>>,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(base_amount, quote_amount)
+    }
+
+    pub fn withdraw_user_position_native<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, WithdrawUserPositionNative<'info>>,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(base_amount, quote_amount)
+    }
+
+    pub fn queue_deposit<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, QueueDeposit<'info>>,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(base_amount, quote_amount)
+    }
+
+    pub fn request_withdrawal<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RequestWithdrawal<'info>>,
+        shares: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(shares)
+    }
+
+    pub fn cancel_withdrawal<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CancelWithdrawal<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn process_chamber_epoch<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ProcessChamberEpoch<'info>>,
+        next_epoch_bump: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .process(&ctx.remaining_accounts, next_epoch_bump)
+    }
+
+    pub fn claim_deposit<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimDeposit<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn claim_withdrawal<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimWithdrawal<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
     pub fn deposit_chamber<'a, 'b, 'c, 'info>(
         ctx: Context<'_, '_, 'c, 'info, DepositChamber<'info>>,
         base_amount: u64,
@@ -59,14 +170,35 @@ mod chamber {
             .process(&ctx.remaining_accounts, base_amount, quote_amount)
     }
 
-    pub fn settle_chamber_position<'a, 'b, 'c, 'info>(
-        ctx: Context<'_, '_, 'c, 'info, SettleChamberPosition<'info>>,
+    pub fn rebalance_chamber<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, RebalanceChamber<'info>>,
+        nonce: u8,
+        meta_nonce: u8,
     ) -> Result<()> {
-        ctx.accounts.process(&ctx.remaining_accounts)
+        ctx.accounts
+            .process(&ctx.remaining_accounts, nonce, meta_nonce)
+    }
+
+    pub fn deleverage_chamber<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, DeleverageChamber<'info>>,
+        nonce_0: u8,
+        nonce_1: u8,
+        meta_nonce_0: u8,
+        meta_nonce_1: u8,
+        withdraw_percent: u8,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            &ctx.remaining_accounts,
+            nonce_0,
+            nonce_1,
+            meta_nonce_0,
+            meta_nonce_1,
+            withdraw_percent,
+        )
     }
 
-    pub fn settle_chamber_position2<'a, 'b, 'c, 'info>(
-        ctx: Context<'_, '_, 'c, 'info, SettleChamberPosition2<'info>>,
+    pub fn crank_chamber<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CrankChamber<'info>>,
         nonce_0: u8,
         nonce_1: u8,
         meta_nonce_0: u8,
@@ -80,19 +212,71 @@ mod chamber {
             meta_nonce_1,
         )
     }
+
+    pub fn swap_chamber<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SwapChamber<'info>>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts
+            .process(&ctx.remaining_accounts, amount_in, minimum_amount_out, data)
+    }
+
+    pub fn migrate_chamber<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MigrateChamber<'info>>,
+        keeper: Pubkey,
+        pool: crate::state::ChamberPool,
+        base_oracle: Pubkey,
+        quote_oracle: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts
+            .process(keeper, pool, base_oracle, quote_oracle)
+    }
+
+    pub fn migrate_legacy_chamber<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MigrateLegacyChamber<'info>>,
+        epoch_bump: u8,
+        amm_type: crate::state::AmmType,
+        config: crate::state::ChamberConfig,
+        keeper: Pubkey,
+        pool: crate::state::ChamberPool,
+        base_oracle: Pubkey,
+        quote_oracle: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            epoch_bump,
+            amm_type,
+            config,
+            keeper,
+            pool,
+            base_oracle,
+            quote_oracle,
+        )
+    }
+
+    pub fn migrate_user_position<'a, 'b, 'c, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MigrateUserPosition<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
 }
 
 #[derive(Accounts)]
-#[instruction(leveraged_farm: Pubkey, bump: u8, authority_bump: u8, protocol_type: state::ProtocolType)]
+#[instruction(leveraged_farm: Pubkey, index: u8, bump: u8, authority_bump: u8, epoch_bump: u8, protocol_type: state::ProtocolType, amm_type: state::AmmType, config: state::ChamberConfig)]
 pub struct InitializeChamber<'info> {
     /// Protocol agnostic `Vault` and strategy controller.
-    #[account(init, seeds = [utils::CHAMBER_PREFIX.as_bytes(), leveraged_farm.key().as_ref()], bump, space = state::Chamber::LEN, payer = payer)]
-    pub chamber: Box<Account<'info, state::Chamber>>,
+    #[account(init, seeds = [utils::CHAMBER_PREFIX.as_bytes(), leveraged_farm.key().as_ref(), &[index]], bump, space = state::Chamber::LEN, payer = payer)]
+    pub chamber: AccountLoader<'info, state::Chamber>,
 
     /// Chamber authority.
     #[account(seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = authority_bump)]
     pub authority: UncheckedAccount<'info>,
 
+    /// First `ChamberEpoch`, which collects queued requests.
+    #[account(init, seeds = [utils::CHAMBER_EPOCH_PREFIX.as_bytes(), chamber.key().as_ref(), &0u64.to_le_bytes()], bump, space = state::ChamberEpoch::LEN, payer = payer)]
+    pub epoch: Box<Account<'info, state::ChamberEpoch>>,
+
     /// `Chamber` strategy uninitialized base associated token account.
     #[account(mut)]
     pub base_ata: UncheckedAccount<'info>,
@@ -104,23 +288,88 @@ pub struct InitializeChamber<'info> {
     pub base_mint: Box<Account<'info, Mint>>,
     pub quote_mint: Box<Account<'info, Mint>>,
 
+    /// Treasury base token account for entry and exit fees.
+    #[account(constraint = treasury_base_ata.mint == base_mint.key())]
+    pub treasury_base_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Treasury quote token account for entry and exit fees.
+    #[account(constraint = treasury_quote_ata.mint == quote_mint.key())]
+    pub treasury_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Alias for chamber admin.
     #[account(mut)]
     pub payer: Signer<'info>,
 
+    pub clock_sysvar: Sysvar<'info, Clock>,
     pub rent_sysvar: Sysvar<'info, Rent>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(config: state::ChamberConfig)]
+pub struct UpdateChamberConfig<'info> {
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        has_one = admin,
+    )]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    pub admin: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct CollectManagementFee<'info> {
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        has_one = admin,
+        has_one = authority,
+        constraint = chamber.load()?.base_ata == chamber_base_ata.key(),
+        constraint = chamber.load()?.quote_ata == chamber_quote_ata.key(),
+        constraint = chamber.load()?.treasury_base_ata == treasury_base_ata.key(),
+        constraint = chamber.load()?.treasury_quote_ata == treasury_quote_ata.key(),
+    )]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// Chamber authority.
+    #[account(seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.load()?.authority_bump)]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub chamber_base_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub chamber_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_base_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    pub admin: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
+}
+
 #[derive(Accounts)]
 pub struct InitializeChamberStrategy<'info> {
     /// Protocol agnostic `Vault` and strategy controller.
-    #[account(seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.leveraged_farm.key().as_ref()], bump = chamber.bump)]
-    pub chamber: Box<Account<'info, state::Chamber>>,
+    #[account(mut, seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()], bump = chamber.load()?.bump)]
+    pub chamber: AccountLoader<'info, state::Chamber>,
 
     /// Chamber authority.
-    #[account(mut, seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.authority_bump)]
+    #[account(mut, seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.load()?.authority_bump)]
     pub authority: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -185,6 +434,16 @@ pub struct InitializeChamberStrategy<'info> {
     pub lending_program: UncheckedAccount<'info>,
     pub levfarm_program: UncheckedAccount<'info>,
     pub solfarm_vault_program: UncheckedAccount<'info>
+
+    /// Accounts expected by Solend:
+
+    /// Lending `Obligation` state, allocated by client and owned by lending program.
+    #[account(mut)]
+    pub obligation: UncheckedAccount<'info>,
+
+    pub lending_market: UncheckedAccount<'info>,
+
+    pub lending_program: UncheckedAccount<'info>,
     */
 }
 
@@ -197,17 +456,20 @@ pub struct InitializeUserPosition<'info> {
 
     /// Protocol agnostic `Vault` and strategy controller.
     #[account(
-        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.leveraged_farm.key().as_ref()],
-        bump = chamber.bump,
-        constraint = chamber.base_ata == chamber_base_ata.key(),
-        constraint = chamber.quote_ata == chamber_quote_ata.key(),
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        constraint = chamber.load()?.base_ata == chamber_base_ata.key(),
+        constraint = chamber.load()?.quote_ata == chamber_quote_ata.key(),
+        constraint = chamber.load()?.treasury_base_ata == treasury_base_ata.key(),
+        constraint = chamber.load()?.treasury_quote_ata == treasury_quote_ata.key(),
     )]
-    pub chamber: Box<Account<'info, state::Chamber>>,
+    pub chamber: AccountLoader<'info, state::Chamber>,
 
-    #[account(mut, constraint = user_base_ata.mint == chamber.base_mint)]
+    #[account(mut, constraint = user_base_ata.mint == chamber.load()?.base_mint)]
     pub user_base_ata: Box<Account<'info, TokenAccount>>,
 
-    #[account(mut, constraint = user_quote_ata.mint == chamber.quote_mint)]
+    #[account(mut, constraint = user_quote_ata.mint == chamber.load()?.quote_mint)]
     pub user_quote_ata: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
@@ -216,33 +478,43 @@ pub struct InitializeUserPosition<'info> {
     #[account(mut)]
     pub chamber_quote_ata: Box<Account<'info, TokenAccount>>,
 
+    #[account(mut)]
+    pub treasury_base_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_quote_ata: Box<Account<'info, TokenAccount>>,
+
     /// Alias for user.
     #[account(mut)]
     pub payer: Signer<'info>,
 
+    pub clock_sysvar: Sysvar<'info, Clock>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(base_amount: u64, quote_amount: u64)]
-pub struct DepositChamber<'info> {
-    #[account(seeds = [utils::USER_POSITION_PREFIX.as_bytes(), payer.key().as_ref(), chamber.key().as_ref()], bump = user_position.bump)]
+#[instruction(bump: u8, base_amount: u64, quote_amount: u64)]
+pub struct InitializeUserPositionNative<'info> {
+    /// Represent `payer` position in provided `Chamber` strategy.
+    #[account(init, seeds = [utils::USER_POSITION_PREFIX.as_bytes(), payer.key().as_ref(), chamber.key().as_ref()], bump, space = state::UserPosition::LEN, payer = payer)]
     pub user_position: Box<Account<'info, state::UserPosition>>,
 
-    /// Protocol agnostic `Vault` and strategy controller.
+    /// Protocol agnostic `Vault` and strategy controller with native SOL base.
     #[account(
-        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.leveraged_farm.key().as_ref()],
-        bump = chamber.bump,
-        has_one = authority,
-        constraint = chamber.base_ata == chamber_base_ata.key(),
-        constraint = chamber.quote_ata == chamber_quote_ata.key(),
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        constraint = chamber.load()?.base_mint == spl_token::native_mint::id(),
+        constraint = chamber.load()?.base_ata == chamber_base_ata.key(),
+        constraint = chamber.load()?.quote_ata == chamber_quote_ata.key(),
+        constraint = chamber.load()?.treasury_base_ata == treasury_base_ata.key(),
+        constraint = chamber.load()?.treasury_quote_ata == treasury_quote_ata.key(),
     )]
-    pub chamber: Box<Account<'info, state::Chamber>>,
+    pub chamber: AccountLoader<'info, state::Chamber>,
 
-    /// Chamber authority.
-    #[account(mut, seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.authority_bump)]
-    pub authority: UncheckedAccount<'info>,
+    #[account(mut, constraint = user_quote_ata.mint == chamber.load()?.quote_mint)]
+    pub user_quote_ata: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
     pub chamber_base_ata: Box<Account<'info, TokenAccount>>,
@@ -250,268 +522,1041 @@ pub struct DepositChamber<'info> {
     #[account(mut)]
     pub chamber_quote_ata: Box<Account<'info, TokenAccount>>,
 
+    #[account(mut)]
+    pub treasury_base_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Alias for user, pays base amount in lamports.
     #[account(mut)]
     pub payer: Signer<'info>,
 
     pub clock_sysvar: Sysvar<'info, Clock>,
-    pub rent_sysvar: Sysvar<'info, Rent>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-    /*
-    /// Accounts expected by Tulip:
+}
 
-    /// 0.
-    #[account(mut)]
-    pub chamber_farm: UncheckedAccount<'info>,
+#[derive(Accounts)]
+#[instruction(bump: u8, side: state::TokenSide, amount: u64, minimum_amount_out: u64)]
+pub struct InitializeUserPositionSingleSided<'info> {
+    /// Represent `payer` position in provided `Chamber` strategy.
+    #[account(init, seeds = [utils::USER_POSITION_PREFIX.as_bytes(), payer.key().as_ref(), chamber.key().as_ref()], bump, space = state::UserPosition::LEN, payer = payer)]
+    pub user_position: Box<Account<'info, state::UserPosition>>,
 
-    /// 1.
-    pub leveraged_farm: UncheckedAccount<'info>,
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        has_one = authority,
+        constraint = chamber.load()?.base_ata == chamber_base_ata.key(),
+        constraint = chamber.load()?.quote_ata == chamber_quote_ata.key(),
+        constraint = chamber.load()?.treasury_base_ata == treasury_base_ata.key(),
+        constraint = chamber.load()?.treasury_quote_ata == treasury_quote_ata.key(),
+    )]
+    pub chamber: AccountLoader<'info, state::Chamber>,
 
-    /// 2.
-    #[account(mut)]
-    pub chamber_farm_obligation_0: UncheckedAccount<'info>,
+    /// Chamber authority.
+    #[account(seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.load()?.authority_bump)]
+    pub authority: UncheckedAccount<'info>,
 
-    /// 3.
-    #[account(mut)]
-    pub chamber_farm_obligation_1: UncheckedAccount<'info>,
+    /// User token account of deposited `side`.
+    #[account(mut, constraint = user_ata.mint == chamber.load()?.mint(side))]
+    pub user_ata: Box<Account<'info, TokenAccount>>,
 
-    /// 4.
     #[account(mut)]
-    pub coin_destination_token_account: UncheckedAccount<'info>,
+    pub chamber_base_ata: Box<Account<'info, TokenAccount>>,
 
-    /// 5.
     #[account(mut)]
-    pub pc_destination_token_account: UncheckedAccount<'info>,
+    pub chamber_quote_ata: Box<Account<'info, TokenAccount>>,
 
-    /// 6.
     #[account(mut)]
-    pub coin_deposit_reserve_account: UncheckedAccount<'info>,
+    pub treasury_base_ata: Box<Account<'info, TokenAccount>>,
 
-    /// 7.
     #[account(mut)]
-    pub pc_deposit_reserve_account: UncheckedAccount<'info>,
-
-    /// 8.
-    pub coin_reserve_liquidity_oracle: UncheckedAccount<'info>,
+    pub treasury_quote_ata: Box<Account<'info, TokenAccount>>,
 
-    /// 9.
-    pub pc_reserve_liquidity_oracle: UncheckedAccount<'info>,
-
-    /// 10.
-    pub lending_market_account: UncheckedAccount<'info>,
+    /// `Raydium` AMM v4 program.
+    #[account(address = tulipv2_sdk_common::config::RAYDIUM_LIQUIDITY_V4)]
+    pub amm_program: UncheckedAccount<'info>,
 
-    /// 11.
-    pub derived_lending_market_authority: UncheckedAccount<'info>,
+    /// AMM of chamber pool.
+    #[account(mut, constraint = chamber.load()?.pool.amm_id == amm_id.key() @ ChamberError::InvalidPool)]
+    pub amm_id: UncheckedAccount<'info>,
 
-    /// 12.
-    pub lending_program: UncheckedAccount<'info>,
+    pub amm_authority: UncheckedAccount<'info>,
 
-    /// 13.
     #[account(mut)]
-    pub coin_source_reserve_liquidity_token_account: UncheckedAccount<'info>,
+    pub amm_open_orders: UncheckedAccount<'info>,
 
-    /// 14.
     #[account(mut)]
-    pub pc_source_reserve_liquidity_token_account: UncheckedAccount<'info>,
+    pub amm_quantities_or_target_orders: UncheckedAccount<'info>,
+
+    /// Base reserve of chamber pool.
+    #[account(mut, constraint = chamber.load()?.pool.coin_token_account == pool_coin_token_account.key() @ ChamberError::InvalidPool)]
+    pub pool_coin_token_account: UncheckedAccount<'info>,
+
+    /// Quote reserve of chamber pool.
+    #[account(mut, constraint = chamber.load()?.pool.pc_token_account == pool_pc_token_account.key() @ ChamberError::InvalidPool)]
+    pub pool_pc_token_account: UncheckedAccount<'info>,
+
+    pub serum_program_id: UncheckedAccount<'info>,
 
-    /// 15.
     #[account(mut)]
-    pub coin_reserve_liquidity_fee_receiver: UncheckedAccount<'info>,
+    pub serum_market: UncheckedAccount<'info>,
 
-    /// 16.
     #[account(mut)]
-    pub pc_reserve_liquidity_fee_receiver: UncheckedAccount<'info>,
+    pub serum_bids: UncheckedAccount<'info>,
 
-    /// 17.
-    pub borrow_authorizer: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub serum_asks: UncheckedAccount<'info>,
 
-    /// 18.
-    pub lp_pyth_price_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub serum_event_queue: UncheckedAccount<'info>,
 
-    /// 19.
     #[account(mut)]
-    pub vault_account: UncheckedAccount<'info>,
+    pub serum_coin_vault_account: UncheckedAccount<'info>,
 
-    /// 20.
     #[account(mut)]
-    pub position_info_0: UncheckedAccount<'info>,
+    pub serum_pc_vault_account: UncheckedAccount<'info>,
 
-    /// 21.
+    pub serum_vault_signer: UncheckedAccount<'info>,
+
+    /// Alias for user.
     #[account(mut)]
-    pub position_info_1: UncheckedAccount<'info>,
+    pub payer: Signer<'info>,
 
-    /// 22.
-    pub levfarm_program: UncheckedAccount<'info>,
-    */
+    pub clock_sysvar: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SettleChamberPosition<'info> {
+#[instruction(base_amount: u64, quote_amount: u64)]
+pub struct WithdrawUserPosition<'info> {
+    #[account(mut, seeds = [utils::USER_POSITION_PREFIX.as_bytes(), payer.key().as_ref(), chamber.key().as_ref()], bump = user_position.bump)]
+    pub user_position: Box<Account<'info, state::UserPosition>>,
+
     /// Protocol agnostic `Vault` and strategy controller.
     #[account(
-        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.leveraged_farm.key().as_ref()],
-        bump = chamber.bump,
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
         has_one = authority,
+        constraint = chamber.load()?.base_ata == chamber_base_ata.key(),
+        constraint = chamber.load()?.quote_ata == chamber_quote_ata.key(),
+        constraint = chamber.load()?.treasury_base_ata == treasury_base_ata.key(),
+        constraint = chamber.load()?.treasury_quote_ata == treasury_quote_ata.key(),
     )]
-    pub chamber: Box<Account<'info, state::Chamber>>,
+    pub chamber: AccountLoader<'info, state::Chamber>,
 
     /// Chamber authority.
-    #[account(mut, seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.authority_bump)]
+    #[account(seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.load()?.authority_bump)]
     pub authority: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+    #[account(mut, constraint = user_base_ata.mint == chamber.load()?.base_mint)]
+    pub user_base_ata: Box<Account<'info, TokenAccount>>,
 
-    pub clock_sysvar: Sysvar<'info, Clock>,
-    pub rent_sysvar: Sysvar<'info, Rent>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    /*
-    /// Accounts expected by Tulip:
+    #[account(mut, constraint = user_quote_ata.mint == chamber.load()?.quote_mint)]
+    pub user_quote_ata: Box<Account<'info, TokenAccount>>,
 
-    /// 0.
     #[account(mut)]
-    pub leveraged_farm: UncheckedAccount<'info>,
+    pub chamber_base_ata: Box<Account<'info, TokenAccount>>,
 
-    /// 1.
     #[account(mut)]
-    pub chamber_farm: UncheckedAccount<'info>,
+    pub chamber_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_base_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Alias for user.
+    pub payer: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(base_amount: u64, quote_amount: u64)]
+pub struct WithdrawUserPositionNative<'info> {
+    #[account(mut, seeds = [utils::USER_POSITION_PREFIX.as_bytes(), payer.key().as_ref(), chamber.key().as_ref()], bump = user_position.bump)]
+    pub user_position: Box<Account<'info, state::UserPosition>>,
+
+    /// Protocol agnostic `Vault` and strategy controller with native SOL base.
+    #[account(
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        has_one = authority,
+        has_one = base_mint,
+        constraint = chamber.load()?.base_mint == spl_token::native_mint::id(),
+        constraint = chamber.load()?.base_ata == chamber_base_ata.key(),
+        constraint = chamber.load()?.quote_ata == chamber_quote_ata.key(),
+        constraint = chamber.load()?.treasury_base_ata == treasury_base_ata.key(),
+        constraint = chamber.load()?.treasury_quote_ata == treasury_quote_ata.key(),
+    )]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// Chamber authority.
+    #[account(seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.load()?.authority_bump)]
+    pub authority: UncheckedAccount<'info>,
+
+    /// Temporary wrapped SOL account, closed to `payer` within instruction.
+    #[account(
+        init,
+        seeds = [utils::NATIVE_UNWRAP_PREFIX.as_bytes(), chamber.key().as_ref(), payer.key().as_ref()],
+        bump,
+        token::mint = base_mint,
+        token::authority = authority,
+        payer = payer,
+    )]
+    pub unwrap_account: Box<Account<'info, TokenAccount>>,
+
+    pub base_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, constraint = user_quote_ata.mint == chamber.load()?.quote_mint)]
+    pub user_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub chamber_base_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub chamber_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_base_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Alias for user, receives base amount in lamports.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+
+    /// Required by `unwrap_account` initialization.
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(base_amount: u64, quote_amount: u64)]
+pub struct DepositChamber<'info> {
+    #[account(mut, seeds = [utils::USER_POSITION_PREFIX.as_bytes(), payer.key().as_ref(), chamber.key().as_ref()], bump = user_position.bump)]
+    pub user_position: Box<Account<'info, state::UserPosition>>,
+
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        has_one = authority,
+        constraint = chamber.load()?.base_ata == chamber_base_ata.key(),
+        constraint = chamber.load()?.quote_ata == chamber_quote_ata.key(),
+    )]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// Current `ChamberEpoch`, which queued deposits are excluded from NAV.
+    #[account(seeds = [utils::CHAMBER_EPOCH_PREFIX.as_bytes(), chamber.key().as_ref(), &chamber.load()?.epoch.to_le_bytes()], bump = epoch.bump)]
+    pub epoch: Box<Account<'info, state::ChamberEpoch>>,
+
+    /// Chamber authority.
+    #[account(mut, seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.load()?.authority_bump)]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub chamber_base_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub chamber_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+    pub rent_sysvar: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    /*
+    /// Accounts expected by Tulip:
+
+    /// 0.
+    #[account(mut)]
+    pub chamber_farm: UncheckedAccount<'info>,
+
+    /// 1.
+    pub leveraged_farm: UncheckedAccount<'info>,
+
+    /// 2.
+    #[account(mut)]
+    pub chamber_farm_obligation_0: UncheckedAccount<'info>,
+
+    /// 3.
+    #[account(mut)]
+    pub chamber_farm_obligation_1: UncheckedAccount<'info>,
+
+    /// 4.
+    #[account(mut)]
+    pub coin_destination_token_account: UncheckedAccount<'info>,
+
+    /// 5.
+    #[account(mut)]
+    pub pc_destination_token_account: UncheckedAccount<'info>,
+
+    /// 6.
+    #[account(mut)]
+    pub coin_deposit_reserve_account: UncheckedAccount<'info>,
+
+    /// 7.
+    #[account(mut)]
+    pub pc_deposit_reserve_account: UncheckedAccount<'info>,
+
+    /// 8.
+    pub coin_reserve_liquidity_oracle: UncheckedAccount<'info>,
+
+    /// 9.
+    pub pc_reserve_liquidity_oracle: UncheckedAccount<'info>,
+
+    /// 10.
+    pub lending_market_account: UncheckedAccount<'info>,
+
+    /// 11.
+    pub derived_lending_market_authority: UncheckedAccount<'info>,
+
+    /// 12.
+    pub lending_program: UncheckedAccount<'info>,
+
+    /// 13.
+    #[account(mut)]
+    pub coin_source_reserve_liquidity_token_account: UncheckedAccount<'info>,
+
+    /// 14.
+    #[account(mut)]
+    pub pc_source_reserve_liquidity_token_account: UncheckedAccount<'info>,
+
+    /// 15.
+    #[account(mut)]
+    pub coin_reserve_liquidity_fee_receiver: UncheckedAccount<'info>,
+
+    /// 16.
+    #[account(mut)]
+    pub pc_reserve_liquidity_fee_receiver: UncheckedAccount<'info>,
+
+    /// 17.
+    pub borrow_authorizer: UncheckedAccount<'info>,
+
+    /// 18.
+    pub lp_pyth_price_account: UncheckedAccount<'info>,
+
+    /// 19.
+    #[account(mut)]
+    pub vault_account: UncheckedAccount<'info>,
+
+    /// 20.
+    #[account(mut)]
+    pub position_info_0: UncheckedAccount<'info>,
+
+    /// 21.
+    #[account(mut)]
+    pub position_info_1: UncheckedAccount<'info>,
+
+    /// 22.
+    pub levfarm_program: UncheckedAccount<'info>,
+
+    /// Accounts expected by Solend:
+
+    /// 0.
+    pub lending_program: UncheckedAccount<'info>,
+
+    /// 1.
+    pub lending_market: UncheckedAccount<'info>,
+
+    /// 2.
+    pub lending_market_authority: UncheckedAccount<'info>,
+
+    /// 3.
+    #[account(mut)]
+    pub obligation: UncheckedAccount<'info>,
+
+    /// 4.
+    #[account(mut)]
+    pub base_reserve: UncheckedAccount<'info>,
+
+    /// 5.
+    #[account(mut)]
+    pub base_reserve_liquidity_supply: UncheckedAccount<'info>,
+
+    /// 6.
+    #[account(mut)]
+    pub base_reserve_liquidity_fee_receiver: UncheckedAccount<'info>,
+
+    /// 7.
+    pub base_price: UncheckedAccount<'info>,
+
+    /// 8.
+    #[account(mut)]
+    pub quote_reserve: UncheckedAccount<'info>,
+
+    /// 9.
+    #[account(mut)]
+    pub quote_reserve_liquidity_supply: UncheckedAccount<'info>,
+
+    /// 10.
+    #[account(mut)]
+    pub quote_reserve_collateral_mint: UncheckedAccount<'info>,
+
+    /// 11.
+    #[account(mut)]
+    pub quote_reserve_collateral_supply: UncheckedAccount<'info>,
+
+    /// 12.
+    pub quote_price: UncheckedAccount<'info>,
+
+    /// 13.
+    #[account(mut)]
+    pub chamber_collateral_ata: UncheckedAccount<'info>,
+
+    /// 14.
+    pub amm_program: UncheckedAccount<'info>,
+
+    /// 15.
+    #[account(mut)]
+    pub amm_id: UncheckedAccount<'info>,
+
+    /// 16.
+    pub amm_authority: UncheckedAccount<'info>,
+
+    /// 17.
+    #[account(mut)]
+    pub amm_open_orders: UncheckedAccount<'info>,
+
+    /// 18.
+    #[account(mut)]
+    pub amm_target_orders: UncheckedAccount<'info>,
+
+    /// 19.
+    #[account(mut)]
+    pub lp_mint: UncheckedAccount<'info>,
+
+    /// 20.
+    #[account(mut)]
+    pub pool_coin_token_account: UncheckedAccount<'info>,
+
+    /// 21.
+    #[account(mut)]
+    pub pool_pc_token_account: UncheckedAccount<'info>,
+
+    /// 22.
+    pub serum_market: UncheckedAccount<'info>,
+
+    /// 23.
+    #[account(mut)]
+    pub chamber_lp_ata: UncheckedAccount<'info>,
+    */
+}
+
+#[derive(Accounts)]
+#[instruction(nonce: u8, meta_nonce: u8)]
+pub struct RebalanceChamber<'info> {
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        has_one = authority,
+        constraint = chamber.load()?.base_ata == chamber_base_ata.key(),
+        constraint = chamber.load()?.quote_ata == chamber_quote_ata.key(),
+        constraint = chamber.load()?.is_keeper(&payer.key()) @ ChamberError::InvalidKeeper,
+    )]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// Chamber authority.
+    #[account(mut, seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.load()?.authority_bump)]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub chamber_base_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub chamber_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Pool base reserve.
+    #[account(constraint = chamber.load()?.pool.coin_token_account == pool_coin_token_account.key() @ ChamberError::InvalidPool)]
+    pub pool_coin_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Pool quote reserve.
+    #[account(constraint = chamber.load()?.pool.pc_token_account == pool_pc_token_account.key() @ ChamberError::InvalidPool)]
+    pub pool_pc_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(constraint = chamber.load()?.pool.lp_mint == lp_mint.key() @ ChamberError::InvalidPool)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// Pyth price account of base token, which pool price is checked against.
+    #[account(constraint = chamber.load()?.base_oracle == base_oracle.key() @ ChamberError::InvalidOracle)]
+    pub base_oracle: UncheckedAccount<'info>,
+
+    /// Pyth price account of quote token, which pool price is checked against.
+    #[account(constraint = chamber.load()?.quote_oracle == quote_oracle.key() @ ChamberError::InvalidOracle)]
+    pub quote_oracle: UncheckedAccount<'info>,
+
+    /// Alias for keeper, chamber admin or keeper.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+    pub rent_sysvar: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    // Remaining accounts expected by Tulip are the same as in `DepositChamber` when base
+    // is borrowed, and the same as in `DeleverageChamber` when base debt is repaid.
+}
+
+#[derive(Accounts)]
+#[instruction(nonce_0: u8, nonce_1: u8, meta_nonce_0: u8, meta_nonce_1: u8, withdraw_percent: u8)]
+pub struct DeleverageChamber<'info> {
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        has_one = authority,
+        constraint = chamber.load()?.is_keeper(&payer.key()) @ ChamberError::InvalidKeeper,
+    )]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// Chamber authority.
+    #[account(mut, seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.load()?.authority_bump)]
+    pub authority: UncheckedAccount<'info>,
+
+    /// Alias for keeper, chamber admin or keeper.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+    pub rent_sysvar: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    /*
+    /// Accounts expected by Tulip are the same as in `CrankChamber` stake step, followed by:
+    /// Obligations 24 and 25 must be owned by levfarm and derived for chamber authority.
+
+    /// 29.
+    #[account(mut)]
+    pub position_info_0: UncheckedAccount<'info>,
+
+    /// 30.
+    #[account(mut)]
+    pub position_info_1: UncheckedAccount<'info>,
+    */
+}
+
+#[derive(Accounts)]
+#[instruction(base_amount: u64, quote_amount: u64)]
+pub struct QueueDeposit<'info> {
+    #[account(mut, seeds = [utils::USER_POSITION_PREFIX.as_bytes(), payer.key().as_ref(), chamber.key().as_ref()], bump = user_position.bump)]
+    pub user_position: Box<Account<'info, state::UserPosition>>,
+
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(mut, seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()], bump = chamber.load()?.bump)]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// Current `ChamberEpoch`.
+    #[account(mut, seeds = [utils::CHAMBER_EPOCH_PREFIX.as_bytes(), chamber.key().as_ref(), &chamber.load()?.epoch.to_le_bytes()], bump = epoch.bump)]
+    pub epoch: Box<Account<'info, state::ChamberEpoch>>,
+
+    /// Alias for user.
+    pub payer: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction(shares: u64)]
+pub struct RequestWithdrawal<'info> {
+    #[account(mut, seeds = [utils::USER_POSITION_PREFIX.as_bytes(), payer.key().as_ref(), chamber.key().as_ref()], bump = user_position.bump)]
+    pub user_position: Box<Account<'info, state::UserPosition>>,
+
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()], bump = chamber.load()?.bump)]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// Current `ChamberEpoch`.
+    #[account(mut, seeds = [utils::CHAMBER_EPOCH_PREFIX.as_bytes(), chamber.key().as_ref(), &chamber.load()?.epoch.to_le_bytes()], bump = epoch.bump)]
+    pub epoch: Box<Account<'info, state::ChamberEpoch>>,
+
+    /// Alias for user.
+    pub payer: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    #[account(mut, seeds = [utils::USER_POSITION_PREFIX.as_bytes(), payer.key().as_ref(), chamber.key().as_ref()], bump = user_position.bump)]
+    pub user_position: Box<Account<'info, state::UserPosition>>,
+
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()], bump = chamber.load()?.bump)]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// `ChamberEpoch` holding withdrawal request.
+    #[account(mut, seeds = [utils::CHAMBER_EPOCH_PREFIX.as_bytes(), chamber.key().as_ref(), &user_position.withdrawal_epoch.to_le_bytes()], bump = epoch.bump)]
+    pub epoch: Box<Account<'info, state::ChamberEpoch>>,
+
+    /// Alias for user.
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(next_epoch_bump: u8)]
+pub struct ProcessChamberEpoch<'info> {
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        has_one = authority,
+        constraint = chamber.load()?.base_ata == chamber_base_ata.key(),
+        constraint = chamber.load()?.quote_ata == chamber_quote_ata.key(),
+        constraint = chamber.load()?.is_keeper(&payer.key()) @ ChamberError::InvalidKeeper,
+    )]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// Chamber authority.
+    #[account(mut, seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.load()?.authority_bump)]
+    pub authority: UncheckedAccount<'info>,
+
+    /// Current `ChamberEpoch`, processed by instruction.
+    #[account(mut, seeds = [utils::CHAMBER_EPOCH_PREFIX.as_bytes(), chamber.key().as_ref(), &chamber.load()?.epoch.to_le_bytes()], bump = epoch.bump)]
+    pub epoch: Box<Account<'info, state::ChamberEpoch>>,
+
+    /// Uninitialized `ChamberEpoch`, which collects requests after processing.
+    #[account(init, seeds = [utils::CHAMBER_EPOCH_PREFIX.as_bytes(), chamber.key().as_ref(), &(chamber.load()?.epoch + 1).to_le_bytes()], bump, space = state::ChamberEpoch::LEN, payer = payer)]
+    pub next_epoch: Box<Account<'info, state::ChamberEpoch>>,
+
+    #[account(mut)]
+    pub chamber_base_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub chamber_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Alias for keeper, chamber admin or keeper.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+    pub rent_sysvar: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    /*
+    /// Accounts expected by Tulip are the same as in `DepositChamber`.
+    /// Obligations 2 and 3 must be owned by levfarm and derived for chamber authority,
+    /// Pyth accounts 8 and 9 must match chamber oracles.
+     */
+}
+
+#[derive(Accounts)]
+pub struct ClaimDeposit<'info> {
+    #[account(mut, seeds = [utils::USER_POSITION_PREFIX.as_bytes(), payer.key().as_ref(), chamber.key().as_ref()], bump = user_position.bump)]
+    pub user_position: Box<Account<'info, state::UserPosition>>,
+
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()], bump = chamber.load()?.bump)]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// `ChamberEpoch` holding queued deposit.
+    #[account(seeds = [utils::CHAMBER_EPOCH_PREFIX.as_bytes(), chamber.key().as_ref(), &user_position.queued_epoch.to_le_bytes()], bump = epoch.bump)]
+    pub epoch: Box<Account<'info, state::ChamberEpoch>>,
+
+    /// Alias for user.
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWithdrawal<'info> {
+    #[account(mut, seeds = [utils::USER_POSITION_PREFIX.as_bytes(), payer.key().as_ref(), chamber.key().as_ref()], bump = user_position.bump)]
+    pub user_position: Box<Account<'info, state::UserPosition>>,
+
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        has_one = authority,
+        constraint = chamber.load()?.quote_ata == chamber_quote_ata.key(),
+    )]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// Chamber authority.
+    #[account(seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.load()?.authority_bump)]
+    pub authority: UncheckedAccount<'info>,
+
+    /// `ChamberEpoch` holding withdrawal request.
+    #[account(seeds = [utils::CHAMBER_EPOCH_PREFIX.as_bytes(), chamber.key().as_ref(), &user_position.withdrawal_epoch.to_le_bytes()], bump = epoch.bump)]
+    pub epoch: Box<Account<'info, state::ChamberEpoch>>,
+
+    #[account(mut, constraint = user_quote_ata.mint == chamber.load()?.quote_mint)]
+    pub user_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub chamber_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Alias for user.
+    pub payer: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(nonce_0: u8, nonce_1: u8, meta_nonce_0: u8, meta_nonce_1: u8)]
+pub struct CrankChamber<'info> {
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(
+        mut,
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        has_one = authority,
+    )]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// Chamber authority.
+    #[account(mut, seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.load()?.authority_bump)]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+    pub rent_sysvar: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    /*
+    /// Accounts expected by Tulip for Raydium `Borrowed` (swap) and `Swapped` (add liquidity) steps:
+
+    /// 0.
+    #[account(mut)]
+    pub leveraged_farm: UncheckedAccount<'info>,
+
+    /// 1.
+    #[account(mut)]
+    pub chamber_farm: UncheckedAccount<'info>,
+
+    /// 2.
+    #[account(mut)]
+    pub chamber_farm_obligation_0: UncheckedAccount<'info>,
+
+    /// 3.
+    #[account(mut)]
+    pub chamber_farm_obligation_1: UncheckedAccount<'info>,
+
+    /// 4.
+    #[account(mut)]
+    pub amm_id: UncheckedAccount<'info>,
+
+    /// 5.
+    #[account(mut)]
+    pub amm_authority: UncheckedAccount<'info>,
+
+    /// 6.
+    #[account(mut)]
+    pub amm_open_orders: UncheckedAccount<'info>,
+
+    /// 7.
+    #[account(mut)]
+    pub amm_quantities_or_target_orders: UncheckedAccount<'info>,
+
+    /// 8.
+    #[account(mut)]
+    pub pool_coin_token_account: UncheckedAccount<'info>,
+
+    /// 9.
+    #[account(mut)]
+    pub pool_pc_token_account: UncheckedAccount<'info>,
+
+    /// 10.
+    pub serum_program_id: UncheckedAccount<'info>,
+
+    /// 11.
+    #[account(mut)]
+    pub serum_market: UncheckedAccount<'info>,
+
+    /// 12.
+    #[account(mut)]
+    pub serum_bids: UncheckedAccount<'info>,
+
+    /// 13.
+    #[account(mut)]
+    pub serum_asks: UncheckedAccount<'info>,
+
+    /// 14.
+    #[account(mut)]
+    pub serum_event_queue: UncheckedAccount<'info>,
+
+    /// 15.
+    #[account(mut)]
+    pub serum_coin_vault_account: UncheckedAccount<'info>,
+
+    /// 16.
+    #[account(mut)]
+    pub serum_pc_vault_account: UncheckedAccount<'info>,
+
+    /// 17.
+    #[account(mut)]
+    pub vault_signer: UncheckedAccount<'info>,
+
+    /// 18.
+    #[account(mut)]
+    pub coin_wallet: UncheckedAccount<'info>,
+
+    /// 19.
+    #[account(mut)]
+    pub pc_wallet: UncheckedAccount<'info>,
+
+    /// 20.
+    pub lending_market: UncheckedAccount<'info>,
+
+    /// 21.
+    pub lending_market_authority: UncheckedAccount<'info>,
+
+    /// 22.
+    pub lending_program: UncheckedAccount<'info>,
+
+    /// 23.
+    #[account(mut)]
+    pub position_info_0: UncheckedAccount<'info>,
+
+    /// 24.
+    #[account(mut)]
+    pub position_info_1: UncheckedAccount<'info>,
+
+    /// 25.
+    #[account(mut)]
+    pub lp_mint: UncheckedAccount<'info>,
+
+    /// 26.
+    #[account(mut)]
+    pub lp_token_account_0: UncheckedAccount<'info>,
+
+    /// 27.
+    #[account(mut)]
+    pub lp_token_account_1: UncheckedAccount<'info>,
+
+    /// 28.
+    pub pyth_price_account: UncheckedAccount<'info>,
+
+    /// 29.
+    pub levfarm_program: UncheckedAccount<'info>,
+
+    /// 30.
+    pub liquidity_program: UncheckedAccount<'info>,
+
+    /// Accounts expected by Tulip for Raydium `LiquidityAdded` (stake) step:
+
+    /// 0.
+    #[account(mut)]
+    pub chamber_farm: UncheckedAccount<'info>,
+
+    /// 1.
+    #[account(mut)]
+    pub chamber_farm_obligation_vault_0: UncheckedAccount<'info>,
 
     /// 2.
     #[account(mut)]
-    pub chamber_farm_obligation_0: UncheckedAccount<'info>,
+    pub chamber_farm_obligation_vault_1: UncheckedAccount<'info>,
 
     /// 3.
     #[account(mut)]
-    pub chamber_farm_obligation_1: UncheckedAccount<'info>,
+    pub leveraged_farm: UncheckedAccount<'info>,
 
     /// 4.
-    #[account(mut)]
-    pub amm_id: UncheckedAccount<'info>,
+    pub vault_program: UncheckedAccount<'info>,
 
     /// 5.
     #[account(mut)]
-    pub amm_authority: UncheckedAccount<'info>,
+    pub authority_token_account_0: UncheckedAccount<'info>,
 
     /// 6.
     #[account(mut)]
-    pub amm_open_orders: UncheckedAccount<'info>,
+    pub authority_token_account_1: UncheckedAccount<'info>,
 
     /// 7.
     #[account(mut)]
-    pub amm_quantities_or_target_orders: UncheckedAccount<'info>,
+    pub vault_pda_account: UncheckedAccount<'info>,
 
     /// 8.
     #[account(mut)]
-    pub pool_coin_token_account: UncheckedAccount<'info>,
+    pub vault: UncheckedAccount<'info>,
 
     /// 9.
     #[account(mut)]
-    pub pool_pc_token_account: UncheckedAccount<'info>,
+    pub lp_token_account: UncheckedAccount<'info>,
 
     /// 10.
-    pub serum_program_id: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub chamber_balance_account_0: UncheckedAccount<'info>,
 
     /// 11.
     #[account(mut)]
-    pub serum_market: UncheckedAccount<'info>,
+    pub chamber_balance_account_1: UncheckedAccount<'info>,
 
     /// 12.
-    #[account(mut)]
-    pub serum_bids: UncheckedAccount<'info>,
+    pub stake_program: UncheckedAccount<'info>,
 
     /// 13.
     #[account(mut)]
-    pub serum_asks: UncheckedAccount<'info>,
+    pub pool_id: UncheckedAccount<'info>,
 
     /// 14.
     #[account(mut)]
-    pub serum_event_queue: UncheckedAccount<'info>,
+    pub pool_authority: UncheckedAccount<'info>,
 
     /// 15.
     #[account(mut)]
-    pub serum_coin_vault_account: UncheckedAccount<'info>,
+    pub vault_info_account: UncheckedAccount<'info>,
 
     /// 16.
     #[account(mut)]
-    pub serum_pc_vault_account: UncheckedAccount<'info>,
+    pub pool_lp_token_account: UncheckedAccount<'info>,
 
     /// 17.
     #[account(mut)]
-    pub vault_signer: UncheckedAccount<'info>,
+    pub reward_a_token_account: UncheckedAccount<'info>,
 
     /// 18.
     #[account(mut)]
-    pub coin_wallet: UncheckedAccount<'info>,
+    pub pool_reward_a_token_account: UncheckedAccount<'info>,
 
     /// 19.
     #[account(mut)]
-    pub pc_wallet: UncheckedAccount<'info>,
+    pub reward_b_token_account: UncheckedAccount<'info>,
 
     /// 20.
-    pub lending_market: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub pool_reward_b_token_account: UncheckedAccount<'info>,
 
     /// 21.
-    pub lending_market_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub chamber_balance_metadata_0: UncheckedAccount<'info>,
 
     /// 22.
-    pub lending_program: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub chamber_balance_metadata_1: UncheckedAccount<'info>,
 
     /// 23.
-    #[account(mut)]
-    pub position_info_0: UncheckedAccount<'info>,
+    pub lending_market: UncheckedAccount<'info>,
 
     /// 24.
     #[account(mut)]
-    pub position_info_1: UncheckedAccount<'info>,
+    pub chamber_farm_obligation_0: UncheckedAccount<'info>,
 
     /// 25.
     #[account(mut)]
-    pub lp_mint: UncheckedAccount<'info>,
+    pub chamber_farm_obligation_1: UncheckedAccount<'info>,
 
     /// 26.
-    #[account(mut)]
-    pub lp_token_account_0: UncheckedAccount<'info>,
+    pub lending_market_authority: UncheckedAccount<'info>,
 
     /// 27.
-    #[account(mut)]
-    pub lp_token_account_1: UncheckedAccount<'info>,
+    pub lending_program: UncheckedAccount<'info>,
 
     /// 28.
-    pub pyth_price_account: UncheckedAccount<'info>,
-
-    /// 29.
     pub levfarm_program: UncheckedAccount<'info>,
 
-    /// 30.
-    pub liquidity_program: UncheckedAccount<'info>,
-    */
-}
+    /// Accounts expected by Tulip for Orca `Borrowed` (swap) and `Swapped` (add liquidity) steps:
 
-#[derive(Accounts)]
-#[instruction(nonce_0: u8, nonce_1: u8, meta_nonce_0: u8, meta_nonce_1: u8)]
-pub struct SettleChamberPosition2<'info> {
-    /// Protocol agnostic `Vault` and strategy controller.
-    #[account(
-        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.leveraged_farm.key().as_ref()],
-        bump = chamber.bump,
-        has_one = authority,
-    )]
-    pub chamber: Box<Account<'info, state::Chamber>>,
+    /// 0.
+    #[account(mut)]
+    pub leveraged_farm: UncheckedAccount<'info>,
 
-    /// Chamber authority.
-    #[account(mut, seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.authority_bump)]
-    pub authority: UncheckedAccount<'info>,
+    /// 1.
+    #[account(mut)]
+    pub chamber_farm: UncheckedAccount<'info>,
 
+    /// 2.
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub chamber_farm_obligation_0: UncheckedAccount<'info>,
 
-    pub clock_sysvar: Sysvar<'info, Clock>,
-    pub rent_sysvar: Sysvar<'info, Rent>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    /*
-    /// Accounts expected by Tulip:
+    /// 3.
+    #[account(mut)]
+    pub chamber_farm_obligation_1: UncheckedAccount<'info>,
+
+    /// 4.
+    #[account(mut)]
+    pub vault_signer: UncheckedAccount<'info>,
+
+    /// 5.
+    pub swap_program: UncheckedAccount<'info>,
+
+    /// 6.
+    #[account(mut)]
+    pub swap_account: UncheckedAccount<'info>,
+
+    /// 7.
+    pub swap_authority: UncheckedAccount<'info>,
+
+    /// 8.
+    #[account(mut)]
+    pub pool_coin_token_account: UncheckedAccount<'info>,
+
+    /// 9.
+    #[account(mut)]
+    pub pool_pc_token_account: UncheckedAccount<'info>,
+
+    /// 10.
+    #[account(mut)]
+    pub pool_token_mint: UncheckedAccount<'info>,
+
+    /// 11.
+    #[account(mut)]
+    pub pool_fee_account: UncheckedAccount<'info>,
+
+    /// 12.
+    #[account(mut)]
+    pub coin_wallet: UncheckedAccount<'info>,
+
+    /// 13.
+    #[account(mut)]
+    pub pc_wallet: UncheckedAccount<'info>,
+
+    /// 14.
+    pub lending_market: UncheckedAccount<'info>,
+
+    /// 15.
+    pub lending_market_authority: UncheckedAccount<'info>,
+
+    /// 16.
+    pub lending_program: UncheckedAccount<'info>,
+
+    /// 17.
+    #[account(mut)]
+    pub position_info_0: UncheckedAccount<'info>,
+
+    /// 18.
+    #[account(mut)]
+    pub position_info_1: UncheckedAccount<'info>,
+
+    /// 19.
+    #[account(mut)]
+    pub lp_token_account_0: UncheckedAccount<'info>,
+
+    /// 20.
+    #[account(mut)]
+    pub lp_token_account_1: UncheckedAccount<'info>,
+
+    /// 21.
+    pub pyth_price_account: UncheckedAccount<'info>,
+
+    /// 22.
+    pub levfarm_program: UncheckedAccount<'info>,
+
+    /// Accounts expected by Tulip for Orca `LiquidityAdded` (stake) step:
 
     /// 0.
     #[account(mut)]
@@ -534,93 +1579,217 @@ pub struct SettleChamberPosition2<'info> {
 
     /// 5.
     #[account(mut)]
-    pub authority_token_account_0: UncheckedAccount<'info>,
+    pub vault: UncheckedAccount<'info>,
 
     /// 6.
     #[account(mut)]
-    pub authority_token_account_1: UncheckedAccount<'info>,
+    pub vault_pda_account: UncheckedAccount<'info>,
 
     /// 7.
     #[account(mut)]
-    pub vault_pda_account: UncheckedAccount<'info>,
+    pub chamber_balance_account_0: UncheckedAccount<'info>,
 
     /// 8.
     #[account(mut)]
-    pub vault: UncheckedAccount<'info>,
+    pub chamber_balance_account_1: UncheckedAccount<'info>,
 
     /// 9.
     #[account(mut)]
-    pub lp_token_account: UncheckedAccount<'info>,
+    pub lp_token_account_0: UncheckedAccount<'info>,
 
     /// 10.
     #[account(mut)]
-    pub chamber_balance_account_0: UncheckedAccount<'info>,
+    pub lp_token_account_1: UncheckedAccount<'info>,
 
     /// 11.
     #[account(mut)]
-    pub chamber_balance_account_1: UncheckedAccount<'info>,
+    pub farm_token_account_0: UncheckedAccount<'info>,
 
     /// 12.
-    pub stake_program: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub farm_token_account_1: UncheckedAccount<'info>,
 
     /// 13.
     #[account(mut)]
-    pub pool_id: UncheckedAccount<'info>,
+    pub reward_token_account_0: UncheckedAccount<'info>,
 
     /// 14.
     #[account(mut)]
-    pub pool_authority: UncheckedAccount<'info>,
+    pub reward_token_account_1: UncheckedAccount<'info>,
 
     /// 15.
     #[account(mut)]
-    pub vault_info_account: UncheckedAccount<'info>,
+    pub global_base_token_vault: UncheckedAccount<'info>,
 
     /// 16.
     #[account(mut)]
-    pub pool_lp_token_account: UncheckedAccount<'info>,
+    pub farm_token_mint: UncheckedAccount<'info>,
 
     /// 17.
     #[account(mut)]
-    pub reward_a_token_account: UncheckedAccount<'info>,
+    pub global_farm: UncheckedAccount<'info>,
 
     /// 18.
     #[account(mut)]
-    pub pool_reward_a_token_account: UncheckedAccount<'info>,
+    pub orca_user_farm_0: UncheckedAccount<'info>,
 
     /// 19.
     #[account(mut)]
-    pub reward_b_token_account: UncheckedAccount<'info>,
+    pub orca_user_farm_1: UncheckedAccount<'info>,
 
     /// 20.
     #[account(mut)]
-    pub pool_reward_b_token_account: UncheckedAccount<'info>,
+    pub global_reward_token_vault: UncheckedAccount<'info>,
 
     /// 21.
+    pub convert_authority: UncheckedAccount<'info>,
+
+    /// 22.
+    pub aqua_farm_program: UncheckedAccount<'info>,
+
+    /// 23.
     #[account(mut)]
     pub chamber_balance_metadata_0: UncheckedAccount<'info>,
 
-    /// 22.
+    /// 24.
     #[account(mut)]
     pub chamber_balance_metadata_1: UncheckedAccount<'info>,
 
-    /// 23.
+    /// 25.
     pub lending_market: UncheckedAccount<'info>,
 
-    /// 24.
+    /// 26.
     #[account(mut)]
     pub chamber_farm_obligation_0: UncheckedAccount<'info>,
 
-    /// 25.
+    /// 27.
     #[account(mut)]
     pub chamber_farm_obligation_1: UncheckedAccount<'info>,
 
-    /// 26.
+    /// 28.
     pub lending_market_authority: UncheckedAccount<'info>,
 
-    /// 27.
+    /// 29.
     pub lending_program: UncheckedAccount<'info>,
 
-    /// 28.
+    /// 30.
     pub levfarm_program: UncheckedAccount<'info>,
     */
 }
+
+#[derive(Accounts)]
+pub struct SwapChamber<'info> {
+    /// Protocol agnostic `Vault` and strategy controller.
+    #[account(
+        seeds = [utils::CHAMBER_PREFIX.as_bytes(), chamber.load()?.leveraged_farm.key().as_ref(), chamber.load()?.index_seed()],
+        bump = chamber.load()?.bump,
+        has_one = authority,
+        constraint = chamber.load()?.is_keeper(&admin.key()) @ ChamberError::InvalidKeeper,
+        constraint = chamber.load()?.base_ata == chamber_base_ata.key(),
+        constraint = chamber.load()?.quote_ata == chamber_quote_ata.key(),
+    )]
+    pub chamber: AccountLoader<'info, state::Chamber>,
+
+    /// Current `ChamberEpoch`, which queued deposits stay in chamber token accounts.
+    #[account(seeds = [utils::CHAMBER_EPOCH_PREFIX.as_bytes(), chamber.key().as_ref(), &chamber.load()?.epoch.to_le_bytes()], bump = epoch.bump)]
+    pub epoch: Box<Account<'info, state::ChamberEpoch>>,
+
+    /// Chamber authority.
+    #[account(seeds = [utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(), chamber.key().as_ref()], bump = chamber.load()?.authority_bump)]
+    pub authority: UncheckedAccount<'info>,
+
+    /// Holds users idle base balances, checked after swap.
+    pub chamber_base_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Holds users idle quote balances and claimable withdrawals, checked after swap.
+    pub chamber_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Chamber token account spent by swap, e.g. reward or base/quote ata.
+    #[account(mut, constraint = source_ata.owner == authority.key())]
+    pub source_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Chamber token account receiving swap output.
+    #[account(mut, constraint = destination_ata.owner == authority.key())]
+    pub destination_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Aggregator program configured in `ChamberConfig`.
+    #[account(
+        constraint = swap_program.key() == chamber.load()?.config.swap_program @ ChamberError::InvalidSwapProgram,
+        constraint = utils::is_swap_program(&swap_program.key()) @ ChamberError::InvalidSwapProgram,
+    )]
+    pub swap_program: UncheckedAccount<'info>,
+
+    /// Chamber admin, or keeper converting rewards into chamber base or quote token.
+    pub admin: Signer<'info>,
+    // Remaining accounts are aggregator route accounts passed to `swap_program` as is,
+    // chamber `authority` is signed by program.
+}
+
+#[derive(Accounts)]
+pub struct MigrateChamber<'info> {
+    /// `Chamber` in any known layout version, deserialized by processor after realloc.
+    #[account(mut, owner = crate::ID)]
+    pub chamber: UncheckedAccount<'info>,
+
+    /// Alias for chamber admin, pays rent for grown account.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch_bump: u8)]
+pub struct MigrateLegacyChamber<'info> {
+    /// `Chamber` of the first release, deserialized by processor before realloc.
+    #[account(mut, owner = crate::ID)]
+    pub chamber: UncheckedAccount<'info>,
+
+    /// First `ChamberEpoch`, legacy chambers had no epochs.
+    #[account(init, seeds = [utils::CHAMBER_EPOCH_PREFIX.as_bytes(), chamber.key().as_ref(), &0u64.to_le_bytes()], bump, space = state::ChamberEpoch::LEN, payer = admin)]
+    pub epoch: Box<Account<'info, state::ChamberEpoch>>,
+
+    pub base_mint: Box<Account<'info, Mint>>,
+    pub quote_mint: Box<Account<'info, Mint>>,
+
+    /// `Chamber` base associated token account.
+    pub base_ata: Box<Account<'info, TokenAccount>>,
+
+    /// `Chamber` quote associated token account.
+    pub quote_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Treasury base token account for entry and exit fees.
+    #[account(constraint = treasury_base_ata.mint == base_mint.key())]
+    pub treasury_base_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Treasury quote token account for entry and exit fees.
+    #[account(constraint = treasury_quote_ata.mint == quote_mint.key())]
+    pub treasury_quote_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Chamber>,
+
+    /// Legacy chambers have no admin, so program upgrade authority takes over.
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ChamberError::InvalidUpgradeAuthority)]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// Becomes chamber admin, pays rent for grown account and epoch.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub clock_sysvar: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateUserPosition<'info> {
+    /// `UserPosition` in any known layout version, deserialized by processor after realloc.
+    #[account(mut, owner = crate::ID)]
+    pub user_position: UncheckedAccount<'info>,
+
+    /// Anyone may migrate a position, payer covers rent for grown account.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,364 @@
+//! Module provide strategy math helpers.
+
+use crate::{
+    state::{StrategyLeg, TokenSide},
+    ChamberError,
+};
+use std::{convert::TryFrom, result::Result};
+use tulipv2_sdk_common::math::{
+    common::{TryAdd, TryDiv, TryMul, TrySub},
+    decimal::Decimal,
+};
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Shares minted per unit of value for the first deposit.
+pub const SHARE_DECIMALS: u8 = 6;
+
+/// Obligation LTV targeted by Solend strategy deposits.
+pub const SOLEND_TARGET_LTV_BPS: u16 = 5_000;
+
+/// Maximum leverage of Tulip obligation opened by deposit, position value to deposited value.
+pub const TULIP_DEPOSIT_LEVERAGE: u64 = 3;
+
+/// Part of deposit supplied to first Tulip obligation, which borrows quote, `1 / n`.
+pub const TULIP_FIRST_LEG_DIVISOR: u64 = 4;
+
+/// Maximum deviation of pool price from oracle prices, at which exposure is priced from pool.
+pub const MAX_POOL_PRICE_DEVIATION_BPS: u64 = 100;
+
+/// Minimum value of free idle funds in USD, which epoch processing deploys.
+pub const MIN_IDLE_DEPLOY_VALUE: u64 = 10;
+
+/// Calculate management fee accrued on `amount` for `elapsed` seconds.
+///
+/// Returns accrued fee and sub-unit remainder, which must be passed to the next
+/// accrual, so splitting a period into many small calls charges exactly the same fee.
+pub fn calculate_management_fee(
+    amount: u64,
+    fee_bps: u16,
+    elapsed: u64,
+    remainder: u128,
+) -> Result<(u64, u128), ChamberError> {
+    let denominator = (BPS_DENOMINATOR as u128)
+        .checked_mul(SECONDS_PER_YEAR as u128)
+        .ok_or(ChamberError::MathOverflow)?;
+
+    let numerator = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(ChamberError::MathOverflow)?
+        .checked_mul(elapsed as u128)
+        .ok_or(ChamberError::MathOverflow)?
+        .checked_add(remainder)
+        .ok_or(ChamberError::MathOverflow)?;
+
+    let fee = u64::try_from(numerator / denominator).map_err(|_| ChamberError::MathOverflow)?;
+
+    Ok((fee, numerator % denominator))
+}
+
+/// Calculate fee taken from `amount` with `fee_bps` rate, rounded down.
+pub fn calculate_fee(amount: u64, fee_bps: u16) -> Result<u64, ChamberError> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(ChamberError::MathOverflow)?
+        / (BPS_DENOMINATOR as u128);
+
+    u64::try_from(fee).map_err(|_| ChamberError::MathOverflow)
+}
+
+/// Calculate part of single-sided deposit `amount`, which must be swapped to the
+/// other token to match `base_split_bps` split by value.
+pub fn calculate_swap_amount(
+    amount: u64,
+    side: TokenSide,
+    base_split_bps: u16,
+) -> Result<u64, ChamberError> {
+    let swap_bps = match side {
+        TokenSide::Base => BPS_DENOMINATOR
+            .checked_sub(base_split_bps as u64)
+            .ok_or(ChamberError::MathOverflow)?,
+        TokenSide::Quote => base_split_bps as u64,
+    };
+
+    let swap_amount = (amount as u128)
+        .checked_mul(swap_bps as u128)
+        .ok_or(ChamberError::MathOverflow)?
+        / (BPS_DENOMINATOR as u128);
+
+    u64::try_from(swap_amount).map_err(|_| ChamberError::MathOverflow)
+}
+
+/// Calculate part of quote `amount`, which is provided to liquidity, while the rest is
+/// supplied as collateral, so borrowing base of the same value keeps obligation at `ltv_bps`.
+pub fn calculate_liquidity_amount(amount: u64, ltv_bps: u16) -> Result<u64, ChamberError> {
+    let liquidity_amount = (amount as u128)
+        .checked_mul(ltv_bps as u128)
+        .ok_or(ChamberError::MathOverflow)?
+        / (BPS_DENOMINATOR as u128 + ltv_bps as u128);
+
+    u64::try_from(liquidity_amount).map_err(|_| ChamberError::MathOverflow)
+}
+
+/// Calculate `10^decimals` token units multiplier.
+pub fn decimals_factor(decimals: u8) -> Result<u64, ChamberError> {
+    10u64
+        .checked_pow(decimals as u32)
+        .ok_or(ChamberError::MathOverflow)
+}
+
+/// Calculate value of `amount` tokens with provided price.
+pub fn calculate_value(amount: u64, price: Decimal, decimals: u8) -> anchor_lang::Result<Decimal> {
+    Ok(price.try_mul(amount)?.try_div(decimals_factor(decimals)?)?)
+}
+
+/// Calculate amount of token with provided price worth `value`, rounded down.
+pub fn calculate_amount(value: Decimal, price: Decimal, decimals: u8) -> anchor_lang::Result<u64> {
+    Ok(value
+        .try_div(price)?
+        .try_mul(decimals_factor(decimals)?)?
+        .try_floor_u64()?)
+}
+
+/// Calculate amount of token with `price`, which is borrowed against `deposit_value`,
+/// so obligation reaches `TULIP_DEPOSIT_LEVERAGE`. Rounded down.
+pub fn calculate_borrow_amount(
+    deposit_value: Decimal,
+    price: Decimal,
+    decimals: u8,
+) -> anchor_lang::Result<u64> {
+    Ok(deposit_value
+        .try_mul(TULIP_DEPOSIT_LEVERAGE - 1)?
+        .try_div(price)?
+        .try_mul(decimals_factor(decimals)?)?
+        .try_floor_u64()?)
+}
+
+/// Calculate split of deposit between Tulip obligations.
+///
+/// First leg receives `1 / TULIP_FIRST_LEG_DIVISOR` of both amounts and borrows quote,
+/// second leg receives the rest and borrows base.
+pub fn calculate_tulip_deposit(
+    base_amount: u64,
+    quote_amount: u64,
+    base_price: Decimal,
+    quote_price: Decimal,
+    base_decimals: u8,
+    quote_decimals: u8,
+) -> anchor_lang::Result<[StrategyLeg; 2]> {
+    let mut legs = [StrategyLeg::default(); 2];
+
+    legs[0].base_collateral = base_amount / TULIP_FIRST_LEG_DIVISOR;
+    legs[0].quote_collateral = quote_amount / TULIP_FIRST_LEG_DIVISOR;
+    legs[1].base_collateral = base_amount - legs[0].base_collateral;
+    legs[1].quote_collateral = quote_amount - legs[0].quote_collateral;
+
+    let deposit_value = |leg: &StrategyLeg| -> anchor_lang::Result<Decimal> {
+        Ok(
+            calculate_value(leg.base_collateral, base_price, base_decimals)?.try_add(
+                calculate_value(leg.quote_collateral, quote_price, quote_decimals)?,
+            )?,
+        )
+    };
+
+    legs[0].quote_debt =
+        calculate_borrow_amount(deposit_value(&legs[0])?, quote_price, quote_decimals)?;
+    legs[1].base_debt =
+        calculate_borrow_amount(deposit_value(&legs[1])?, base_price, base_decimals)?;
+
+    Ok(legs)
+}
+
+/// Calculate shares minted for `value` deposited into chamber worth `nav` with
+/// `total_shares` outstanding.
+pub fn calculate_shares(
+    value: Decimal,
+    total_shares: u64,
+    nav: Decimal,
+) -> anchor_lang::Result<u64> {
+    if total_shares == 0 || nav == Decimal::zero() {
+        return Ok(value
+            .try_mul(10u64.pow(SHARE_DECIMALS as u32))?
+            .try_floor_u64()?);
+    }
+
+    Ok(value.try_mul(total_shares)?.try_div(nav)?.try_floor_u64()?)
+}
+
+/// Calculate value of `shares` in chamber worth `nav` with `total_shares` outstanding.
+pub fn calculate_shares_value(
+    shares: u64,
+    total_shares: u64,
+    nav: Decimal,
+) -> anchor_lang::Result<Decimal> {
+    if total_shares == 0 {
+        return Ok(Decimal::zero());
+    }
+
+    Ok(nav.try_mul(shares)?.try_div(total_shares)?)
+}
+
+/// Calculate `part / total` share of `amount`, rounded down.
+pub fn calculate_pro_rata(amount: u64, part: u64, total: u64) -> Result<u64, ChamberError> {
+    if total == 0 {
+        return Ok(0);
+    }
+
+    let result = (amount as u128)
+        .checked_mul(part as u128)
+        .ok_or(ChamberError::MathOverflow)?
+        / (total as u128);
+
+    u64::try_from(result).map_err(|_| ChamberError::MathOverflow)
+}
+
+/// Calculate idle balance units issued for `amount` added to balances worth `total_amount`
+/// with `total_units` outstanding, rounded down.
+pub fn calculate_units(
+    amount: u64,
+    total_units: u64,
+    total_amount: u64,
+) -> Result<u64, ChamberError> {
+    if total_units == 0 || total_amount == 0 {
+        return Ok(amount);
+    }
+
+    calculate_pro_rata(amount, total_units, total_amount)
+}
+
+/// Calculate idle balance units burned for `amount` taken from balances worth `total_amount`
+/// with `total_units` outstanding, rounded up, so rounding never favours the withdrawer.
+pub fn calculate_units_ceil(
+    amount: u64,
+    total_units: u64,
+    total_amount: u64,
+) -> Result<u64, ChamberError> {
+    if total_amount == 0 {
+        return Ok(0);
+    }
+
+    let result = (amount as u128)
+        .checked_mul(total_units as u128)
+        .ok_or(ChamberError::MathOverflow)?
+        .checked_add(total_amount as u128 - 1)
+        .ok_or(ChamberError::MathOverflow)?
+        / (total_amount as u128);
+
+    u64::try_from(result).map_err(|_| ChamberError::MathOverflow)
+}
+
+/// Calculate amount of `units` of balances worth `total_amount` with `total_units`
+/// outstanding, rounded down.
+pub fn calculate_units_amount(
+    units: u64,
+    total_units: u64,
+    total_amount: u64,
+) -> Result<u64, ChamberError> {
+    calculate_pro_rata(units, total_amount, total_units)
+}
+
+/// Calculate base amount provided to liquidity and base amount borrowed by all legs.
+///
+/// Liquidity is base share of pool reserve `pool_base_amount` owned by `lp_amounts` out of
+/// `lp_supply`, so it follows pool price. Rounded down.
+pub fn calculate_base_exposure(
+    legs: &[StrategyLeg],
+    lp_amounts: &[u64],
+    pool_base_amount: u64,
+    lp_supply: u64,
+) -> Result<(u64, u64), ChamberError> {
+    let mut lp_amount = 0u64;
+    let mut base_debt = 0u64;
+
+    for (leg, leg_lp_amount) in legs.iter().zip(lp_amounts) {
+        lp_amount = lp_amount
+            .checked_add(*leg_lp_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        base_debt = base_debt
+            .checked_add(leg.base_debt)
+            .ok_or(ChamberError::MathOverflow)?;
+    }
+
+    let base_liquidity = calculate_pro_rata(pool_base_amount, lp_amount, lp_supply)?;
+
+    Ok((base_liquidity, base_debt))
+}
+
+/// Calculate percent of leg liquidity, which must be unwound to repay `net_exposure` of
+/// excess base debt, when leg holds `base_liquidity` and owes `base_debt`.
+///
+/// Rounded up and capped at 100, returns 0 when unwinding the leg can't reduce net debt.
+pub fn calculate_repay_percent(
+    net_exposure: u64,
+    base_liquidity: u64,
+    base_debt: u64,
+) -> Result<u8, ChamberError> {
+    if base_debt <= base_liquidity {
+        return Ok(0);
+    }
+
+    let leg_exposure = (base_debt - base_liquidity) as u128;
+    let percent = (net_exposure as u128)
+        .checked_mul(100)
+        .ok_or(ChamberError::MathOverflow)?
+        .checked_add(leg_exposure - 1)
+        .ok_or(ChamberError::MathOverflow)?
+        / leg_exposure;
+
+    Ok(percent.min(100) as u8)
+}
+
+/// Calculate drift of net base exposure in basis points of base liquidity.
+pub fn calculate_exposure_drift_bps(
+    base_liquidity: u64,
+    base_debt: u64,
+) -> Result<u64, ChamberError> {
+    if base_liquidity == 0 {
+        return Ok(0);
+    }
+
+    let net_exposure = if base_liquidity > base_debt {
+        base_liquidity - base_debt
+    } else {
+        base_debt - base_liquidity
+    };
+
+    let drift_bps = (net_exposure as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(ChamberError::MathOverflow)?
+        / (base_liquidity as u128);
+
+    u64::try_from(drift_bps).map_err(|_| ChamberError::MathOverflow)
+}
+
+/// Calculate deviation of pool price from oracle prices in basis points.
+///
+/// Constant product pool holds reserves of the same value, so deviation is the difference
+/// of base and quote reserves values at oracle prices, relative to quote reserve value.
+pub fn calculate_pool_price_deviation_bps(
+    pool_base_amount: u64,
+    pool_quote_amount: u64,
+    base_price: Decimal,
+    quote_price: Decimal,
+    base_decimals: u8,
+    quote_decimals: u8,
+) -> anchor_lang::Result<u64> {
+    let base_value = calculate_value(pool_base_amount, base_price, base_decimals)?;
+    let quote_value = calculate_value(pool_quote_amount, quote_price, quote_decimals)?;
+
+    if quote_value == Decimal::zero() {
+        return Err(ChamberError::InvalidPool.into());
+    }
+
+    let difference = if base_value > quote_value {
+        base_value.try_sub(quote_value)?
+    } else {
+        quote_value.try_sub(base_value)?
+    };
+
+    Ok(difference
+        .try_mul(BPS_DENOMINATOR)?
+        .try_div(quote_value)?
+        .try_floor_u64()?)
+}
@@ -0,0 +1,31 @@
+use crate::{event, state, CancelWithdrawal, ChamberError};
+use anchor_lang::prelude::*;
+
+impl<'c, 'info> CancelWithdrawal<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let shares = self.user_position.withdrawal_shares;
+
+        if shares == 0 {
+            return Err(ChamberError::InvalidAmount.into());
+        }
+
+        // Processed requests already burned shares
+        if self.epoch.processed {
+            return Err(ChamberError::WithdrawalProcessed.into());
+        }
+
+        self.epoch.cancel_withdrawal(shares)?;
+        self.user_position.cancel_withdrawal()?;
+        self.user_position
+            .transition(state::PositionStatus::Deployed)?;
+
+        emit!(event::WithdrawalCancelled {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            epoch: self.epoch.index,
+            shares,
+        });
+
+        Ok(())
+    }
+}
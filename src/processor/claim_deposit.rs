@@ -0,0 +1,55 @@
+use crate::{event, math, state, ChamberError, ClaimDeposit};
+use anchor_lang::prelude::*;
+use tulipv2_sdk_common::math::{
+    common::{TryAdd, TryDiv, TryMul},
+    decimal::Decimal,
+};
+
+impl<'c, 'info> ClaimDeposit<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        if !self.user_position.has_queued_deposit() {
+            return Err(ChamberError::InvalidAmount.into());
+        }
+
+        if !self.epoch.processed {
+            return Err(ChamberError::EpochNotProcessed.into());
+        }
+
+        let chamber = self.chamber.load()?;
+
+        // Shares are split by value at epoch prices
+        let user_value = math::calculate_value(
+            self.user_position.queued_base_amount,
+            Decimal::from_scaled_val(self.epoch.base_price),
+            chamber.base_decimals,
+        )?
+        .try_add(math::calculate_value(
+            self.user_position.queued_quote_amount,
+            Decimal::from_scaled_val(self.epoch.quote_price),
+            chamber.quote_decimals,
+        )?)?;
+
+        let shares = user_value
+            .try_mul(self.epoch.minted_shares)?
+            .try_div(Decimal::from_scaled_val(self.epoch.deposit_value))?
+            .try_floor_u64()?;
+
+        self.user_position.claim_deposit(shares)?;
+
+        // Pending withdrawal request keeps its status
+        let status = match self.user_position.status {
+            state::PositionStatus::WithdrawRequested => state::PositionStatus::WithdrawRequested,
+            _ => state::PositionStatus::Deployed,
+        };
+        self.user_position.transition(status)?;
+
+        emit!(event::DepositClaimed {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            epoch: self.epoch.index,
+            shares,
+        });
+
+        Ok(())
+    }
+}
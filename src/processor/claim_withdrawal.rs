@@ -0,0 +1,88 @@
+use crate::{event, math, state, utils, ChamberError, ClaimWithdrawal};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+impl<'c, 'info> ClaimWithdrawal<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let shares = self.user_position.withdrawal_shares;
+
+        if shares == 0 {
+            return Err(ChamberError::InvalidAmount.into());
+        }
+
+        let mut chamber = self.chamber.load_mut()?;
+
+        let claimable_ts = self
+            .user_position
+            .withdrawal_requested_ts
+            .checked_add(chamber.config.withdrawal_cooldown as i64)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        if self.clock_sysvar.unix_timestamp < claimable_ts {
+            return Err(ChamberError::WithdrawalCooldown.into());
+        }
+
+        // Keeper reserves liquidity for requests on epoch processing
+        if !self.epoch.processed {
+            return Err(ChamberError::EpochNotProcessed.into());
+        }
+
+        let quote_amount = math::calculate_pro_rata(
+            self.epoch.withdraw_quote_amount,
+            shares,
+            self.epoch.pending_withdraw_shares,
+        )?;
+
+        // Exit fee stays in chamber token account as accrued fee, until collected to treasury
+        let quote_fee = math::calculate_fee(quote_amount, chamber.config.exit_fee_bps)?;
+
+        if quote_amount > 0 {
+            let chamber_pubkey = self.chamber.key();
+
+            let seeds: &[&[&[u8]]] = &[&[
+                utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+                chamber_pubkey.as_ref(),
+                &[chamber.authority_bump],
+            ]];
+
+            let cpi_accounts = token::Transfer {
+                from: self.chamber_quote_ata.to_account_info(),
+                to: self.user_quote_ata.to_account_info(),
+                authority: self.authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                seeds,
+            );
+            token::transfer(cpi_ctx, quote_amount - quote_fee)?;
+
+            chamber.claimable_quote_amount = chamber
+                .claimable_quote_amount
+                .checked_sub(quote_amount)
+                .ok_or(ChamberError::MathOverflow)?;
+            chamber.charge_quote_fee(quote_fee)?;
+        }
+
+        self.user_position.clear_withdrawal();
+        self.user_position.last_withdraw_ts = self.clock_sysvar.unix_timestamp;
+
+        let status = if self.user_position.is_empty() {
+            state::PositionStatus::Closed
+        } else {
+            state::PositionStatus::Deployed
+        };
+        self.user_position.transition(status)?;
+
+        emit!(event::WithdrawalClaimed {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            epoch: self.epoch.index,
+            shares,
+            quote_amount,
+            quote_fee,
+        });
+
+        Ok(())
+    }
+}
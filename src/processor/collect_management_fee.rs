@@ -0,0 +1,58 @@
+use crate::{event, utils, CollectManagementFee};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+impl<'c, 'info> CollectManagementFee<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let mut chamber = self.chamber.load_mut()?;
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+        // Fee is already excluded from users balances, collection only moves tokens
+        let (base_fee, quote_fee) = chamber.collect_management_fee()?;
+
+        let chamber_pubkey = self.chamber.key();
+
+        let seeds: &[&[&[u8]]] = &[&[
+            utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+            chamber_pubkey.as_ref(),
+            &[chamber.authority_bump],
+        ]];
+
+        if base_fee > 0 {
+            let cpi_accounts = token::Transfer {
+                from: self.chamber_base_ata.to_account_info(),
+                to: self.treasury_base_ata.to_account_info(),
+                authority: self.authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                seeds,
+            );
+            token::transfer(cpi_ctx, base_fee)?;
+        }
+
+        if quote_fee > 0 {
+            let cpi_accounts = token::Transfer {
+                from: self.chamber_quote_ata.to_account_info(),
+                to: self.treasury_quote_ata.to_account_info(),
+                authority: self.authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                seeds,
+            );
+            token::transfer(cpi_ctx, quote_fee)?;
+        }
+
+        emit!(event::ManagementFeeCollected {
+            chamber: chamber_pubkey,
+            user: self.admin.key(),
+            base_fee,
+            quote_fee,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,685 @@
+use crate::{
+    event,
+    handler::{
+        add_liquidity_orca_tulip_levfarm, add_liquidity_raydium_tulip_levfarm,
+        deposit_vault_orca_tulip_levfarm, deposit_vault_raydium_tulip_levfarm,
+        swap_tokens_orca_tulip_levfarm, swap_tokens_raydium_tulip_levfarm,
+        AddLiquidityOrcaTulipLevfarmAccounts, AddLiquidityRaydiumTulipLevfarmAccounts,
+        DepositVaultOrcaTulipLevfarmAccounts, DepositVaultRaydiumTulipLevfarmAccounts,
+        SwapTokensOrcaTulipLevfarmAccounts, SwapTokensRaydiumTulipLevfarmAccounts,
+    },
+    state, ChamberError, CrankChamber,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+/// Amounts moved by crank step, reported by step events.
+#[derive(Default)]
+struct CrankAmounts {
+    base_amount: u64,
+    quote_amount: u64,
+    minted_lp: [u64; 2],
+    deposited_lp: u64,
+}
+
+impl<'c, 'info> CrankChamber<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &'c [AccountInfo<'info>],
+        nonce_0: u8,
+        nonce_1: u8,
+        meta_nonce_0: u8,
+        meta_nonce_1: u8,
+    ) -> Result<()> {
+        let (operation, amm_type) = {
+            let mut chamber = self.chamber.load_mut()?;
+
+            // TODO: Extend protocols support
+            if chamber.protocol_type()? != state::ProtocolType::Tulip {
+                return Err(ChamberError::UnsupportedProtocol.into());
+            }
+
+            chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+            (chamber.operation()?, chamber.amm_type()?)
+        };
+
+        // Each step either completes with state change or reverts entirely,
+        // so interrupted flow is resumed by calling crank again.
+        let amounts = match (operation, amm_type) {
+            (state::ChamberOperation::Borrowed, state::AmmType::Raydium) => {
+                self.swap_raydium(remaining_accounts)?;
+                CrankAmounts::default()
+            }
+            (state::ChamberOperation::Borrowed, state::AmmType::Orca) => {
+                self.swap_orca(remaining_accounts)?;
+                CrankAmounts::default()
+            }
+            (state::ChamberOperation::Swapped, state::AmmType::Raydium) => {
+                self.add_liquidity_raydium(remaining_accounts)?
+            }
+            (state::ChamberOperation::Swapped, state::AmmType::Orca) => {
+                self.add_liquidity_orca(remaining_accounts)?
+            }
+            // Settled chamber restakes LP left outside of vault, e.g. by unwinding
+            (
+                state::ChamberOperation::LiquidityAdded | state::ChamberOperation::Staked,
+                state::AmmType::Raydium,
+            ) => self.stake_raydium(
+                remaining_accounts,
+                nonce_0,
+                nonce_1,
+                meta_nonce_0,
+                meta_nonce_1,
+            )?,
+            (
+                state::ChamberOperation::LiquidityAdded | state::ChamberOperation::Staked,
+                state::AmmType::Orca,
+            ) => self.stake_orca(
+                remaining_accounts,
+                nonce_0,
+                nonce_1,
+                meta_nonce_0,
+                meta_nonce_1,
+            )?,
+            (state::ChamberOperation::Idle, _) => return Err(ChamberError::InvalidOperation.into()),
+        };
+
+        // Step helpers borrow chamber for signer seeds, so it is loaded again
+        let mut chamber = self.chamber.load_mut()?;
+        chamber.add_lp(0, amounts.minted_lp[0])?;
+        chamber.add_lp(1, amounts.minted_lp[1])?;
+
+        if operation == state::ChamberOperation::Staked {
+            // Settled chamber has no step to crank without LP to restake
+            if amounts.deposited_lp == 0 {
+                return Err(ChamberError::InvalidOperation.into());
+            }
+        } else {
+            chamber.advance_operation()?;
+        }
+
+        emit!(event::ChamberCranked {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            operation: chamber.operation()?,
+        });
+
+        match chamber.operation()? {
+            state::ChamberOperation::LiquidityAdded => emit!(event::PositionSettled {
+                chamber: self.chamber.key(),
+                user: self.payer.key(),
+                base_amount: amounts.base_amount,
+                quote_amount: amounts.quote_amount,
+                lp_amount: amounts.minted_lp[0]
+                    .checked_add(amounts.minted_lp[1])
+                    .ok_or(ChamberError::MathOverflow)?,
+            }),
+            state::ChamberOperation::Staked => emit!(event::VaultDeposited {
+                chamber: self.chamber.key(),
+                user: self.payer.key(),
+                lp_amount: amounts.deposited_lp,
+            }),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Swap borrowed tokens via Raydium.
+    fn swap_raydium(&self, remaining_accounts: &'c [AccountInfo<'info>]) -> Result<()> {
+        let chamber = self.chamber.load()?;
+
+        swap_tokens_raydium_tulip_levfarm(
+            Box::new(SwapTokensRaydiumTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                leveraged_farm: &remaining_accounts[0],
+                chamber_farm: &remaining_accounts[1],
+                chamber_farm_obligation: &remaining_accounts[2],
+                token_program: &self.token_program,
+                vault_signer: &remaining_accounts[17],
+                swap_or_liquidity_program_id: &remaining_accounts[30],
+                amm_id: &remaining_accounts[4],
+                amm_authority: &remaining_accounts[5],
+                amm_open_orders: &remaining_accounts[6],
+                amm_quantities_or_target_orders: &remaining_accounts[7],
+                pool_coin_token_account: &remaining_accounts[8],
+                pool_pc_token_account: &remaining_accounts[9],
+                serum_program_id: &remaining_accounts[10],
+                serum_market: &remaining_accounts[11],
+                serum_bids: &remaining_accounts[12],
+                serum_asks: &remaining_accounts[13],
+                serum_event_queue: &remaining_accounts[14],
+                serum_coin_vault_account: &remaining_accounts[15],
+                serum_pc_vault_account: &remaining_accounts[16],
+                coin_wallet: &remaining_accounts[18],
+                pc_wallet: &remaining_accounts[19],
+                lending_market: &remaining_accounts[20],
+                lending_market_authority: &remaining_accounts[21],
+                lending_program: &remaining_accounts[22],
+                position_info: &remaining_accounts[23],
+                levfarm_program: &remaining_accounts[29],
+            }),
+            0,
+        )?;
+
+        swap_tokens_raydium_tulip_levfarm(
+            Box::new(SwapTokensRaydiumTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                leveraged_farm: &remaining_accounts[0],
+                chamber_farm: &remaining_accounts[1],
+                chamber_farm_obligation: &remaining_accounts[3],
+                token_program: &self.token_program,
+                vault_signer: &remaining_accounts[17],
+                swap_or_liquidity_program_id: &remaining_accounts[30],
+                amm_id: &remaining_accounts[4],
+                amm_authority: &remaining_accounts[5],
+                amm_open_orders: &remaining_accounts[6],
+                amm_quantities_or_target_orders: &remaining_accounts[7],
+                pool_coin_token_account: &remaining_accounts[8],
+                pool_pc_token_account: &remaining_accounts[9],
+                serum_program_id: &remaining_accounts[10],
+                serum_market: &remaining_accounts[11],
+                serum_bids: &remaining_accounts[12],
+                serum_asks: &remaining_accounts[13],
+                serum_event_queue: &remaining_accounts[14],
+                serum_coin_vault_account: &remaining_accounts[15],
+                serum_pc_vault_account: &remaining_accounts[16],
+                coin_wallet: &remaining_accounts[18],
+                pc_wallet: &remaining_accounts[19],
+                lending_market: &remaining_accounts[20],
+                lending_market_authority: &remaining_accounts[21],
+                lending_program: &remaining_accounts[22],
+                position_info: &remaining_accounts[24],
+                levfarm_program: &remaining_accounts[29],
+            }),
+            1,
+        )?;
+
+        Ok(())
+    }
+
+    /// Add tokens to Raydium liquidity pool, returns provided tokens and LP minted into
+    /// each obligation.
+    fn add_liquidity_raydium(
+        &self,
+        remaining_accounts: &'c [AccountInfo<'info>],
+    ) -> Result<CrankAmounts> {
+        let chamber = self.chamber.load()?;
+        let tokens_before = token_amounts([&remaining_accounts[18], &remaining_accounts[19]])?;
+        let lp_before = token_amounts([&remaining_accounts[26], &remaining_accounts[27]])?;
+
+        add_liquidity_raydium_tulip_levfarm(
+            Box::new(AddLiquidityRaydiumTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                chamber_farm: &remaining_accounts[1],
+                leveraged_farm: &remaining_accounts[0],
+                liquidity_program_id: &remaining_accounts[30],
+                amm_id: &remaining_accounts[4],
+                amm_authority: &remaining_accounts[5],
+                amm_open_orders: &remaining_accounts[6],
+                amm_quantities_or_target_orders: &remaining_accounts[7],
+                lp_mint_address: &remaining_accounts[25],
+                pool_coin_token_account: &remaining_accounts[8],
+                pool_pc_token_account: &remaining_accounts[9],
+                serum_market: &remaining_accounts[11],
+                token_program: &self.token_program,
+                lev_farm_coin_token_account: &remaining_accounts[18],
+                lev_farm_pc_token_account: &remaining_accounts[19],
+                lp_token_account: &remaining_accounts[26],
+                pyth_price_account: &remaining_accounts[28],
+                lending_market: &remaining_accounts[20],
+                chamber_farm_obligation: &remaining_accounts[2],
+                lending_market_authority: &remaining_accounts[21],
+                lending_program: &remaining_accounts[22],
+                clock_sysvar: &self.clock_sysvar,
+                dex_program: &remaining_accounts[10],
+                position_info: &remaining_accounts[23],
+                levfarm_program: &remaining_accounts[29],
+            }),
+            0,
+        )?;
+
+        add_liquidity_raydium_tulip_levfarm(
+            Box::new(AddLiquidityRaydiumTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                chamber_farm: &remaining_accounts[1],
+                leveraged_farm: &remaining_accounts[0],
+                liquidity_program_id: &remaining_accounts[30],
+                amm_id: &remaining_accounts[4],
+                amm_authority: &remaining_accounts[5],
+                amm_open_orders: &remaining_accounts[6],
+                amm_quantities_or_target_orders: &remaining_accounts[7],
+                lp_mint_address: &remaining_accounts[25],
+                pool_coin_token_account: &remaining_accounts[8],
+                pool_pc_token_account: &remaining_accounts[9],
+                serum_market: &remaining_accounts[11],
+                token_program: &self.token_program,
+                lev_farm_coin_token_account: &remaining_accounts[18],
+                lev_farm_pc_token_account: &remaining_accounts[19],
+                lp_token_account: &remaining_accounts[27],
+                pyth_price_account: &remaining_accounts[28],
+                lending_market: &remaining_accounts[20],
+                chamber_farm_obligation: &remaining_accounts[3],
+                lending_market_authority: &remaining_accounts[21],
+                lending_program: &remaining_accounts[22],
+                clock_sysvar: &self.clock_sysvar,
+                dex_program: &remaining_accounts[10],
+                position_info: &remaining_accounts[24],
+                levfarm_program: &remaining_accounts[29],
+            }),
+            1,
+        )?;
+
+        let [base_amount, quote_amount] = spent(
+            tokens_before,
+            [&remaining_accounts[18], &remaining_accounts[19]],
+        )?;
+
+        Ok(CrankAmounts {
+            base_amount,
+            quote_amount,
+            minted_lp: minted(
+                lp_before,
+                [&remaining_accounts[26], &remaining_accounts[27]],
+            )?,
+            ..CrankAmounts::default()
+        })
+    }
+
+    /// Deposit LP tokens into Tulip Raydium vault, returns deposited LP.
+    fn stake_raydium(
+        &self,
+        remaining_accounts: &'c [AccountInfo<'info>],
+        nonce_0: u8,
+        nonce_1: u8,
+        meta_nonce_0: u8,
+        meta_nonce_1: u8,
+    ) -> Result<CrankAmounts> {
+        let chamber = self.chamber.load()?;
+        let lp_before = token_amounts([&remaining_accounts[5], &remaining_accounts[6]])?;
+
+        deposit_vault_raydium_tulip_levfarm(
+            Box::new(DepositVaultRaydiumTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                chamber_farm: &remaining_accounts[0],
+                chamber_farm_obligation_vault: &remaining_accounts[1],
+                leveraged_farm: &remaining_accounts[3],
+                vault_program: &remaining_accounts[4],
+                authority_token_account: &remaining_accounts[5],
+                vault_pda_account: &remaining_accounts[7],
+                vault: &remaining_accounts[8],
+                lp_token_account: &remaining_accounts[9],
+                chamber_balance_account: &remaining_accounts[10],
+                system_program: &self.system_program,
+                stake_program: &remaining_accounts[12],
+                pool_id: &remaining_accounts[13],
+                pool_authority: &remaining_accounts[14],
+                vault_info_account: &remaining_accounts[15],
+                pool_lp_token_account: &remaining_accounts[16],
+                reward_a_token_account: &remaining_accounts[17],
+                pool_reward_a_token_account: &remaining_accounts[18],
+                reward_b_token_account: &remaining_accounts[19],
+                pool_reward_b_token_account: &remaining_accounts[20],
+                clock_sysvar: &self.clock_sysvar,
+                rent_sysvar: &self.rent_sysvar,
+                token_program_id: &self.token_program,
+                chamber_balance_metadata: &remaining_accounts[21],
+                lending_market: &remaining_accounts[23],
+                chamber_farm_obligation: &remaining_accounts[24],
+                lending_market_authority: &remaining_accounts[26],
+                lending_program: &remaining_accounts[27],
+                levfarm_program: &remaining_accounts[28],
+            }),
+            nonce_0,
+            meta_nonce_0,
+            0,
+        )?;
+
+        deposit_vault_raydium_tulip_levfarm(
+            Box::new(DepositVaultRaydiumTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                chamber_farm: &remaining_accounts[0],
+                chamber_farm_obligation_vault: &remaining_accounts[2],
+                leveraged_farm: &remaining_accounts[3],
+                vault_program: &remaining_accounts[4],
+                authority_token_account: &remaining_accounts[6],
+                vault_pda_account: &remaining_accounts[7],
+                vault: &remaining_accounts[8],
+                lp_token_account: &remaining_accounts[9],
+                chamber_balance_account: &remaining_accounts[11],
+                system_program: &self.system_program,
+                stake_program: &remaining_accounts[12],
+                pool_id: &remaining_accounts[13],
+                pool_authority: &remaining_accounts[14],
+                vault_info_account: &remaining_accounts[15],
+                pool_lp_token_account: &remaining_accounts[16],
+                reward_a_token_account: &remaining_accounts[17],
+                pool_reward_a_token_account: &remaining_accounts[18],
+                reward_b_token_account: &remaining_accounts[19],
+                pool_reward_b_token_account: &remaining_accounts[20],
+                clock_sysvar: &self.clock_sysvar,
+                rent_sysvar: &self.rent_sysvar,
+                token_program_id: &self.token_program,
+                chamber_balance_metadata: &remaining_accounts[22],
+                lending_market: &remaining_accounts[23],
+                chamber_farm_obligation: &remaining_accounts[25],
+                lending_market_authority: &remaining_accounts[26],
+                lending_program: &remaining_accounts[27],
+                levfarm_program: &remaining_accounts[28],
+            }),
+            nonce_1,
+            meta_nonce_1,
+            1,
+        )?;
+
+        let [deposited_0, deposited_1] =
+            spent(lp_before, [&remaining_accounts[5], &remaining_accounts[6]])?;
+
+        Ok(CrankAmounts {
+            deposited_lp: deposited_0
+                .checked_add(deposited_1)
+                .ok_or(ChamberError::MathOverflow)?,
+            ..CrankAmounts::default()
+        })
+    }
+
+    /// Swap borrowed tokens via Orca.
+    fn swap_orca(&self, remaining_accounts: &'c [AccountInfo<'info>]) -> Result<()> {
+        let chamber = self.chamber.load()?;
+
+        swap_tokens_orca_tulip_levfarm(
+            Box::new(SwapTokensOrcaTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                leveraged_farm: &remaining_accounts[0],
+                chamber_farm: &remaining_accounts[1],
+                chamber_farm_obligation: &remaining_accounts[2],
+                token_program: &self.token_program,
+                vault_signer: &remaining_accounts[4],
+                swap_program_id: &remaining_accounts[5],
+                swap_account: &remaining_accounts[6],
+                swap_authority: &remaining_accounts[7],
+                pool_coin_token_account: &remaining_accounts[8],
+                pool_pc_token_account: &remaining_accounts[9],
+                pool_token_mint: &remaining_accounts[10],
+                pool_fee_account: &remaining_accounts[11],
+                coin_wallet: &remaining_accounts[12],
+                pc_wallet: &remaining_accounts[13],
+                lending_market: &remaining_accounts[14],
+                lending_market_authority: &remaining_accounts[15],
+                lending_program: &remaining_accounts[16],
+                position_info: &remaining_accounts[17],
+                levfarm_program: &remaining_accounts[22],
+            }),
+            0,
+        )?;
+
+        swap_tokens_orca_tulip_levfarm(
+            Box::new(SwapTokensOrcaTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                leveraged_farm: &remaining_accounts[0],
+                chamber_farm: &remaining_accounts[1],
+                chamber_farm_obligation: &remaining_accounts[3],
+                token_program: &self.token_program,
+                vault_signer: &remaining_accounts[4],
+                swap_program_id: &remaining_accounts[5],
+                swap_account: &remaining_accounts[6],
+                swap_authority: &remaining_accounts[7],
+                pool_coin_token_account: &remaining_accounts[8],
+                pool_pc_token_account: &remaining_accounts[9],
+                pool_token_mint: &remaining_accounts[10],
+                pool_fee_account: &remaining_accounts[11],
+                coin_wallet: &remaining_accounts[12],
+                pc_wallet: &remaining_accounts[13],
+                lending_market: &remaining_accounts[14],
+                lending_market_authority: &remaining_accounts[15],
+                lending_program: &remaining_accounts[16],
+                position_info: &remaining_accounts[18],
+                levfarm_program: &remaining_accounts[22],
+            }),
+            1,
+        )?;
+
+        Ok(())
+    }
+
+    /// Add tokens to Orca liquidity pool, returns provided tokens and LP minted into
+    /// each obligation.
+    fn add_liquidity_orca(
+        &self,
+        remaining_accounts: &'c [AccountInfo<'info>],
+    ) -> Result<CrankAmounts> {
+        let chamber = self.chamber.load()?;
+        let tokens_before = token_amounts([&remaining_accounts[12], &remaining_accounts[13]])?;
+        let lp_before = token_amounts([&remaining_accounts[19], &remaining_accounts[20]])?;
+
+        add_liquidity_orca_tulip_levfarm(
+            Box::new(AddLiquidityOrcaTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                chamber_farm: &remaining_accounts[1],
+                leveraged_farm: &remaining_accounts[0],
+                swap_program_id: &remaining_accounts[5],
+                swap_account: &remaining_accounts[6],
+                swap_authority: &remaining_accounts[7],
+                pool_token_mint: &remaining_accounts[10],
+                pool_coin_token_account: &remaining_accounts[8],
+                pool_pc_token_account: &remaining_accounts[9],
+                token_program: &self.token_program,
+                lev_farm_coin_token_account: &remaining_accounts[12],
+                lev_farm_pc_token_account: &remaining_accounts[13],
+                lp_token_account: &remaining_accounts[19],
+                pyth_price_account: &remaining_accounts[21],
+                lending_market: &remaining_accounts[14],
+                chamber_farm_obligation: &remaining_accounts[2],
+                lending_market_authority: &remaining_accounts[15],
+                lending_program: &remaining_accounts[16],
+                clock_sysvar: &self.clock_sysvar,
+                position_info: &remaining_accounts[17],
+                levfarm_program: &remaining_accounts[22],
+            }),
+            0,
+        )?;
+
+        add_liquidity_orca_tulip_levfarm(
+            Box::new(AddLiquidityOrcaTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                chamber_farm: &remaining_accounts[1],
+                leveraged_farm: &remaining_accounts[0],
+                swap_program_id: &remaining_accounts[5],
+                swap_account: &remaining_accounts[6],
+                swap_authority: &remaining_accounts[7],
+                pool_token_mint: &remaining_accounts[10],
+                pool_coin_token_account: &remaining_accounts[8],
+                pool_pc_token_account: &remaining_accounts[9],
+                token_program: &self.token_program,
+                lev_farm_coin_token_account: &remaining_accounts[12],
+                lev_farm_pc_token_account: &remaining_accounts[13],
+                lp_token_account: &remaining_accounts[20],
+                pyth_price_account: &remaining_accounts[21],
+                lending_market: &remaining_accounts[14],
+                chamber_farm_obligation: &remaining_accounts[3],
+                lending_market_authority: &remaining_accounts[15],
+                lending_program: &remaining_accounts[16],
+                clock_sysvar: &self.clock_sysvar,
+                position_info: &remaining_accounts[18],
+                levfarm_program: &remaining_accounts[22],
+            }),
+            1,
+        )?;
+
+        let [base_amount, quote_amount] = spent(
+            tokens_before,
+            [&remaining_accounts[12], &remaining_accounts[13]],
+        )?;
+
+        Ok(CrankAmounts {
+            base_amount,
+            quote_amount,
+            minted_lp: minted(
+                lp_before,
+                [&remaining_accounts[19], &remaining_accounts[20]],
+            )?,
+            ..CrankAmounts::default()
+        })
+    }
+
+    /// Deposit LP tokens into Tulip Orca vault, returns deposited LP.
+    fn stake_orca(
+        &self,
+        remaining_accounts: &'c [AccountInfo<'info>],
+        nonce_0: u8,
+        nonce_1: u8,
+        meta_nonce_0: u8,
+        meta_nonce_1: u8,
+    ) -> Result<CrankAmounts> {
+        let chamber = self.chamber.load()?;
+        let lp_before = token_amounts([&remaining_accounts[9], &remaining_accounts[10]])?;
+
+        deposit_vault_orca_tulip_levfarm(
+            Box::new(DepositVaultOrcaTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                chamber_farm: &remaining_accounts[0],
+                chamber_farm_obligation_vault: &remaining_accounts[1],
+                leveraged_farm: &remaining_accounts[3],
+                vault_program: &remaining_accounts[4],
+                vault: &remaining_accounts[5],
+                vault_pda_account: &remaining_accounts[6],
+                chamber_balance_account: &remaining_accounts[7],
+                system_program: &self.system_program,
+                lp_token_account: &remaining_accounts[9],
+                farm_token_account: &remaining_accounts[11],
+                reward_token_account: &remaining_accounts[13],
+                global_base_token_vault: &remaining_accounts[15],
+                farm_token_mint: &remaining_accounts[16],
+                global_farm: &remaining_accounts[17],
+                orca_user_farm: &remaining_accounts[18],
+                global_reward_token_vault: &remaining_accounts[20],
+                convert_authority: &remaining_accounts[21],
+                aqua_farm_program: &remaining_accounts[22],
+                clock_sysvar: &self.clock_sysvar,
+                rent_sysvar: &self.rent_sysvar,
+                token_program_id: &self.token_program,
+                chamber_balance_metadata: &remaining_accounts[23],
+                lending_market: &remaining_accounts[25],
+                chamber_farm_obligation: &remaining_accounts[26],
+                lending_market_authority: &remaining_accounts[28],
+                lending_program: &remaining_accounts[29],
+                levfarm_program: &remaining_accounts[30],
+            }),
+            nonce_0,
+            meta_nonce_0,
+            0,
+        )?;
+
+        deposit_vault_orca_tulip_levfarm(
+            Box::new(DepositVaultOrcaTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                chamber_farm: &remaining_accounts[0],
+                chamber_farm_obligation_vault: &remaining_accounts[2],
+                leveraged_farm: &remaining_accounts[3],
+                vault_program: &remaining_accounts[4],
+                vault: &remaining_accounts[5],
+                vault_pda_account: &remaining_accounts[6],
+                chamber_balance_account: &remaining_accounts[8],
+                system_program: &self.system_program,
+                lp_token_account: &remaining_accounts[10],
+                farm_token_account: &remaining_accounts[12],
+                reward_token_account: &remaining_accounts[14],
+                global_base_token_vault: &remaining_accounts[15],
+                farm_token_mint: &remaining_accounts[16],
+                global_farm: &remaining_accounts[17],
+                orca_user_farm: &remaining_accounts[19],
+                global_reward_token_vault: &remaining_accounts[20],
+                convert_authority: &remaining_accounts[21],
+                aqua_farm_program: &remaining_accounts[22],
+                clock_sysvar: &self.clock_sysvar,
+                rent_sysvar: &self.rent_sysvar,
+                token_program_id: &self.token_program,
+                chamber_balance_metadata: &remaining_accounts[24],
+                lending_market: &remaining_accounts[25],
+                chamber_farm_obligation: &remaining_accounts[27],
+                lending_market_authority: &remaining_accounts[28],
+                lending_program: &remaining_accounts[29],
+                levfarm_program: &remaining_accounts[30],
+            }),
+            nonce_1,
+            meta_nonce_1,
+            1,
+        )?;
+
+        let [deposited_0, deposited_1] =
+            spent(lp_before, [&remaining_accounts[9], &remaining_accounts[10]])?;
+
+        Ok(CrankAmounts {
+            deposited_lp: deposited_0
+                .checked_add(deposited_1)
+                .ok_or(ChamberError::MathOverflow)?,
+            ..CrankAmounts::default()
+        })
+    }
+}
+
+/// Token account is created by levfarm lazily, so missing one holds nothing.
+fn token_amount(account: &AccountInfo) -> Result<u64> {
+    if account.data_is_empty() {
+        return Ok(0);
+    }
+
+    token::accessor::amount(account)
+}
+
+fn token_amounts(accounts: [&AccountInfo; 2]) -> Result<[u64; 2]> {
+    Ok([token_amount(accounts[0])?, token_amount(accounts[1])?])
+}
+
+/// Tokens received by `accounts` since `before` was read.
+fn minted(before: [u64; 2], accounts: [&AccountInfo; 2]) -> Result<[u64; 2]> {
+    let after = token_amounts(accounts)?;
+
+    Ok([
+        after[0]
+            .checked_sub(before[0])
+            .ok_or(ChamberError::MathOverflow)?,
+        after[1]
+            .checked_sub(before[1])
+            .ok_or(ChamberError::MathOverflow)?,
+    ])
+}
+
+/// Tokens taken from `accounts` since `before` was read.
+fn spent(before: [u64; 2], accounts: [&AccountInfo; 2]) -> Result<[u64; 2]> {
+    let after = token_amounts(accounts)?;
+
+    Ok([
+        before[0]
+            .checked_sub(after[0])
+            .ok_or(ChamberError::MathOverflow)?,
+        before[1]
+            .checked_sub(after[1])
+            .ok_or(ChamberError::MathOverflow)?,
+    ])
+}
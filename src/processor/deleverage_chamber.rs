@@ -0,0 +1,135 @@
+use crate::{
+    event,
+    handler::{withdraw_vault_raydium_tulip_levfarm, WithdrawVaultRaydiumTulipLevfarmAccounts},
+    state, ChamberError, DeleverageChamber,
+};
+use anchor_lang::prelude::*;
+
+impl<'c, 'info> DeleverageChamber<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &'c [AccountInfo<'info>],
+        nonce_0: u8,
+        nonce_1: u8,
+        meta_nonce_0: u8,
+        meta_nonce_1: u8,
+        withdraw_percent: u8,
+    ) -> Result<()> {
+        let mut chamber = self.chamber.load_mut()?;
+
+        // TODO: Extend protocols support
+        if chamber.protocol_type()? != state::ProtocolType::Tulip {
+            return Err(ChamberError::UnsupportedProtocol.into());
+        }
+
+        // TODO: Support Orca vault withdrawals
+        if chamber.amm_type()? != state::AmmType::Raydium {
+            return Err(ChamberError::UnsupportedAmm.into());
+        }
+
+        if withdraw_percent == 0 || withdraw_percent > 100 {
+            return Err(ChamberError::InvalidAmount.into());
+        }
+
+        // Unwinding is not allowed in the middle of deploy flow
+        if !chamber.operation()?.is_settled() {
+            return Err(ChamberError::InvalidOperation.into());
+        }
+
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+        // 1. Find the riskier leg
+        let chamber_farm = remaining_accounts[0].key();
+        let ltv_0 = state::TulipObligation::load_checked(
+            &remaining_accounts[24],
+            &chamber.authority,
+            &chamber_farm,
+            0,
+        )?
+        .ltv_bps()?;
+        let ltv_1 = state::TulipObligation::load_checked(
+            &remaining_accounts[25],
+            &chamber.authority,
+            &chamber_farm,
+            1,
+        )?
+        .ltv_bps()?;
+
+        let (obligation_index, ltv) = if ltv_0 >= ltv_1 {
+            (0usize, ltv_0)
+        } else {
+            (1usize, ltv_1)
+        };
+
+        if chamber.config.deleverage_ltv_bps == 0 || ltv <= chamber.config.deleverage_ltv_bps as u64
+        {
+            return Err(ChamberError::DeleverageNotRequired.into());
+        }
+
+        msg!(
+            "Deleverage obligation {} with LTV {}",
+            obligation_index,
+            ltv
+        );
+
+        let (nonce, meta_nonce) = if obligation_index == 0 {
+            (nonce_0, meta_nonce_0)
+        } else {
+            (nonce_1, meta_nonce_1)
+        };
+
+        // 2. Withdraw part of leg liquidity from vault and repay debt
+        withdraw_vault_raydium_tulip_levfarm(
+            Box::new(WithdrawVaultRaydiumTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                chamber_farm: &remaining_accounts[0],
+                chamber_farm_obligation_vault: &remaining_accounts[1 + obligation_index],
+                leveraged_farm: &remaining_accounts[3],
+                vault_program: &remaining_accounts[4],
+                authority_token_account: &remaining_accounts[5 + obligation_index],
+                vault_pda_account: &remaining_accounts[7],
+                vault: &remaining_accounts[8],
+                lp_token_account: &remaining_accounts[9],
+                chamber_balance_account: &remaining_accounts[10 + obligation_index],
+                system_program: &self.system_program,
+                stake_program: &remaining_accounts[12],
+                pool_id: &remaining_accounts[13],
+                pool_authority: &remaining_accounts[14],
+                vault_info_account: &remaining_accounts[15],
+                pool_lp_token_account: &remaining_accounts[16],
+                reward_a_token_account: &remaining_accounts[17],
+                pool_reward_a_token_account: &remaining_accounts[18],
+                reward_b_token_account: &remaining_accounts[19],
+                pool_reward_b_token_account: &remaining_accounts[20],
+                clock_sysvar: &self.clock_sysvar,
+                rent_sysvar: &self.rent_sysvar,
+                token_program_id: &self.token_program,
+                chamber_balance_metadata: &remaining_accounts[21 + obligation_index],
+                lending_market: &remaining_accounts[23],
+                chamber_farm_obligation: &remaining_accounts[24 + obligation_index],
+                lending_market_authority: &remaining_accounts[26],
+                lending_program: &remaining_accounts[27],
+                position_info: &remaining_accounts[29 + obligation_index],
+                levfarm_program: &remaining_accounts[28],
+            }),
+            nonce,
+            meta_nonce,
+            obligation_index as u8,
+            withdraw_percent,
+        )?;
+
+        chamber.unwind_leg(obligation_index, withdraw_percent)?;
+
+        emit!(event::ChamberDeleveraged {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            obligation_index: obligation_index as u8,
+            withdraw_percent,
+            ltv_bps: ltv,
+        });
+
+        Ok(())
+    }
+}
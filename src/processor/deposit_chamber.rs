@@ -1,10 +1,22 @@
 use crate::{
-    handler::{deposit_borrow_tulip_levfarm, DepositBorrowTulipLevfarmAccounts},
-    ChamberError, DepositChamber,
+    event,
+    handler::{
+        add_liquidity_raydium, borrow_solend, deposit_borrow_tulip_levfarm,
+        deposit_obligation_collateral_solend, deposit_reserve_liquidity_solend,
+        refresh_obligation_solend, refresh_reserve_solend, AddLiquidityRaydiumAccounts,
+        BorrowSolendAccounts, DepositBorrowTulipLevfarmAccounts,
+        DepositObligationCollateralSolendAccounts, DepositReserveLiquiditySolendAccounts,
+        RefreshObligationSolendAccounts, RefreshReserveSolendAccounts,
+    },
+    math, state, ChamberError, DepositChamber,
 };
 use anchor_lang::prelude::*;
+use anchor_spl::token;
 use tulipv2_sdk_common::{
-    math::common::{TryAdd, TryDiv, TryMul},
+    math::{
+        common::{TryAdd, TryDiv, TryMul},
+        decimal::Decimal,
+    },
     pyth,
 };
 
@@ -19,58 +31,126 @@ impl<'c, 'info> DepositChamber<'info> {
         // TODO: Enhance state management
         // TODO: Improve calculation & optimization
 
-        if base_amount > self.user_position.base_amount
-            || quote_amount > self.user_position.quote_amount
+        let mut chamber = self.chamber.load_mut()?;
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+        if base_amount > chamber.base_units_amount(self.user_position.base_amount)?
+            || quote_amount > chamber.quote_units_amount(self.user_position.quote_amount)?
         {
             return Err(ChamberError::InsufficientUserPositionFunds.into());
         }
 
-        let actual_base_amount = if base_amount > 0 {
-            base_amount
-                .checked_div(4)
-                .ok_or(ChamberError::MathOverflow)?
-        } else {
-            0
-        };
+        let protocol_type = chamber.protocol_type()?;
+
+        // Shares are minted at NAV before deposit, while deposited funds still count as
+        // idle balance, so the same liquidity is never booked for other shareholders
+        let (base_price, quote_price, nav) = match protocol_type {
+            state::ProtocolType::Tulip if chamber.total_shares > 0 => {
+                let tulip_nav = state::TulipNav::load(
+                    &chamber,
+                    &self.epoch,
+                    remaining_accounts,
+                    self.chamber_base_ata.amount,
+                    self.chamber_quote_ata.amount,
+                )?;
+
+                (tulip_nav.base_price, tulip_nav.quote_price, tulip_nav.nav)
+            }
+            // First shares are minted 1:1 with value, so NAV isn't read
+            state::ProtocolType::Tulip => {
+                let (base_price, quote_price) =
+                    state::TulipNav::load_prices(&chamber, remaining_accounts)?;
+
+                (base_price, quote_price, Decimal::zero())
+            }
+            // TODO: Price NAV of Solend strategy
+            state::ProtocolType::Solend if chamber.total_shares > 0 => {
+                return Err(ChamberError::UnsupportedProtocol.into());
+            }
+            state::ProtocolType::Solend => {
+                if remaining_accounts[7].key() != chamber.base_oracle
+                    || remaining_accounts[12].key() != chamber.quote_oracle
+                {
+                    return Err(ChamberError::InvalidOracle.into());
+                }
 
-        let actual_quote_amount = if quote_amount > 0 {
-            quote_amount
-                .checked_div(4)
-                .ok_or(ChamberError::MathOverflow)?
-        } else {
-            0
+                (
+                    pyth::load_pyth_price(remaining_accounts[7].data.borrow().as_ref())?,
+                    pyth::load_pyth_price(remaining_accounts[12].data.borrow().as_ref())?,
+                    Decimal::zero(),
+                )
+            }
+            _ => return Err(ChamberError::UnsupportedProtocol.into()),
         };
 
-        let base_price_account = &remaining_accounts[8];
-        let quote_price_account = &remaining_accounts[9];
+        let deposit_value =
+            math::calculate_value(base_amount, base_price, chamber.base_decimals)?.try_add(
+                math::calculate_value(quote_amount, quote_price, chamber.quote_decimals)?,
+            )?;
+        let minted_shares = math::calculate_shares(deposit_value, chamber.total_shares, nav)?;
 
-        let base_price = pyth::load_pyth_price(base_price_account.data.borrow().as_ref())?;
-        // TODO: Add mint decimals lookup
-        let base_decimals = 10u64.pow(9u32);
+        // Deployed funds leave idle balance for shares, so they can't be withdrawn once again
+        let base_units = chamber.withdraw_base(base_amount)?;
+        let quote_units = chamber.withdraw_quote(quote_amount)?;
+        chamber.total_shares = chamber
+            .total_shares
+            .checked_add(minted_shares)
+            .ok_or(ChamberError::MathOverflow)?;
 
-        let quote_price = pyth::load_pyth_price(quote_price_account.data.borrow().as_ref())?;
-        // TODO: Add mint decimals lookup
-        let quote_decimals = 10u64.pow(6u32);
+        self.user_position
+            .deploy(base_units, quote_units, minted_shares)?;
+        self.user_position
+            .transition(state::PositionStatus::Deployed)?;
+        self.user_position.last_deposit_ts = self.clock_sysvar.unix_timestamp;
 
-        let total_base_value = base_price
-            .try_mul(actual_base_amount)?
-            .try_div(base_decimals)?;
-        let total_quote_value = quote_price
-            .try_mul(actual_quote_amount)?
-            .try_div(quote_decimals)?;
-        let total_deposit_value = total_base_value.try_add(total_quote_value)?;
-        let max_deposit_value = total_deposit_value.try_mul(2)?;
+        drop(chamber);
 
-        // TODO: Change calculation to actual quote token amount
-        let quote_borrow_amount = max_deposit_value
-            .try_div(quote_price)?
-            .try_mul(quote_decimals)?
-            .try_floor_u64()?;
+        match protocol_type {
+            state::ProtocolType::Tulip => self.deposit_tulip(
+                remaining_accounts,
+                base_amount,
+                quote_amount,
+                base_price,
+                quote_price,
+                minted_shares,
+            ),
+            _ => self.deposit_solend(
+                remaining_accounts,
+                base_amount,
+                quote_amount,
+                base_price,
+                quote_price,
+                minted_shares,
+            ),
+        }
+    }
+
+    fn deposit_tulip(
+        &mut self,
+        remaining_accounts: &'c [AccountInfo<'info>],
+        base_amount: u64,
+        quote_amount: u64,
+        base_price: Decimal,
+        quote_price: Decimal,
+        minted_shares: u64,
+    ) -> Result<()> {
+        let mut chamber = self.chamber.load_mut()?;
+        chamber.begin_operation()?;
+
+        let legs = math::calculate_tulip_deposit(
+            base_amount,
+            quote_amount,
+            base_price,
+            quote_price,
+            chamber.base_decimals,
+            chamber.quote_decimals,
+        )?;
 
         // Enter 1st position
         deposit_borrow_tulip_levfarm(
             Box::new(DepositBorrowTulipLevfarmAccounts {
                 chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
                 chamber_authority: &self.authority,
                 chamber_farm: &remaining_accounts[0],
                 leveraged_farm: &remaining_accounts[1],
@@ -99,34 +179,25 @@ impl<'c, 'info> DepositChamber<'info> {
                 levfarm_program: &remaining_accounts[22],
                 system_program: &self.system_program,
             }),
-            actual_base_amount,  //  SOL
-            actual_quote_amount, //  USDC
-            0,
-            quote_borrow_amount,
+            legs[0].base_collateral,  //  SOL
+            legs[0].quote_collateral, //  USDC
+            legs[0].base_debt,
+            legs[0].quote_debt,
             0,
         )?;
 
-        let actual_base_amount = base_amount - actual_base_amount;
-        let actual_quote_amount = quote_amount - actual_quote_amount;
-
-        let total_base_value = base_price
-            .try_mul(actual_base_amount)?
-            .try_div(base_decimals)?;
-        let total_quote_value = quote_price
-            .try_mul(actual_quote_amount)?
-            .try_div(quote_decimals)?;
-        let total_deposit_value = total_base_value.try_add(total_quote_value)?;
-        let max_deposit_value = total_deposit_value.try_mul(2)?;
-
-        let base_borrow_amount = max_deposit_value
-            .try_div(base_price)?
-            .try_mul(base_decimals)?
-            .try_floor_u64()?;
+        chamber.legs[0].deposit_borrow(
+            legs[0].base_collateral,
+            legs[0].quote_collateral,
+            legs[0].base_debt,
+            legs[0].quote_debt,
+        )?;
 
         // Enter 2nd position
         deposit_borrow_tulip_levfarm(
             Box::new(DepositBorrowTulipLevfarmAccounts {
                 chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
                 chamber_authority: &self.authority,
                 chamber_farm: &remaining_accounts[0],
                 leveraged_farm: &remaining_accounts[1],
@@ -155,13 +226,200 @@ impl<'c, 'info> DepositChamber<'info> {
                 levfarm_program: &remaining_accounts[22],
                 system_program: &self.system_program,
             }),
-            actual_base_amount,  //  SOL
-            actual_quote_amount, //  USDC
-            base_borrow_amount,
-            0,
+            legs[1].base_collateral,  //  SOL
+            legs[1].quote_collateral, //  USDC
+            legs[1].base_debt,
+            legs[1].quote_debt,
             1,
         )?;
 
+        chamber.legs[1].deposit_borrow(
+            legs[1].base_collateral,
+            legs[1].quote_collateral,
+            legs[1].base_debt,
+            legs[1].quote_debt,
+        )?;
+
+        emit!(event::ChamberDeposited {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            base_amount,
+            quote_amount,
+            quote_borrow_amount: legs[0].quote_debt,
+            base_borrow_amount: legs[1].base_debt,
+            base_price: base_price.to_scaled_val()?,
+            quote_price: quote_price.to_scaled_val()?,
+            minted_shares,
+        });
+
+        Ok(())
+    }
+
+    /// Supply quote as collateral and borrow base of the same value as quote provided to
+    /// Raydium pool, so base debt offsets base exposure of liquidity.
+    fn deposit_solend(
+        &mut self,
+        remaining_accounts: &'c [AccountInfo<'info>],
+        base_amount: u64,
+        quote_amount: u64,
+        base_price: Decimal,
+        quote_price: Decimal,
+        minted_shares: u64,
+    ) -> Result<()> {
+        let mut chamber = self.chamber.load_mut()?;
+
+        // Base deposit would stay unhedged
+        if base_amount > 0 || quote_amount == 0 {
+            return Err(ChamberError::InvalidAmount.into());
+        }
+
+        // 1. Split quote between collateral and liquidity
+        let liquidity_quote_amount =
+            math::calculate_liquidity_amount(quote_amount, math::SOLEND_TARGET_LTV_BPS)?;
+        let collateral_quote_amount = quote_amount - liquidity_quote_amount;
+
+        let base_borrow_amount =
+            math::calculate_value(liquidity_quote_amount, quote_price, chamber.quote_decimals)?
+                .try_div(base_price)?
+                .try_mul(10u64.pow(chamber.base_decimals as u32))?
+                .try_floor_u64()?;
+
+        // 2. Supply quote collateral
+        refresh_reserve_solend(Box::new(RefreshReserveSolendAccounts {
+            reserve: &remaining_accounts[8],
+            reserve_liquidity_oracle: &remaining_accounts[12],
+            clock_sysvar: &self.clock_sysvar,
+            lending_program: &remaining_accounts[0],
+        }))?;
+
+        let collateral_before = token::accessor::amount(&remaining_accounts[13])?;
+
+        deposit_reserve_liquidity_solend(
+            Box::new(DepositReserveLiquiditySolendAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                source_liquidity: &self.chamber_quote_ata.to_account_info(),
+                destination_collateral: &remaining_accounts[13],
+                reserve: &remaining_accounts[8],
+                reserve_liquidity_supply: &remaining_accounts[9],
+                reserve_collateral_mint: &remaining_accounts[10],
+                lending_market: &remaining_accounts[1],
+                lending_market_authority: &remaining_accounts[2],
+                clock_sysvar: &self.clock_sysvar,
+                token_program: &self.token_program,
+                lending_program: &remaining_accounts[0],
+            }),
+            collateral_quote_amount,
+        )?;
+
+        let collateral_amount = token::accessor::amount(&remaining_accounts[13])?
+            .checked_sub(collateral_before)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        // Liquidity deposit marks reserve stale
+        refresh_reserve_solend(Box::new(RefreshReserveSolendAccounts {
+            reserve: &remaining_accounts[8],
+            reserve_liquidity_oracle: &remaining_accounts[12],
+            clock_sysvar: &self.clock_sysvar,
+            lending_program: &remaining_accounts[0],
+        }))?;
+
+        deposit_obligation_collateral_solend(
+            Box::new(DepositObligationCollateralSolendAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                source_collateral: &remaining_accounts[13],
+                reserve_collateral_supply: &remaining_accounts[11],
+                reserve: &remaining_accounts[8],
+                obligation: &remaining_accounts[3],
+                lending_market: &remaining_accounts[1],
+                clock_sysvar: &self.clock_sysvar,
+                token_program: &self.token_program,
+                lending_program: &remaining_accounts[0],
+            }),
+            collateral_amount,
+        )?;
+
+        // 3. Borrow base
+        refresh_reserve_solend(Box::new(RefreshReserveSolendAccounts {
+            reserve: &remaining_accounts[4],
+            reserve_liquidity_oracle: &remaining_accounts[7],
+            clock_sysvar: &self.clock_sysvar,
+            lending_program: &remaining_accounts[0],
+        }))?;
+
+        // Obligation expects deposit reserves followed by borrow reserves
+        let mut reserves = vec![&remaining_accounts[8]];
+        if chamber.legs[0].base_debt > 0 {
+            reserves.push(&remaining_accounts[4]);
+        }
+
+        refresh_obligation_solend(Box::new(RefreshObligationSolendAccounts {
+            obligation: &remaining_accounts[3],
+            clock_sysvar: &self.clock_sysvar,
+            reserves,
+            lending_program: &remaining_accounts[0],
+        }))?;
+
+        borrow_solend(
+            Box::new(BorrowSolendAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                reserve_liquidity_supply: &remaining_accounts[5],
+                destination_liquidity: &self.chamber_base_ata.to_account_info(),
+                reserve: &remaining_accounts[4],
+                reserve_liquidity_fee_receiver: &remaining_accounts[6],
+                obligation: &remaining_accounts[3],
+                lending_market: &remaining_accounts[1],
+                lending_market_authority: &remaining_accounts[2],
+                clock_sysvar: &self.clock_sysvar,
+                token_program: &self.token_program,
+                lending_program: &remaining_accounts[0],
+            }),
+            base_borrow_amount,
+        )?;
+
+        // 4. Provide borrowed base and quote to liquidity
+        add_liquidity_raydium(
+            Box::new(AddLiquidityRaydiumAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                token_program: &self.token_program,
+                amm_program: &remaining_accounts[14],
+                amm_id: &remaining_accounts[15],
+                amm_authority: &remaining_accounts[16],
+                amm_open_orders: &remaining_accounts[17],
+                amm_quantities_or_target_orders: &remaining_accounts[18],
+                lp_mint: &remaining_accounts[19],
+                pool_coin_token_account: &remaining_accounts[20],
+                pool_pc_token_account: &remaining_accounts[21],
+                serum_market: &remaining_accounts[22],
+                coin_token_account: &self.chamber_base_ata.to_account_info(),
+                pc_token_account: &self.chamber_quote_ata.to_account_info(),
+                lp_token_account: &remaining_accounts[23],
+            }),
+            base_borrow_amount,
+            liquidity_quote_amount,
+        )?;
+
+        chamber.legs[0].deposit_borrow(0, collateral_quote_amount, base_borrow_amount, 0)?;
+
+        emit!(event::ChamberDeposited {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            base_amount,
+            quote_amount,
+            quote_borrow_amount: 0,
+            base_borrow_amount,
+            base_price: base_price.to_scaled_val()?,
+            quote_price: quote_price.to_scaled_val()?,
+            minted_shares,
+        });
+
         Ok(())
     }
 }
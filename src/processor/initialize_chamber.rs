@@ -1,4 +1,4 @@
-use crate::{state, utils, ChamberError, InitializeChamber};
+use crate::{event, state, utils, ChamberError, InitializeChamber};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token;
 
@@ -6,34 +6,66 @@ impl<'c, 'info> InitializeChamber<'info> {
     pub fn process(
         &mut self,
         leveraged_farm: Pubkey,
+        index: u8,
         bump: u8,
         authority_bump: u8,
+        epoch_bump: u8,
         protocol_type: state::ProtocolType,
+        amm_type: state::AmmType,
+        config: state::ChamberConfig,
+        keeper: Pubkey,
+        pool: state::ChamberPool,
+        base_oracle: Pubkey,
+        quote_oracle: Pubkey,
     ) -> Result<()> {
         // TODO: Extend protocols support
-        if protocol_type != state::ProtocolType::Tulip {
-            return Err(ChamberError::UnsupportedProtocol.into());
+        match protocol_type {
+            state::ProtocolType::Tulip => {}
+            // Solend strategy provides liquidity to Raydium directly
+            state::ProtocolType::Solend => {
+                if amm_type != state::AmmType::Raydium {
+                    return Err(ChamberError::UnsupportedAmm.into());
+                }
+            }
+            _ => return Err(ChamberError::UnsupportedProtocol.into()),
         }
 
+        config.validate()?;
+
         // 1. Initialize chamber
-        self.chamber.init(
+        self.chamber.load_init()?.init(
             &leveraged_farm,
+            index,
             &self.authority.key(),
+            &self.payer.key(),
             &self.base_ata.key(),
             &self.quote_ata.key(),
             &self.base_mint.key(),
             &self.quote_mint.key(),
+            self.base_mint.decimals,
+            self.quote_mint.decimals,
+            &self.treasury_base_ata.key(),
+            &self.treasury_quote_ata.key(),
             protocol_type,
+            amm_type,
+            config,
+            &keeper,
+            pool,
+            &base_oracle,
+            &quote_oracle,
+            self.clock_sysvar.unix_timestamp,
             bump,
             authority_bump,
         );
 
         let chamber_pubkey = self.chamber.key();
 
+        self.epoch.init(&chamber_pubkey, 0, epoch_bump);
+
         let seeds: &[&[&[u8]]] = &[&[
             utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
             chamber_pubkey.as_ref(),
-            &[self.chamber.authority_bump],
+            &[authority_bump],
         ]];
 
         // 2. Create base ata
@@ -74,6 +106,16 @@ impl<'c, 'info> InitializeChamber<'info> {
             associated_token::create(cpi_ctx)?;
         }
 
+        emit!(event::ChamberInitialized {
+            chamber: chamber_pubkey,
+            user: self.payer.key(),
+            leveraged_farm,
+            index,
+            base_mint: self.base_mint.key(),
+            quote_mint: self.quote_mint.key(),
+            config,
+        });
+
         Ok(())
     }
 }
@@ -1,8 +1,9 @@
 use crate::{
+    event,
     handler::{
-        create_obligation_tulip_levfarm, initialize_tulip_levfarm, transfer_lamports,
-        CreateObligationTulipLevfarmAccounts, InitializeTulipLevfarmAccounts,
-        TransferLamportsAccounts,
+        create_obligation_tulip_levfarm, init_obligation_solend, initialize_tulip_levfarm,
+        transfer_lamports, CreateObligationTulipLevfarmAccounts, InitObligationSolendAccounts,
+        InitializeTulipLevfarmAccounts, TransferLamportsAccounts,
     },
     state, ChamberError, InitializeChamberStrategy,
 };
@@ -10,8 +11,11 @@ use anchor_lang::prelude::*;
 
 impl<'c, 'info> InitializeChamberStrategy<'info> {
     pub fn process(&mut self, remaining_accounts: &'c [AccountInfo<'info>]) -> Result<()> {
+        let mut chamber = self.chamber.load_mut()?;
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
         // TODO: Extend protocols support
-        match self.chamber.protocol_type {
+        match chamber.protocol_type()? {
             state::ProtocolType::Tulip => {
                 // Fund PDA authority
                 transfer_lamports(
@@ -28,6 +32,7 @@ impl<'c, 'info> InitializeChamberStrategy<'info> {
                 // Initialize obligation LP ata
                 initialize_tulip_levfarm(Box::new(InitializeTulipLevfarmAccounts {
                     chamber: &self.chamber,
+                    authority_bump: chamber.authority_bump,
                     chamber_authority: &self.authority.to_account_info(),
                     payer: &self.payer.to_account_info(),
                     global: &remaining_accounts[0],
@@ -53,6 +58,7 @@ impl<'c, 'info> InitializeChamberStrategy<'info> {
                 // Initialize obligation LP ata
                 create_obligation_tulip_levfarm(Box::new(CreateObligationTulipLevfarmAccounts {
                     chamber: &self.chamber,
+                    authority_bump: chamber.authority_bump,
                     chamber_authority: &self.authority,
                     chamber_farm: &remaining_accounts[1],
                     leveraged_farm: &remaining_accounts[7],
@@ -72,9 +78,28 @@ impl<'c, 'info> InitializeChamberStrategy<'info> {
                     system_program: &self.system_program,
                 }))?;
             }
+            state::ProtocolType::Solend => {
+                // Obligation account is allocated by client and owned by lending program
+                init_obligation_solend(Box::new(InitObligationSolendAccounts {
+                    chamber: &self.chamber,
+                    authority_bump: chamber.authority_bump,
+                    chamber_authority: &self.authority,
+                    obligation: &remaining_accounts[0],
+                    lending_market: &remaining_accounts[1],
+                    clock_sysvar: &self.clock_sysvar,
+                    rent_sysvar: &self.rent_sysvar,
+                    token_program: &self.token_program,
+                    lending_program: &remaining_accounts[2],
+                }))?;
+            }
             _ => return Err(ChamberError::UnsupportedProtocol.into()),
         };
 
+        emit!(event::StrategyInitialized {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+        });
+
         Ok(())
     }
 }
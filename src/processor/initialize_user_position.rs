@@ -1,18 +1,50 @@
-use crate::InitializeUserPosition;
+use crate::{event, math, InitializeUserPosition};
 use anchor_lang::prelude::*;
 use anchor_spl::token;
 
 impl<'c, 'info> InitializeUserPosition<'info> {
     pub fn process(&mut self, bump: u8, base_amount: u64, quote_amount: u64) -> Result<()> {
-        self.user_position
-            .init(self.payer.key, &self.chamber.key(), bump);
+        let mut chamber = self.chamber.load_mut()?;
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+        self.user_position.init(
+            self.payer.key,
+            &self.chamber.key(),
+            self.clock_sysvar.unix_timestamp,
+            bump,
+        );
 
         // TODO: Enhance user position management
-        // TODO: Add position states
         // TODO: Change exact amounts to interest bearing tokens (percentage)
 
-        // 1. Deposit base amount
+        let base_fee = math::calculate_fee(base_amount, chamber.config.entry_fee_bps)?;
+        let quote_fee = math::calculate_fee(quote_amount, chamber.config.entry_fee_bps)?;
+
+        // 1. Take entry fees
+        if base_fee > 0 {
+            let cpi_accounts = token::Transfer {
+                from: self.user_base_ata.to_account_info(),
+                to: self.treasury_base_ata.to_account_info(),
+                authority: self.payer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, base_fee)?;
+        }
+
+        if quote_fee > 0 {
+            let cpi_accounts = token::Transfer {
+                from: self.user_quote_ata.to_account_info(),
+                to: self.treasury_quote_ata.to_account_info(),
+                authority: self.payer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, quote_fee)?;
+        }
+
+        // 2. Deposit base amount
         {
+            let base_amount = base_amount - base_fee;
+
             let cpi_accounts = token::Transfer {
                 from: self.user_base_ata.to_account_info(),
                 to: self.chamber_base_ata.to_account_info(),
@@ -21,11 +53,14 @@ impl<'c, 'info> InitializeUserPosition<'info> {
             let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
             token::transfer(cpi_ctx, base_amount)?;
 
-            self.user_position.deposit_base(base_amount)?;
+            let units = chamber.deposit_base(base_amount)?;
+            self.user_position.deposit_base(units)?;
         }
 
-        // 2. Deposit quote amount
+        // 3. Deposit quote amount
         {
+            let quote_amount = quote_amount - quote_fee;
+
             let cpi_accounts = token::Transfer {
                 from: self.user_quote_ata.to_account_info(),
                 to: self.chamber_quote_ata.to_account_info(),
@@ -34,9 +69,19 @@ impl<'c, 'info> InitializeUserPosition<'info> {
             let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
             token::transfer(cpi_ctx, quote_amount)?;
 
-            self.user_position.deposit_quote(quote_amount)?;
+            let units = chamber.deposit_quote(quote_amount)?;
+            self.user_position.deposit_quote(units)?;
         }
 
+        emit!(event::PositionOpened {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            base_amount: base_amount - base_fee,
+            quote_amount: quote_amount - quote_fee,
+            base_fee,
+            quote_fee,
+        });
+
         Ok(())
     }
 }
@@ -0,0 +1,93 @@
+use crate::{
+    event,
+    handler::{sync_native, transfer_lamports, SyncNativeAccounts, TransferLamportsAccounts},
+    math, InitializeUserPositionNative,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+impl<'c, 'info> InitializeUserPositionNative<'info> {
+    pub fn process(&mut self, bump: u8, base_amount: u64, quote_amount: u64) -> Result<()> {
+        let mut chamber = self.chamber.load_mut()?;
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+        self.user_position.init(
+            self.payer.key,
+            &self.chamber.key(),
+            self.clock_sysvar.unix_timestamp,
+            bump,
+        );
+
+        let base_fee = math::calculate_fee(base_amount, chamber.config.entry_fee_bps)?;
+        let quote_fee = math::calculate_fee(quote_amount, chamber.config.entry_fee_bps)?;
+
+        // 1. Take entry fees, base fee is wrapped into treasury
+        if base_fee > 0 {
+            self.wrap_lamports(&self.treasury_base_ata.to_account_info(), base_fee)?;
+        }
+
+        if quote_fee > 0 {
+            let cpi_accounts = token::Transfer {
+                from: self.user_quote_ata.to_account_info(),
+                to: self.treasury_quote_ata.to_account_info(),
+                authority: self.payer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, quote_fee)?;
+        }
+
+        // 2. Wrap and deposit base amount
+        {
+            let base_amount = base_amount - base_fee;
+
+            self.wrap_lamports(&self.chamber_base_ata.to_account_info(), base_amount)?;
+
+            let units = chamber.deposit_base(base_amount)?;
+            self.user_position.deposit_base(units)?;
+        }
+
+        // 3. Deposit quote amount
+        {
+            let quote_amount = quote_amount - quote_fee;
+
+            let cpi_accounts = token::Transfer {
+                from: self.user_quote_ata.to_account_info(),
+                to: self.chamber_quote_ata.to_account_info(),
+                authority: self.payer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, quote_amount)?;
+
+            let units = chamber.deposit_quote(quote_amount)?;
+            self.user_position.deposit_quote(units)?;
+        }
+
+        emit!(event::PositionOpened {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            base_amount: base_amount - base_fee,
+            quote_amount: quote_amount - quote_fee,
+            base_fee,
+            quote_fee,
+        });
+
+        Ok(())
+    }
+
+    /// Transfer `amount` lamports from payer into wrapped SOL token account.
+    fn wrap_lamports(&self, token_account: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer_lamports(
+            Box::new(TransferLamportsAccounts {
+                from: &self.payer.to_account_info(),
+                to: token_account,
+                system_program: &self.system_program,
+            }),
+            amount,
+        )?;
+
+        sync_native(Box::new(SyncNativeAccounts {
+            token_account,
+            token_program: &self.token_program,
+        }))
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    event,
+    handler::{swap_base_in_raydium, SwapBaseInRaydiumAccounts},
+    math,
+    state::TokenSide,
+    ChamberError, InitializeUserPositionSingleSided,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+impl<'c, 'info> InitializeUserPositionSingleSided<'info> {
+    pub fn process(
+        &mut self,
+        bump: u8,
+        side: TokenSide,
+        amount: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        let mut chamber = self.chamber.load_mut()?;
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+        self.user_position.init(
+            self.payer.key,
+            &self.chamber.key(),
+            self.clock_sysvar.unix_timestamp,
+            bump,
+        );
+
+        let fee = math::calculate_fee(amount, chamber.config.entry_fee_bps)?;
+        let amount = amount - fee;
+
+        let (treasury_ata, source_ata, destination_ata) = match side {
+            TokenSide::Base => (
+                &self.treasury_base_ata,
+                &self.chamber_base_ata,
+                &self.chamber_quote_ata,
+            ),
+            TokenSide::Quote => (
+                &self.treasury_quote_ata,
+                &self.chamber_quote_ata,
+                &self.chamber_base_ata,
+            ),
+        };
+
+        // 1. Take entry fee
+        if fee > 0 {
+            let cpi_accounts = token::Transfer {
+                from: self.user_ata.to_account_info(),
+                to: treasury_ata.to_account_info(),
+                authority: self.payer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, fee)?;
+        }
+
+        // 2. Deposit amount
+        {
+            let cpi_accounts = token::Transfer {
+                from: self.user_ata.to_account_info(),
+                to: source_ata.to_account_info(),
+                authority: self.payer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        // 3. Swap part of amount to the other token by configured split
+        let swap_amount = math::calculate_swap_amount(amount, side, chamber.config.base_split_bps)?;
+
+        let received_amount = if swap_amount > 0 {
+            let destination_amount_before = destination_ata.amount;
+
+            swap_base_in_raydium(
+                Box::new(SwapBaseInRaydiumAccounts {
+                    chamber: &self.chamber,
+                    authority_bump: chamber.authority_bump,
+                    chamber_authority: &self.authority,
+                    token_program: &self.token_program,
+                    amm_program: &self.amm_program,
+                    amm_id: &self.amm_id,
+                    amm_authority: &self.amm_authority,
+                    amm_open_orders: &self.amm_open_orders,
+                    amm_quantities_or_target_orders: &self.amm_quantities_or_target_orders,
+                    pool_coin_token_account: &self.pool_coin_token_account,
+                    pool_pc_token_account: &self.pool_pc_token_account,
+                    serum_program_id: &self.serum_program_id,
+                    serum_market: &self.serum_market,
+                    serum_bids: &self.serum_bids,
+                    serum_asks: &self.serum_asks,
+                    serum_event_queue: &self.serum_event_queue,
+                    serum_coin_vault_account: &self.serum_coin_vault_account,
+                    serum_pc_vault_account: &self.serum_pc_vault_account,
+                    serum_vault_signer: &self.serum_vault_signer,
+                    source_token_account: &source_ata.to_account_info(),
+                    destination_token_account: &destination_ata.to_account_info(),
+                }),
+                swap_amount,
+                minimum_amount_out,
+            )?;
+
+            // Credit actually received amount, instead of AMM quote
+            let destination_amount_after = match side {
+                TokenSide::Base => {
+                    self.chamber_quote_ata.reload()?;
+                    self.chamber_quote_ata.amount
+                }
+                TokenSide::Quote => {
+                    self.chamber_base_ata.reload()?;
+                    self.chamber_base_ata.amount
+                }
+            };
+
+            destination_amount_after
+                .checked_sub(destination_amount_before)
+                .ok_or(ChamberError::MathOverflow)?
+        } else {
+            0
+        };
+
+        if received_amount < minimum_amount_out {
+            return Err(ChamberError::SlippageExceeded.into());
+        }
+
+        // 4. Update position
+        let kept_amount = amount - swap_amount;
+
+        let (base_amount, quote_amount, base_fee, quote_fee) = match side {
+            TokenSide::Base => (kept_amount, received_amount, fee, 0),
+            TokenSide::Quote => (received_amount, kept_amount, 0, fee),
+        };
+
+        let base_units = chamber.deposit_base(base_amount)?;
+        let quote_units = chamber.deposit_quote(quote_amount)?;
+        self.user_position.deposit_base(base_units)?;
+        self.user_position.deposit_quote(quote_units)?;
+
+        emit!(event::PositionOpened {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            base_amount,
+            quote_amount,
+            base_fee,
+            quote_fee,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{
+    event,
+    handler::{realloc_account, ReallocAccountAccounts},
+    state, ChamberError, MigrateChamber,
+};
+use anchor_lang::{prelude::*, Discriminator};
+
+impl<'c, 'info> MigrateChamber<'info> {
+    pub fn process(
+        &mut self,
+        keeper: Pubkey,
+        pool: state::ChamberPool,
+        base_oracle: Pubkey,
+        quote_oracle: Pubkey,
+    ) -> Result<()> {
+        let chamber = self.chamber.to_account_info();
+
+        if chamber.data.borrow().get(..8) != Some(&state::Chamber::discriminator()[..]) {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+
+        // Layout of every previous version is a prefix of the current one
+        match chamber.data_len() {
+            state::Chamber::V0_LEN | state::Chamber::V1_LEN => realloc_account(
+                Box::new(ReallocAccountAccounts {
+                    account: &chamber,
+                    payer: &self.admin.to_account_info(),
+                    system_program: &self.system_program,
+                }),
+                state::Chamber::LEN,
+            )?,
+            state::Chamber::LEN => {}
+            _ => return Err(ChamberError::InvalidAccountLayout.into()),
+        }
+
+        let loader = AccountLoader::<state::Chamber>::try_from(&chamber)?;
+        let mut state = loader.load_mut()?;
+
+        if state.admin != self.admin.key() {
+            return Err(ErrorCode::ConstraintHasOne.into());
+        }
+
+        let previous_version = state.version;
+        state.migrate(&keeper, pool, &base_oracle, &quote_oracle)?;
+
+        emit!(event::ChamberMigrated {
+            chamber: chamber.key(),
+            user: self.admin.key(),
+            previous_version,
+            version: state.version,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{
+    event,
+    handler::{realloc_account, ReallocAccountAccounts},
+    state, utils, ChamberError, MigrateLegacyChamber,
+};
+use anchor_lang::{prelude::*, Discriminator};
+
+impl<'c, 'info> MigrateLegacyChamber<'info> {
+    pub fn process(
+        &mut self,
+        epoch_bump: u8,
+        amm_type: state::AmmType,
+        config: state::ChamberConfig,
+        keeper: Pubkey,
+        pool: state::ChamberPool,
+        base_oracle: Pubkey,
+        quote_oracle: Pubkey,
+    ) -> Result<()> {
+        let chamber = self.chamber.to_account_info();
+
+        if chamber.data.borrow().get(..8) != Some(&state::Chamber::discriminator()[..]) {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+
+        // Layout of the first release isn't a prefix of the current one, so it's decoded
+        // before realloc and the whole state is rewritten
+        if chamber.data_len() != state::LegacyChamber::LEN {
+            return Err(ChamberError::InvalidAccountLayout.into());
+        }
+
+        let legacy = state::LegacyChamber::deserialize(&mut &chamber.data.borrow()[8..])?;
+
+        let legacy_address = Pubkey::create_program_address(
+            &[
+                utils::CHAMBER_PREFIX.as_bytes(),
+                legacy.leveraged_farm.as_ref(),
+                &[legacy.bump],
+            ],
+            &crate::id(),
+        )
+        .map_err(|_| ChamberError::InvalidAccountLayout)?;
+
+        if legacy_address != chamber.key() {
+            return Err(ChamberError::InvalidAccountLayout.into());
+        }
+
+        // First release supported Tulip only
+        if legacy.protocol_type != state::ProtocolType::Tulip as u8 {
+            return Err(ChamberError::UnsupportedProtocol.into());
+        }
+
+        if legacy.base_mint != self.base_mint.key()
+            || legacy.quote_mint != self.quote_mint.key()
+            || legacy.base_ata != self.base_ata.key()
+            || legacy.quote_ata != self.quote_ata.key()
+        {
+            return Err(ErrorCode::ConstraintHasOne.into());
+        }
+
+        config.validate()?;
+
+        realloc_account(
+            Box::new(ReallocAccountAccounts {
+                account: &chamber,
+                payer: &self.admin.to_account_info(),
+                system_program: &self.system_program,
+            }),
+            state::Chamber::LEN,
+        )?;
+
+        let loader = AccountLoader::<state::Chamber>::try_from(&chamber)?;
+        let mut state = loader.load_mut()?;
+
+        state.init(
+            &legacy.leveraged_farm,
+            0,
+            &legacy.authority,
+            &self.admin.key(),
+            &legacy.base_ata,
+            &legacy.quote_ata,
+            &legacy.base_mint,
+            &legacy.quote_mint,
+            self.base_mint.decimals,
+            self.quote_mint.decimals,
+            &self.treasury_base_ata.key(),
+            &self.treasury_quote_ata.key(),
+            state::ProtocolType::Tulip,
+            amm_type,
+            config,
+            &keeper,
+            pool,
+            &base_oracle,
+            &quote_oracle,
+            self.clock_sysvar.unix_timestamp,
+            legacy.bump,
+            legacy.authority_bump,
+        );
+        state.legacy_address = 1;
+
+        // Token account balances become users idle balances, units are issued 1:1 as
+        // `LegacyUserPosition::upgrade()` does for positions. Funds deployed by the first
+        // release are not recorded in legs, so they don't back any units
+        state.total_base_amount = self.base_ata.amount;
+        state.total_quote_amount = self.quote_ata.amount;
+        state.total_base_units = self.base_ata.amount;
+        state.total_quote_units = self.quote_ata.amount;
+
+        self.epoch.init(&chamber.key(), 0, epoch_bump);
+
+        emit!(event::ChamberMigrated {
+            chamber: chamber.key(),
+            user: self.admin.key(),
+            // First release had no version field
+            previous_version: 0,
+            version: state.version,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{
+    event,
+    handler::{realloc_account, ReallocAccountAccounts},
+    state, ChamberError, MigrateUserPosition,
+};
+use anchor_lang::{prelude::*, Discriminator};
+use std::io::Cursor;
+
+impl<'c, 'info> MigrateUserPosition<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let user_position = self.user_position.to_account_info();
+
+        if user_position.data.borrow().get(..8) != Some(&state::UserPosition::discriminator()[..]) {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+
+        // Layout of the first release holds only amounts, which are carried into a new state
+        if user_position.data_len() == state::LegacyUserPosition::LEN {
+            let legacy =
+                state::LegacyUserPosition::deserialize(&mut &user_position.data.borrow()[8..])?;
+
+            realloc_account(
+                Box::new(ReallocAccountAccounts {
+                    account: &user_position,
+                    payer: &self.payer.to_account_info(),
+                    system_program: &self.system_program,
+                }),
+                state::UserPosition::LEN,
+            )?;
+
+            let state = legacy.upgrade();
+
+            let mut data = user_position.try_borrow_mut_data()?;
+            state.try_serialize(&mut Cursor::new(&mut data[..]))?;
+
+            emit!(event::PositionMigrated {
+                chamber: state.chamber,
+                user: state.owner,
+                // First release had no version field
+                previous_version: 0,
+                version: state.version,
+            });
+
+            return Ok(());
+        }
+
+        // Layout of every previous version is a prefix of the current one
+        match user_position.data_len() {
+            state::UserPosition::V0_LEN => realloc_account(
+                Box::new(ReallocAccountAccounts {
+                    account: &user_position,
+                    payer: &self.payer.to_account_info(),
+                    system_program: &self.system_program,
+                }),
+                state::UserPosition::LEN,
+            )?,
+            state::UserPosition::LEN => {}
+            _ => return Err(ChamberError::InvalidAccountLayout.into()),
+        }
+
+        let mut state =
+            state::UserPosition::try_deserialize(&mut user_position.data.borrow().as_ref())?;
+
+        let previous_version = state.version;
+        state.migrate()?;
+
+        let mut data = user_position.try_borrow_mut_data()?;
+        state.try_serialize(&mut Cursor::new(&mut data[..]))?;
+
+        emit!(event::PositionMigrated {
+            chamber: state.chamber,
+            user: state.owner,
+            previous_version,
+            version: state.version,
+        });
+
+        Ok(())
+    }
+}
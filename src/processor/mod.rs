@@ -1,15 +1,49 @@
 //! Module provide program instructions processor.
 
+mod cancel_withdrawal;
+mod claim_deposit;
+mod claim_withdrawal;
+mod collect_management_fee;
+mod crank_chamber;
+mod deleverage_chamber;
 mod deposit_chamber;
 mod initialize_chamber;
 mod initialize_chamber_strategy;
 mod initialize_user_position;
-mod settle_chamber_position;
-mod settle_chamber_position2;
+mod initialize_user_position_native;
+mod initialize_user_position_single_sided;
+mod migrate_chamber;
+mod migrate_legacy_chamber;
+mod migrate_user_position;
+mod process_chamber_epoch;
+mod queue_deposit;
+mod rebalance_chamber;
+mod request_withdrawal;
+mod swap_chamber;
+mod update_chamber_config;
+mod withdraw_user_position;
+mod withdraw_user_position_native;
 
+pub use cancel_withdrawal::*;
+pub use claim_deposit::*;
+pub use claim_withdrawal::*;
+pub use collect_management_fee::*;
+pub use crank_chamber::*;
+pub use deleverage_chamber::*;
 pub use deposit_chamber::*;
 pub use initialize_chamber::*;
 pub use initialize_chamber_strategy::*;
 pub use initialize_user_position::*;
-pub use settle_chamber_position::*;
-pub use settle_chamber_position2::*;
+pub use initialize_user_position_native::*;
+pub use initialize_user_position_single_sided::*;
+pub use migrate_chamber::*;
+pub use migrate_legacy_chamber::*;
+pub use migrate_user_position::*;
+pub use process_chamber_epoch::*;
+pub use queue_deposit::*;
+pub use rebalance_chamber::*;
+pub use request_withdrawal::*;
+pub use swap_chamber::*;
+pub use update_chamber_config::*;
+pub use withdraw_user_position::*;
+pub use withdraw_user_position_native::*;
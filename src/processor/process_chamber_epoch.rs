@@ -0,0 +1,275 @@
+use crate::{
+    event,
+    handler::{deposit_borrow_tulip_levfarm, DepositBorrowTulipLevfarmAccounts},
+    math, state, ChamberError, ProcessChamberEpoch,
+};
+use anchor_lang::prelude::*;
+use tulipv2_sdk_common::math::{
+    common::{TryAdd, TrySub},
+    decimal::Decimal,
+};
+
+impl<'c, 'info> ProcessChamberEpoch<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &'c [AccountInfo<'info>],
+        next_epoch_bump: u8,
+    ) -> Result<()> {
+        let mut chamber = self.chamber.load_mut()?;
+
+        // TODO: Extend protocols support
+        if chamber.protocol_type()? != state::ProtocolType::Tulip {
+            return Err(ChamberError::UnsupportedProtocol.into());
+        }
+
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+        // 1. Calculate NAV of shareholders
+        let state::TulipNav {
+            mut nav,
+            base_price,
+            quote_price,
+            idle_base_amount,
+            idle_quote_amount,
+        } = state::TulipNav::load(
+            &chamber,
+            &self.epoch,
+            remaining_accounts,
+            self.chamber_base_ata.amount,
+            self.chamber_quote_ata.amount,
+        )?;
+        let base_decimals = chamber.base_decimals;
+        let quote_decimals = chamber.quote_decimals;
+
+        // 2. Burn queued shares and reserve their value in quote token
+        let burned_shares = self.epoch.pending_withdraw_shares;
+        let mut withdraw_quote_amount = 0;
+
+        if burned_shares > 0 {
+            let withdraw_value =
+                math::calculate_shares_value(burned_shares, chamber.total_shares, nav)?;
+
+            withdraw_quote_amount =
+                math::calculate_amount(withdraw_value, quote_price, quote_decimals)?;
+
+            // Keeper must unwind liquidity before processing withdrawals
+            if withdraw_quote_amount > idle_quote_amount {
+                return Err(ChamberError::InsufficientLiquidity.into());
+            }
+
+            nav = nav.try_sub(withdraw_value)?;
+
+            chamber.total_shares = chamber
+                .total_shares
+                .checked_sub(burned_shares)
+                .ok_or(ChamberError::MathOverflow)?;
+            chamber.claimable_quote_amount = chamber
+                .claimable_quote_amount
+                .checked_add(withdraw_quote_amount)
+                .ok_or(ChamberError::MathOverflow)?;
+        }
+
+        // 3. Burn management fee shares and keep their value in quote token for treasury.
+        // Settlement waits for an epoch with enough idle quote left after withdrawals
+        let mut fee_quote_amount = 0;
+
+        if chamber.fee_shares > 0 {
+            let fee_value =
+                math::calculate_shares_value(chamber.fee_shares, chamber.total_shares, nav)?;
+            let amount = math::calculate_amount(fee_value, quote_price, quote_decimals)?;
+
+            if withdraw_quote_amount
+                .checked_add(amount)
+                .ok_or(ChamberError::MathOverflow)?
+                <= idle_quote_amount
+            {
+                nav = nav.try_sub(fee_value)?;
+                fee_quote_amount = amount;
+
+                chamber.settle_fee_shares(fee_quote_amount)?;
+            }
+        }
+
+        // 4. Mint shares for queued deposits at NAV before deposit
+        let base_amount = self.epoch.pending_base_amount;
+        let quote_amount = self.epoch.pending_quote_amount;
+
+        let deposit_value =
+            math::calculate_value(base_amount, base_price, base_decimals)?.try_add(
+                math::calculate_value(quote_amount, quote_price, quote_decimals)?,
+            )?;
+        let minted_shares = math::calculate_shares(deposit_value, chamber.total_shares, nav)?;
+
+        chamber.total_shares = chamber
+            .total_shares
+            .checked_add(minted_shares)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        // 5. Deploy queued deposits with single deposit per obligation. Free idle funds of
+        // settled chamber, e.g. leftovers of swaps, are already part of NAV and join them
+        let mut free_base_amount = 0;
+        let mut free_quote_amount = 0;
+
+        if chamber.operation()?.is_settled() {
+            let quote_amount = idle_quote_amount
+                .checked_sub(withdraw_quote_amount)
+                .ok_or(ChamberError::MathOverflow)?
+                .checked_sub(fee_quote_amount)
+                .ok_or(ChamberError::MathOverflow)?;
+            let free_value =
+                math::calculate_value(idle_base_amount, base_price, base_decimals)?.try_add(
+                    math::calculate_value(quote_amount, quote_price, quote_decimals)?,
+                )?;
+
+            // Dust isn't worth borrowing against
+            if free_value >= Decimal::from(math::MIN_IDLE_DEPLOY_VALUE) {
+                free_base_amount = idle_base_amount;
+                free_quote_amount = quote_amount;
+            }
+        }
+
+        let base_amount = base_amount
+            .checked_add(free_base_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        let quote_amount = quote_amount
+            .checked_add(free_quote_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        if base_amount > 0 || quote_amount > 0 {
+            chamber.begin_operation()?;
+
+            let legs = math::calculate_tulip_deposit(
+                base_amount,
+                quote_amount,
+                base_price,
+                quote_price,
+                base_decimals,
+                quote_decimals,
+            )?;
+
+            // Enter 1st position
+            deposit_borrow_tulip_levfarm(
+                Box::new(DepositBorrowTulipLevfarmAccounts {
+                    chamber: &self.chamber,
+                    authority_bump: chamber.authority_bump,
+                    chamber_authority: &self.authority,
+                    chamber_farm: &remaining_accounts[0],
+                    leveraged_farm: &remaining_accounts[1],
+                    chamber_farm_obligation: &remaining_accounts[2],
+                    coin_source_token_account: &self.chamber_base_ata.to_account_info(),
+                    coin_destination_token_account: &remaining_accounts[4],
+                    pc_source_token_account: &self.chamber_quote_ata.to_account_info(),
+                    pc_destination_token_account: &remaining_accounts[5],
+                    coin_deposit_reserve_account: &remaining_accounts[6],
+                    pc_deposit_reserve_account: &remaining_accounts[7],
+                    coin_reserve_liquidity_oracle: &remaining_accounts[8],
+                    pc_reserve_liquidity_oracle: &remaining_accounts[9],
+                    lending_market_account: &remaining_accounts[10],
+                    derived_lending_market_authority: &remaining_accounts[11],
+                    token_program: &self.token_program,
+                    lending_program: &remaining_accounts[12],
+                    coin_source_reserve_liquidity_token_account: &remaining_accounts[13],
+                    pc_source_reserve_liquidity_token_account: &remaining_accounts[14],
+                    coin_reserve_liquidity_fee_receiver: &remaining_accounts[15],
+                    pc_reserve_liquidity_fee_receiver: &remaining_accounts[16],
+                    borrow_authorizer: &remaining_accounts[17],
+                    lp_pyth_price_account: &remaining_accounts[18],
+                    vault_account: &remaining_accounts[19],
+                    position_info_account: &remaining_accounts[20],
+                    rent_sysvar: &self.rent_sysvar,
+                    levfarm_program: &remaining_accounts[22],
+                    system_program: &self.system_program,
+                }),
+                legs[0].base_collateral,
+                legs[0].quote_collateral,
+                legs[0].base_debt,
+                legs[0].quote_debt,
+                0,
+            )?;
+
+            chamber.legs[0].deposit_borrow(
+                legs[0].base_collateral,
+                legs[0].quote_collateral,
+                legs[0].base_debt,
+                legs[0].quote_debt,
+            )?;
+
+            // Enter 2nd position
+            deposit_borrow_tulip_levfarm(
+                Box::new(DepositBorrowTulipLevfarmAccounts {
+                    chamber: &self.chamber,
+                    authority_bump: chamber.authority_bump,
+                    chamber_authority: &self.authority,
+                    chamber_farm: &remaining_accounts[0],
+                    leveraged_farm: &remaining_accounts[1],
+                    chamber_farm_obligation: &remaining_accounts[3],
+                    coin_source_token_account: &self.chamber_base_ata.to_account_info(),
+                    coin_destination_token_account: &remaining_accounts[4],
+                    pc_source_token_account: &self.chamber_quote_ata.to_account_info(),
+                    pc_destination_token_account: &remaining_accounts[5],
+                    coin_deposit_reserve_account: &remaining_accounts[6],
+                    pc_deposit_reserve_account: &remaining_accounts[7],
+                    coin_reserve_liquidity_oracle: &remaining_accounts[8],
+                    pc_reserve_liquidity_oracle: &remaining_accounts[9],
+                    lending_market_account: &remaining_accounts[10],
+                    derived_lending_market_authority: &remaining_accounts[11],
+                    token_program: &self.token_program,
+                    lending_program: &remaining_accounts[12],
+                    coin_source_reserve_liquidity_token_account: &remaining_accounts[13],
+                    pc_source_reserve_liquidity_token_account: &remaining_accounts[14],
+                    coin_reserve_liquidity_fee_receiver: &remaining_accounts[15],
+                    pc_reserve_liquidity_fee_receiver: &remaining_accounts[16],
+                    borrow_authorizer: &remaining_accounts[17],
+                    lp_pyth_price_account: &remaining_accounts[18],
+                    vault_account: &remaining_accounts[19],
+                    position_info_account: &remaining_accounts[21],
+                    rent_sysvar: &self.rent_sysvar,
+                    levfarm_program: &remaining_accounts[22],
+                    system_program: &self.system_program,
+                }),
+                legs[1].base_collateral,
+                legs[1].quote_collateral,
+                legs[1].base_debt,
+                legs[1].quote_debt,
+                1,
+            )?;
+
+            chamber.legs[1].deposit_borrow(
+                legs[1].base_collateral,
+                legs[1].quote_collateral,
+                legs[1].base_debt,
+                legs[1].quote_debt,
+            )?;
+        }
+
+        // 6. Close epoch and open the next one
+        self.epoch.base_price = base_price.to_scaled_val()?;
+        self.epoch.quote_price = quote_price.to_scaled_val()?;
+        self.epoch.deposit_value = deposit_value.to_scaled_val()?;
+        self.epoch.minted_shares = minted_shares;
+        self.epoch.withdraw_quote_amount = withdraw_quote_amount;
+        self.epoch.processed = true;
+
+        chamber.epoch = chamber
+            .epoch
+            .checked_add(1)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.next_epoch
+            .init(&self.chamber.key(), chamber.epoch, next_epoch_bump);
+
+        emit!(event::EpochProcessed {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            epoch: self.epoch.index,
+            nav: nav.to_scaled_val()?,
+            minted_shares,
+            burned_shares,
+            withdraw_quote_amount,
+            fee_quote_amount,
+            free_base_amount,
+            free_quote_amount,
+        });
+
+        Ok(())
+    }
+}
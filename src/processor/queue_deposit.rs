@@ -0,0 +1,51 @@
+use crate::{event, state, ChamberError, QueueDeposit};
+use anchor_lang::prelude::*;
+
+impl<'c, 'info> QueueDeposit<'info> {
+    pub fn process(&mut self, base_amount: u64, quote_amount: u64) -> Result<()> {
+        if self.user_position.has_queued_deposit()
+            && self.user_position.queued_epoch != self.epoch.index
+        {
+            return Err(ChamberError::UnclaimedEpoch.into());
+        }
+
+        let mut chamber = self.chamber.load_mut()?;
+
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+        if base_amount > chamber.base_units_amount(self.user_position.base_amount)?
+            || quote_amount > chamber.quote_units_amount(self.user_position.quote_amount)?
+        {
+            return Err(ChamberError::InsufficientUserPositionFunds.into());
+        }
+
+        // Queued deposit doesn't change position status, until it's claimed
+        let status = self.user_position.status;
+        if status != state::PositionStatus::Pending && status != state::PositionStatus::Deployed {
+            return Err(ChamberError::InvalidPositionStatus.into());
+        }
+        self.user_position.last_deposit_ts = self.clock_sysvar.unix_timestamp;
+
+        // Queued amounts leave idle balances and become part of epoch batch
+        let base_units = chamber.withdraw_base(base_amount)?;
+        let quote_units = chamber.withdraw_quote(quote_amount)?;
+        self.user_position.queue_deposit(
+            self.epoch.index,
+            base_units,
+            quote_units,
+            base_amount,
+            quote_amount,
+        )?;
+        self.epoch.queue_deposit(base_amount, quote_amount)?;
+
+        emit!(event::DepositQueued {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            epoch: self.epoch.index,
+            base_amount,
+            quote_amount,
+        });
+
+        Ok(())
+    }
+}
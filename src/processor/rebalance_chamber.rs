@@ -0,0 +1,215 @@
+use crate::{
+    event,
+    handler::{
+        deposit_borrow_tulip_levfarm, withdraw_vault_raydium_tulip_levfarm,
+        DepositBorrowTulipLevfarmAccounts, WithdrawVaultRaydiumTulipLevfarmAccounts,
+    },
+    math, state, ChamberError, RebalanceChamber,
+};
+use anchor_lang::prelude::*;
+use tulipv2_sdk_common::pyth;
+
+/// Obligation borrowing base, see `math::calculate_tulip_deposit()`.
+const BASE_DEBT_LEG: usize = 1;
+
+impl<'c, 'info> RebalanceChamber<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &'c [AccountInfo<'info>],
+        nonce: u8,
+        meta_nonce: u8,
+    ) -> Result<()> {
+        let mut chamber = self.chamber.load_mut()?;
+
+        // TODO: Extend protocols support
+        if chamber.protocol_type()? != state::ProtocolType::Tulip {
+            return Err(ChamberError::UnsupportedProtocol.into());
+        }
+
+        // Zero threshold would rebalance on every rounding error
+        if chamber.config.rebalance_threshold_bps == 0 {
+            return Err(ChamberError::InvalidConfig.into());
+        }
+
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+        // 1. Check pool price against oracles, since exposure is priced from pool reserves
+        // and skewed pool would make keeper borrow or unwind at manipulated price
+        let pool_base_amount = self.pool_coin_token_account.amount;
+        let base_price = pyth::load_pyth_price(self.base_oracle.data.borrow().as_ref())?;
+        let quote_price = pyth::load_pyth_price(self.quote_oracle.data.borrow().as_ref())?;
+
+        let deviation_bps = math::calculate_pool_price_deviation_bps(
+            pool_base_amount,
+            self.pool_pc_token_account.amount,
+            base_price,
+            quote_price,
+            chamber.base_decimals,
+            chamber.quote_decimals,
+        )?;
+        if deviation_bps > math::MAX_POOL_PRICE_DEVIATION_BPS {
+            return Err(ChamberError::PoolPriceDeviation.into());
+        }
+
+        // 2. Compute net base exposure: base share of pool reserve owned by chamber LP
+        // against base debt of both legs
+        let lp_supply = self.lp_mint.supply;
+
+        let (base_liquidity, base_debt) = math::calculate_base_exposure(
+            &chamber.legs,
+            &chamber.lp_amounts,
+            pool_base_amount,
+            lp_supply,
+        )?;
+
+        let drift_bps = math::calculate_exposure_drift_bps(base_liquidity, base_debt)?;
+        if drift_bps <= chamber.config.rebalance_threshold_bps as u64 {
+            return Err(ChamberError::RebalanceNotRequired.into());
+        }
+
+        // 3. Borrow base when liquidity outgrows debt, half of borrowed value ends up
+        // as base in liquidity after settlement
+        if base_liquidity > base_debt {
+            chamber.begin_operation()?;
+
+            let base_borrow_amount = (base_liquidity - base_debt)
+                .checked_mul(2)
+                .ok_or(ChamberError::MathOverflow)?;
+
+            deposit_borrow_tulip_levfarm(
+                Box::new(DepositBorrowTulipLevfarmAccounts {
+                    chamber: &self.chamber,
+                    authority_bump: chamber.authority_bump,
+                    chamber_authority: &self.authority,
+                    chamber_farm: &remaining_accounts[0],
+                    leveraged_farm: &remaining_accounts[1],
+                    chamber_farm_obligation: &remaining_accounts[2 + BASE_DEBT_LEG],
+                    coin_source_token_account: &self.chamber_base_ata.to_account_info(),
+                    coin_destination_token_account: &remaining_accounts[4],
+                    pc_source_token_account: &self.chamber_quote_ata.to_account_info(),
+                    pc_destination_token_account: &remaining_accounts[5],
+                    coin_deposit_reserve_account: &remaining_accounts[6],
+                    pc_deposit_reserve_account: &remaining_accounts[7],
+                    coin_reserve_liquidity_oracle: &remaining_accounts[8],
+                    pc_reserve_liquidity_oracle: &remaining_accounts[9],
+                    lending_market_account: &remaining_accounts[10],
+                    derived_lending_market_authority: &remaining_accounts[11],
+                    token_program: &self.token_program,
+                    lending_program: &remaining_accounts[12],
+                    coin_source_reserve_liquidity_token_account: &remaining_accounts[13],
+                    pc_source_reserve_liquidity_token_account: &remaining_accounts[14],
+                    coin_reserve_liquidity_fee_receiver: &remaining_accounts[15],
+                    pc_reserve_liquidity_fee_receiver: &remaining_accounts[16],
+                    borrow_authorizer: &remaining_accounts[17],
+                    lp_pyth_price_account: &remaining_accounts[18],
+                    vault_account: &remaining_accounts[19],
+                    position_info_account: &remaining_accounts[20 + BASE_DEBT_LEG],
+                    rent_sysvar: &self.rent_sysvar,
+                    levfarm_program: &remaining_accounts[22],
+                    system_program: &self.system_program,
+                }),
+                0,
+                0,
+                base_borrow_amount,
+                0,
+                BASE_DEBT_LEG as u8,
+            )?;
+
+            chamber.legs[BASE_DEBT_LEG].deposit_borrow(0, 0, base_borrow_amount, 0)?;
+
+            // Borrowed funds are swapped and provided to liquidity by `CrankChamber` steps
+
+            emit!(event::ChamberRebalanced {
+                chamber: self.chamber.key(),
+                user: self.payer.key(),
+                obligation_index: BASE_DEBT_LEG as u8,
+                base_borrow_amount,
+                withdraw_percent: 0,
+                drift_bps,
+            });
+
+            return Ok(());
+        }
+
+        // 4. Repay base debt when it outgrows liquidity, by unwinding part of the leg,
+        // which owes more base than it holds in liquidity
+        // TODO: Support Orca vault withdrawals
+        if chamber.amm_type()? != state::AmmType::Raydium {
+            return Err(ChamberError::UnsupportedAmm.into());
+        }
+
+        // Unwinding is not allowed in the middle of deploy flow
+        if !chamber.operation()?.is_settled() {
+            return Err(ChamberError::InvalidOperation.into());
+        }
+
+        let leg_base_liquidity = math::calculate_pro_rata(
+            pool_base_amount,
+            chamber.lp_amounts[BASE_DEBT_LEG],
+            lp_supply,
+        )?;
+        let withdraw_percent = math::calculate_repay_percent(
+            base_debt - base_liquidity,
+            leg_base_liquidity,
+            chamber.legs[BASE_DEBT_LEG].base_debt,
+        )?;
+
+        if withdraw_percent == 0 {
+            return Err(ChamberError::RebalanceNotRequired.into());
+        }
+
+        withdraw_vault_raydium_tulip_levfarm(
+            Box::new(WithdrawVaultRaydiumTulipLevfarmAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                chamber_farm: &remaining_accounts[0],
+                chamber_farm_obligation_vault: &remaining_accounts[1 + BASE_DEBT_LEG],
+                leveraged_farm: &remaining_accounts[3],
+                vault_program: &remaining_accounts[4],
+                authority_token_account: &remaining_accounts[5 + BASE_DEBT_LEG],
+                vault_pda_account: &remaining_accounts[7],
+                vault: &remaining_accounts[8],
+                lp_token_account: &remaining_accounts[9],
+                chamber_balance_account: &remaining_accounts[10 + BASE_DEBT_LEG],
+                system_program: &self.system_program,
+                stake_program: &remaining_accounts[12],
+                pool_id: &remaining_accounts[13],
+                pool_authority: &remaining_accounts[14],
+                vault_info_account: &remaining_accounts[15],
+                pool_lp_token_account: &remaining_accounts[16],
+                reward_a_token_account: &remaining_accounts[17],
+                pool_reward_a_token_account: &remaining_accounts[18],
+                reward_b_token_account: &remaining_accounts[19],
+                pool_reward_b_token_account: &remaining_accounts[20],
+                clock_sysvar: &self.clock_sysvar,
+                rent_sysvar: &self.rent_sysvar,
+                token_program_id: &self.token_program,
+                chamber_balance_metadata: &remaining_accounts[21 + BASE_DEBT_LEG],
+                lending_market: &remaining_accounts[23],
+                chamber_farm_obligation: &remaining_accounts[24 + BASE_DEBT_LEG],
+                lending_market_authority: &remaining_accounts[26],
+                lending_program: &remaining_accounts[27],
+                position_info: &remaining_accounts[29 + BASE_DEBT_LEG],
+                levfarm_program: &remaining_accounts[28],
+            }),
+            nonce,
+            meta_nonce,
+            BASE_DEBT_LEG as u8,
+            withdraw_percent,
+        )?;
+
+        chamber.unwind_leg(BASE_DEBT_LEG, withdraw_percent)?;
+
+        emit!(event::ChamberRebalanced {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            obligation_index: BASE_DEBT_LEG as u8,
+            base_borrow_amount: 0,
+            withdraw_percent,
+            drift_bps,
+        });
+
+        Ok(())
+    }
+}
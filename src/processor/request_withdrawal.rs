@@ -0,0 +1,35 @@
+use crate::{event, state, ChamberError, RequestWithdrawal};
+use anchor_lang::prelude::*;
+
+impl<'c, 'info> RequestWithdrawal<'info> {
+    pub fn process(&mut self, shares: u64) -> Result<()> {
+        if shares == 0 {
+            return Err(ChamberError::InvalidAmount.into());
+        }
+
+        if self.user_position.has_withdrawal_request()
+            && self.user_position.withdrawal_epoch != self.epoch.index
+        {
+            return Err(ChamberError::UnclaimedEpoch.into());
+        }
+
+        // Cooldown restarts with every request
+        let timestamp = self.clock_sysvar.unix_timestamp;
+
+        self.user_position
+            .request_withdrawal(self.epoch.index, shares, timestamp)?;
+        self.epoch.queue_withdrawal(shares)?;
+        self.user_position
+            .transition(state::PositionStatus::WithdrawRequested)?;
+
+        emit!(event::WithdrawalRequested {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            epoch: self.epoch.index,
+            shares,
+            timestamp,
+        });
+
+        Ok(())
+    }
+}
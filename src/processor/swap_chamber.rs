@@ -0,0 +1,113 @@
+use crate::{
+    event,
+    handler::{swap_aggregator, SwapAggregatorAccounts},
+    ChamberError, SwapChamber,
+};
+use anchor_lang::prelude::*;
+
+impl<'c, 'info> SwapChamber<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &'c [AccountInfo<'info>],
+        amount_in: u64,
+        minimum_amount_out: u64,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let chamber = self.chamber.load()?;
+
+        // Default key is system program, so swaps are disabled until configured
+        if chamber.config.swap_program == Pubkey::default() {
+            return Err(ChamberError::InvalidSwapProgram.into());
+        }
+
+        // Positions are mid-flight, balances belong to crank
+        if !chamber.operation()?.is_settled() {
+            return Err(ChamberError::InvalidOperation.into());
+        }
+
+        if amount_in == 0 || self.source_ata.key() == self.destination_ata.key() {
+            return Err(ChamberError::InvalidAmount.into());
+        }
+
+        // Keeper only harvests rewards, strategy tokens are swapped by admin
+        if self.admin.key() != chamber.admin {
+            let source = self.source_ata.key();
+            let destination = self.destination_ata.key();
+
+            if source == chamber.base_ata
+                || source == chamber.quote_ata
+                || (destination != chamber.base_ata && destination != chamber.quote_ata)
+            {
+                return Err(ChamberError::InvalidKeeper.into());
+            }
+        }
+
+        let source_before = self.source_ata.amount;
+        let destination_before = self.destination_ata.amount;
+
+        swap_aggregator(
+            Box::new(SwapAggregatorAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                swap_program: &self.swap_program,
+                route_accounts: remaining_accounts,
+            }),
+            data,
+        )?;
+
+        self.source_ata.reload()?;
+        self.destination_ata.reload()?;
+
+        // Route is untrusted, so trust only balance changes
+        let actual_amount_in = source_before
+            .checked_sub(self.source_ata.amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        let amount_out = self
+            .destination_ata
+            .amount
+            .checked_sub(destination_before)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        if actual_amount_in > amount_in {
+            return Err(ChamberError::SwapInputExceeded.into());
+        }
+
+        if amount_out < minimum_amount_out {
+            return Err(ChamberError::SlippageExceeded.into());
+        }
+
+        // Authority signs whole route, so whatever it spends, users idle balances,
+        // queued deposits and claimable withdrawals must stay in chamber token accounts
+        self.chamber_base_ata.reload()?;
+        self.chamber_quote_ata.reload()?;
+
+        let reserved_base_amount = chamber
+            .total_base_amount
+            .checked_add(self.epoch.pending_base_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        let reserved_quote_amount = chamber
+            .total_quote_amount
+            .checked_add(self.epoch.pending_quote_amount)
+            .ok_or(ChamberError::MathOverflow)?
+            .checked_add(chamber.claimable_quote_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        if self.chamber_base_ata.amount < reserved_base_amount
+            || self.chamber_quote_ata.amount < reserved_quote_amount
+        {
+            return Err(ChamberError::ReservedBalanceSpent.into());
+        }
+
+        emit!(event::ChamberSwapped {
+            chamber: self.chamber.key(),
+            user: self.admin.key(),
+            source_mint: self.source_ata.mint,
+            destination_mint: self.destination_ata.mint,
+            amount_in: actual_amount_in,
+            amount_out,
+        });
+
+        Ok(())
+    }
+}
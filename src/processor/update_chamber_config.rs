@@ -0,0 +1,23 @@
+use crate::{event, state, UpdateChamberConfig};
+use anchor_lang::prelude::*;
+
+impl<'c, 'info> UpdateChamberConfig<'info> {
+    pub fn process(&mut self, config: state::ChamberConfig) -> Result<()> {
+        config.validate()?;
+
+        let mut chamber = self.chamber.load_mut()?;
+
+        // Charge fee at previous rate before it changes
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+        chamber.config = config;
+
+        emit!(event::ChamberConfigUpdated {
+            chamber: self.chamber.key(),
+            user: self.admin.key(),
+            config: chamber.config,
+        });
+
+        Ok(())
+    }
+}
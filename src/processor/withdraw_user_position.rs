@@ -0,0 +1,52 @@
+use crate::{
+    event,
+    handler::{withdraw_idle_balance, WithdrawIdleBalanceAccounts},
+    WithdrawUserPosition,
+};
+use anchor_lang::prelude::*;
+
+impl<'c, 'info> WithdrawUserPosition<'info> {
+    pub fn process(&mut self, base_amount: u64, quote_amount: u64) -> Result<()> {
+        // TODO: Unwind deployed liquidity on withdrawal
+
+        let mut chamber = self.chamber.load_mut()?;
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+        let (base_fee, quote_fee) = self.user_position.withdraw_idle(
+            &mut chamber,
+            base_amount,
+            quote_amount,
+            self.clock_sysvar.unix_timestamp,
+        )?;
+
+        withdraw_idle_balance(
+            Box::new(WithdrawIdleBalanceAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                chamber_base_ata: &self.chamber_base_ata.to_account_info(),
+                chamber_quote_ata: &self.chamber_quote_ata.to_account_info(),
+                treasury_base_ata: &self.treasury_base_ata.to_account_info(),
+                treasury_quote_ata: &self.treasury_quote_ata.to_account_info(),
+                base_destination: &self.user_base_ata.to_account_info(),
+                quote_destination: &self.user_quote_ata.to_account_info(),
+                token_program: &self.token_program,
+            }),
+            base_amount,
+            quote_amount,
+            base_fee,
+            quote_fee,
+        )?;
+
+        emit!(event::PositionWithdrawn {
+            chamber: self.chamber.key(),
+            user: self.payer.key(),
+            base_amount,
+            quote_amount,
+            base_fee,
+            quote_fee,
+        });
+
+        Ok(())
+    }
+}
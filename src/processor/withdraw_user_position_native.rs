@@ -0,0 +1,71 @@
+use crate::{
+    event,
+    handler::{withdraw_idle_balance, WithdrawIdleBalanceAccounts},
+    utils, WithdrawUserPositionNative,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+impl<'c, 'info> WithdrawUserPositionNative<'info> {
+    pub fn process(&mut self, base_amount: u64, quote_amount: u64) -> Result<()> {
+        // TODO: Unwind deployed liquidity on withdrawal
+
+        let mut chamber = self.chamber.load_mut()?;
+        chamber.accrue_management_fee(self.clock_sysvar.unix_timestamp)?;
+
+        let (base_fee, quote_fee) = self.user_position.withdraw_idle(
+            &mut chamber,
+            base_amount,
+            quote_amount,
+            self.clock_sysvar.unix_timestamp,
+        )?;
+
+        // 1. Withdraw base through temporary account, base fee stays wrapped in treasury
+        withdraw_idle_balance(
+            Box::new(WithdrawIdleBalanceAccounts {
+                chamber: &self.chamber,
+                authority_bump: chamber.authority_bump,
+                chamber_authority: &self.authority,
+                chamber_base_ata: &self.chamber_base_ata.to_account_info(),
+                chamber_quote_ata: &self.chamber_quote_ata.to_account_info(),
+                treasury_base_ata: &self.treasury_base_ata.to_account_info(),
+                treasury_quote_ata: &self.treasury_quote_ata.to_account_info(),
+                base_destination: &self.unwrap_account.to_account_info(),
+                quote_destination: &self.user_quote_ata.to_account_info(),
+                token_program: &self.token_program,
+            }),
+            base_amount,
+            quote_amount,
+            base_fee,
+            quote_fee,
+        )?;
+
+        // 2. Unwrap base to payer, closing wrapped SOL account returns both balance and rent
+        let chamber_pubkey = self.chamber.key();
+        let seeds: &[&[&[u8]]] = &[&[
+            utils::CHAMBER_AUTHORITY_PREFIX.as_bytes(),
+            chamber_pubkey.as_ref(),
+            &[chamber.authority_bump],
+        ]];
+
+        let cpi_accounts = token::CloseAccount {
+            account: self.unwrap_account.to_account_info(),
+            destination: self.payer.to_account_info(),
+            authority: self.authority.to_account_info(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, seeds);
+        token::close_account(cpi_ctx)?;
+
+        emit!(event::PositionWithdrawn {
+            chamber: chamber_pubkey,
+            user: self.payer.key(),
+            base_amount,
+            quote_amount,
+            base_fee,
+            quote_fee,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+//! Module define AMM's used by leveraged farms.
+
+use anchor_lang::prelude::*;
+
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub enum AmmType {
+    Raydium,
+    Orca,
+}
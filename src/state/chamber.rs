@@ -1,16 +1,29 @@
 //! Module provide protocol agnostic repository for DeFi strategies.
 
-use super::ProtocolType;
+use super::{
+    AmmType, ChamberConfig, ChamberOperation, ChamberPool, ProtocolType, StrategyLeg, TokenSide,
+};
+use crate::{math, ChamberError};
 use anchor_lang::prelude::*;
+use std::result::Result;
 
-#[account]
+/// Zero-copy account, so instructions don't pay for Borsh decoding of the whole state.
+/// Fields are packed in declaration order, which keeps byte layout of Borsh encoded
+/// accounts, enums are stored as `u8` and read through typed getters.
+#[account(zero_copy)]
 pub struct Chamber {
     /// Leveraged farm associated with `ProtocolType`.
     pub leveraged_farm: Pubkey,
 
+    /// Distinguishes chambers running different strategy variants on the same farm.
+    pub index: u8,
+
     /// `Chamber` authority for manage positions.
     pub authority: Pubkey,
 
+    /// `Chamber` administrator allowed to update configuration.
+    pub admin: Pubkey,
+
     /// Base associated token account.
     pub base_ata: Pubkey,
 
@@ -19,35 +32,496 @@ pub struct Chamber {
 
     pub base_mint: Pubkey,
     pub quote_mint: Pubkey,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+
+    /// Treasury base token account, receives entry and exit fees.
+    pub treasury_base_ata: Pubkey,
+
+    /// Treasury quote token account, receives entry and exit fees.
+    pub treasury_quote_ata: Pubkey,
+
+    /// `ProtocolType`, see `Chamber::protocol_type()`.
+    pub protocol_type: u8,
+
+    /// AMM used by leveraged farm, selects settlement path, see `Chamber::amm_type()`.
+    pub amm_type: u8,
+
+    pub config: ChamberConfig,
+
+    /// Strategy obligations accounting, indexed by obligation index.
+    pub legs: [StrategyLeg; 2],
+
+    /// Total base amount of users idle balances, including not collected management fee.
+    pub total_base_amount: u64,
+
+    /// Total quote amount of users idle balances, including not collected management fee.
+    pub total_quote_amount: u64,
+
+    /// Management fee charged in base token, but not collected yet.
+    pub accrued_base_fee: u64,
+
+    /// Management fee charged in quote token, but not collected yet.
+    pub accrued_quote_fee: u64,
+
+    /// Sub-unit base fee carried to the next accrual.
+    pub base_fee_remainder: u128,
+
+    /// Sub-unit quote fee carried to the next accrual.
+    pub quote_fee_remainder: u128,
+
+    /// Unix timestamp of the last management fee accrual.
+    pub last_fee_accrual_ts: i64,
+
+    /// Outstanding shares of deployed strategy.
+    pub total_shares: u64,
+
+    /// Current `ChamberEpoch`, which collects queued requests.
+    pub epoch: u64,
+
+    /// Quote amount reserved for processed, but not claimed withdrawals.
+    pub claimable_quote_amount: u64,
+
+    /// Current step of the deploy flow driven by `CrankChamber`, see `Chamber::operation()`.
+    pub operation: u8,
 
-    pub protocol_type: ProtocolType,
     pub bump: u8,
     pub authority_bump: u8,
+
+    /// Layout version, accounts created before versioning are upgraded by `MigrateChamber`.
+    pub version: u8,
+
+    /// Outstanding units of users base idle balances, see `Chamber::base_units_amount()`.
+    pub total_base_units: u64,
+
+    /// Outstanding units of users quote idle balances, see `Chamber::quote_units_amount()`.
+    pub total_quote_units: u64,
+
+    /// LP minted into strategy obligations, indexed by obligation index.
+    pub lp_amounts: [u64; 2],
+
+    /// Keeper allowed to run strategy maintenance besides admin.
+    pub keeper: Pubkey,
+
+    /// AMM pool liquidity is provided to.
+    pub pool: ChamberPool,
+
+    /// Pyth price account of base token, which prices chamber NAV.
+    pub base_oracle: Pubkey,
+
+    /// Pyth price account of quote token, which prices chamber NAV.
+    pub quote_oracle: Pubkey,
+
+    /// Shares minted as management fee on deployed strategy, included in `total_shares`.
+    pub fee_shares: u64,
+
+    /// Sub-unit fee shares carried to the next accrual.
+    pub share_fee_remainder: u128,
+
+    /// Set for chambers of the first release, which are derived without `index` seed,
+    /// see `Chamber::index_seed()`.
+    pub legacy_address: u8,
+
+    /// Space for new fields, so they don't change account size.
+    pub reserved: [u8; 39],
 }
 
 impl Chamber {
-    pub const LEN: usize = 8 + (32 + 32 + 32 + 32 + 32 + 32 + 1 + 1 + 1);
+    pub const VERSION: u8 = 3;
+    pub const RESERVED_LEN: usize = 39;
+
+    pub const LEN: usize = 8 + std::mem::size_of::<Chamber>();
+
+    /// Size of layout without `version` and `reserved` fields.
+    pub const V0_LEN: usize = 8
+        + 32 * 9 // pubkeys
+        + 1 // index
+        + 1 * 2 // decimals
+        + 1 * 2 // protocol and AMM types
+        + ChamberConfig::LEN
+        + StrategyLeg::LEN * 2
+        + 8 * 4 // amounts and fees
+        + 16 * 2 // fee remainders
+        + 8 // fee accrual timestamp
+        + 8 * 3 // shares, epoch and claimable amount
+        + 1 // operation
+        + 1 * 2; // bumps
+
+    /// Size of layout with `version` and `reserved` fields, before `pool` and oracles are
+    /// appended.
+    pub const V1_LEN: usize = Self::V0_LEN + 1 + 64;
 
     pub fn init(
         &mut self,
         leveraged_farm: &Pubkey,
+        index: u8,
         authority: &Pubkey,
+        admin: &Pubkey,
         base_ata: &Pubkey,
         quote_ata: &Pubkey,
         base_mint: &Pubkey,
         quote_mint: &Pubkey,
+        base_decimals: u8,
+        quote_decimals: u8,
+        treasury_base_ata: &Pubkey,
+        treasury_quote_ata: &Pubkey,
         protocol_type: ProtocolType,
+        amm_type: AmmType,
+        config: ChamberConfig,
+        keeper: &Pubkey,
+        pool: ChamberPool,
+        base_oracle: &Pubkey,
+        quote_oracle: &Pubkey,
+        timestamp: i64,
         bump: u8,
         authority_bump: u8,
     ) {
         self.leveraged_farm = leveraged_farm.clone();
+        self.index = index;
         self.authority = authority.clone();
+        self.admin = admin.clone();
         self.base_ata = base_ata.clone();
         self.quote_ata = quote_ata.clone();
         self.base_mint = base_mint.clone();
         self.quote_mint = quote_mint.clone();
-        self.protocol_type = protocol_type;
+        self.base_decimals = base_decimals;
+        self.quote_decimals = quote_decimals;
+        self.treasury_base_ata = treasury_base_ata.clone();
+        self.treasury_quote_ata = treasury_quote_ata.clone();
+        self.protocol_type = protocol_type as u8;
+        self.amm_type = amm_type as u8;
+        self.config = config;
+        self.legs = [StrategyLeg::default(); 2];
+        self.total_base_amount = 0;
+        self.total_quote_amount = 0;
+        self.accrued_base_fee = 0;
+        self.accrued_quote_fee = 0;
+        self.base_fee_remainder = 0;
+        self.quote_fee_remainder = 0;
+        self.last_fee_accrual_ts = timestamp;
+        self.total_shares = 0;
+        self.epoch = 0;
+        self.claimable_quote_amount = 0;
+        self.operation = ChamberOperation::Idle as u8;
         self.bump = bump;
         self.authority_bump = authority_bump;
+        self.version = Self::VERSION;
+        self.total_base_units = 0;
+        self.total_quote_units = 0;
+        self.lp_amounts = [0; 2];
+        self.keeper = *keeper;
+        self.pool = pool;
+        self.base_oracle = *base_oracle;
+        self.quote_oracle = *quote_oracle;
+        self.fee_shares = 0;
+        self.share_fee_remainder = 0;
+        self.legacy_address = 0;
+        self.reserved = [0; Self::RESERVED_LEN];
+    }
+
+    /// Upgrade state decoded from previous layout to `Chamber::VERSION`.
+    ///
+    /// `keeper`, `pool` and oracles are set by admin for chambers created before version 3.
+    pub fn migrate(
+        &mut self,
+        keeper: &Pubkey,
+        pool: ChamberPool,
+        base_oracle: &Pubkey,
+        quote_oracle: &Pubkey,
+    ) -> Result<(), ChamberError> {
+        if self.version >= Self::VERSION {
+            return Err(ChamberError::AlreadyMigrated);
+        }
+
+        // Version 0 -> 1: `version` and `reserved` are appended, zeroed by realloc
+
+        // Version 1 -> 2: user balances become units, issued 1:1 to amounts they hold
+        if self.version < 2 {
+            self.total_base_units = self.total_base_amount;
+            self.total_quote_units = self.total_quote_amount;
+        }
+
+        // Version 2 -> 3: `lp_amounts` and `keeper` take reserved space, `pool`, oracles
+        // and `reserved` are appended. LP minted before is not recorded, so exposure of such
+        // chambers counts only liquidity added after migration
+        if self.version < 3 {
+            self.keeper = *keeper;
+            self.pool = pool;
+            self.base_oracle = *base_oracle;
+            self.quote_oracle = *quote_oracle;
+        }
+
+        self.version = Self::VERSION;
+
+        Ok(())
+    }
+
+    pub fn protocol_type(&self) -> Result<ProtocolType, ChamberError> {
+        ProtocolType::try_from_slice(&[self.protocol_type])
+            .map_err(|_| ChamberError::InvalidAccountLayout)
+    }
+
+    pub fn amm_type(&self) -> Result<AmmType, ChamberError> {
+        AmmType::try_from_slice(&[self.amm_type]).map_err(|_| ChamberError::InvalidAccountLayout)
+    }
+
+    pub fn operation(&self) -> Result<ChamberOperation, ChamberError> {
+        ChamberOperation::try_from_slice(&[self.operation])
+            .map_err(|_| ChamberError::InvalidAccountLayout)
+    }
+
+    /// Last seed of chamber address, empty for chambers created by the first release.
+    pub fn index_seed(&self) -> &[u8] {
+        if self.legacy_address != 0 {
+            &[]
+        } else {
+            std::slice::from_ref(&self.index)
+        }
+    }
+
+    pub fn mint(&self, side: TokenSide) -> Pubkey {
+        match side {
+            TokenSide::Base => self.base_mint,
+            TokenSide::Quote => self.quote_mint,
+        }
+    }
+
+    /// Whether `signer` may run strategy maintenance.
+    pub fn is_keeper(&self, signer: &Pubkey) -> bool {
+        *signer == self.admin || *signer == self.keeper
+    }
+
+    /// Record LP minted into `index` obligation.
+    pub fn add_lp(&mut self, index: usize, amount: u64) -> Result<(), ChamberError> {
+        Ok(self.lp_amounts[index] = self.lp_amounts[index]
+            .checked_add(amount)
+            .ok_or(ChamberError::MathOverflow)?)
+    }
+
+    /// Reduce `index` obligation accounting and LP by `percent` of unwound liquidity.
+    pub fn unwind_leg(&mut self, index: usize, percent: u8) -> Result<(), ChamberError> {
+        self.legs[index].unwind(percent)?;
+
+        let unwound = (self.lp_amounts[index] as u128)
+            .checked_mul(percent as u128)
+            .ok_or(ChamberError::MathOverflow)?
+            / 100;
+        self.lp_amounts[index] -= unwound as u64;
+
+        Ok(())
+    }
+
+    /// Start new deploy flow, previous one must be finished.
+    pub fn begin_operation(&mut self) -> Result<(), ChamberError> {
+        if !self.operation()?.is_settled() {
+            return Err(ChamberError::InvalidOperation);
+        }
+
+        Ok(self.operation = ChamberOperation::Borrowed as u8)
+    }
+
+    /// Move deploy flow to the next step.
+    pub fn advance_operation(&mut self) -> Result<(), ChamberError> {
+        Ok(self.operation = self
+            .operation()?
+            .next()
+            .ok_or(ChamberError::InvalidOperation)? as u8)
+    }
+
+    /// Charge management fee for the time elapsed since the last accrual.
+    ///
+    /// Fee on users idle balances stays in chamber token accounts until
+    /// `Chamber::collect_management_fee()`, but stops counting towards users balances at once.
+    /// Fee on deployed strategy is minted as `fee_shares`, which dilute users shares and are
+    /// settled at NAV by `ProcessChamberEpoch`.
+    pub fn accrue_management_fee(&mut self, timestamp: i64) -> Result<(), ChamberError> {
+        if timestamp <= self.last_fee_accrual_ts {
+            return Ok(());
+        }
+
+        let elapsed = (timestamp - self.last_fee_accrual_ts) as u64;
+        let net_base_amount = self.net_base_amount()?;
+        let net_quote_amount = self.net_quote_amount()?;
+
+        let (base_fee, base_fee_remainder) = math::calculate_management_fee(
+            net_base_amount,
+            self.config.management_fee_bps,
+            elapsed,
+            self.base_fee_remainder,
+        )?;
+        let (quote_fee, quote_fee_remainder) = math::calculate_management_fee(
+            net_quote_amount,
+            self.config.management_fee_bps,
+            elapsed,
+            self.quote_fee_remainder,
+        )?;
+        let (fee_shares, share_fee_remainder) = math::calculate_management_fee(
+            self.total_shares
+                .checked_sub(self.fee_shares)
+                .ok_or(ChamberError::MathOverflow)?,
+            self.config.management_fee_bps,
+            elapsed,
+            self.share_fee_remainder,
+        )?;
+
+        self.accrued_base_fee = self
+            .accrued_base_fee
+            .checked_add(base_fee.min(net_base_amount))
+            .ok_or(ChamberError::MathOverflow)?;
+        self.accrued_quote_fee = self
+            .accrued_quote_fee
+            .checked_add(quote_fee.min(net_quote_amount))
+            .ok_or(ChamberError::MathOverflow)?;
+        self.base_fee_remainder = base_fee_remainder;
+        self.quote_fee_remainder = quote_fee_remainder;
+        self.total_shares = self
+            .total_shares
+            .checked_add(fee_shares)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.fee_shares = self
+            .fee_shares
+            .checked_add(fee_shares)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.share_fee_remainder = share_fee_remainder;
+        self.last_fee_accrual_ts = timestamp;
+
+        Ok(())
+    }
+
+    /// Take accrued management fee out of users idle balances.
+    ///
+    /// Returns collected base and quote fees, which must be transferred to treasury.
+    pub fn collect_management_fee(&mut self) -> Result<(u64, u64), ChamberError> {
+        let base_fee = self.accrued_base_fee;
+        let quote_fee = self.accrued_quote_fee;
+
+        self.total_base_amount = self
+            .total_base_amount
+            .checked_sub(base_fee)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.total_quote_amount = self
+            .total_quote_amount
+            .checked_sub(quote_fee)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.accrued_base_fee = 0;
+        self.accrued_quote_fee = 0;
+
+        Ok((base_fee, quote_fee))
+    }
+
+    /// Burn `fee_shares`, whose value `quote_amount` was kept out of strategy in quote token.
+    pub fn settle_fee_shares(&mut self, quote_amount: u64) -> Result<(), ChamberError> {
+        self.total_shares = self
+            .total_shares
+            .checked_sub(self.fee_shares)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.fee_shares = 0;
+
+        self.charge_quote_fee(quote_amount)
+    }
+
+    /// Add quote fee held in chamber token account to the fee collected to treasury.
+    pub fn charge_quote_fee(&mut self, amount: u64) -> Result<(), ChamberError> {
+        self.total_quote_amount = self
+            .total_quote_amount
+            .checked_add(amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.accrued_quote_fee = self
+            .accrued_quote_fee
+            .checked_add(amount)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Base amount of users idle balances without accrued management fee.
+    pub fn net_base_amount(&self) -> Result<u64, ChamberError> {
+        self.total_base_amount
+            .checked_sub(self.accrued_base_fee)
+            .ok_or(ChamberError::MathOverflow)
+    }
+
+    /// Quote amount of users idle balances without accrued management fee.
+    pub fn net_quote_amount(&self) -> Result<u64, ChamberError> {
+        self.total_quote_amount
+            .checked_sub(self.accrued_quote_fee)
+            .ok_or(ChamberError::MathOverflow)
+    }
+
+    /// Base amount of idle balance holding `units`.
+    pub fn base_units_amount(&self, units: u64) -> Result<u64, ChamberError> {
+        math::calculate_units_amount(units, self.total_base_units, self.net_base_amount()?)
+    }
+
+    /// Quote amount of idle balance holding `units`.
+    pub fn quote_units_amount(&self, units: u64) -> Result<u64, ChamberError> {
+        math::calculate_units_amount(units, self.total_quote_units, self.net_quote_amount()?)
+    }
+
+    /// Add base `amount` to users idle balances, returns units issued for it.
+    pub fn deposit_base(&mut self, amount: u64) -> Result<u64, ChamberError> {
+        let units = math::calculate_units(amount, self.total_base_units, self.net_base_amount()?)?;
+
+        self.total_base_amount = self
+            .total_base_amount
+            .checked_add(amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.total_base_units = self
+            .total_base_units
+            .checked_add(units)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        Ok(units)
+    }
+
+    /// Add quote `amount` to users idle balances, returns units issued for it.
+    pub fn deposit_quote(&mut self, amount: u64) -> Result<u64, ChamberError> {
+        let units =
+            math::calculate_units(amount, self.total_quote_units, self.net_quote_amount()?)?;
+
+        self.total_quote_amount = self
+            .total_quote_amount
+            .checked_add(amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.total_quote_units = self
+            .total_quote_units
+            .checked_add(units)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        Ok(units)
+    }
+
+    /// Take base `amount` from users idle balances, returns units burned for it.
+    pub fn withdraw_base(&mut self, amount: u64) -> Result<u64, ChamberError> {
+        let units =
+            math::calculate_units_ceil(amount, self.total_base_units, self.net_base_amount()?)?;
+
+        self.total_base_amount = self
+            .total_base_amount
+            .checked_sub(amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.total_base_units = self
+            .total_base_units
+            .checked_sub(units)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        Ok(units)
+    }
+
+    /// Take quote `amount` from users idle balances, returns units burned for it.
+    pub fn withdraw_quote(&mut self, amount: u64) -> Result<u64, ChamberError> {
+        let units =
+            math::calculate_units_ceil(amount, self.total_quote_units, self.net_quote_amount()?)?;
+
+        self.total_quote_amount = self
+            .total_quote_amount
+            .checked_sub(amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.total_quote_units = self
+            .total_quote_units
+            .checked_sub(units)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        Ok(units)
     }
 }
@@ -0,0 +1,69 @@
+//! Module define configurable `Chamber` parameters.
+
+use crate::{math, utils, ChamberError};
+use anchor_lang::prelude::*;
+use std::result::Result;
+
+#[zero_copy]
+#[derive(Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct ChamberConfig {
+    /// Annualized management fee in basis points.
+    pub management_fee_bps: u16,
+
+    /// Fee taken from user deposits in basis points.
+    pub entry_fee_bps: u16,
+
+    /// Fee taken from user withdrawals in basis points.
+    pub exit_fee_bps: u16,
+
+    /// Allowed drift of net base exposure in basis points of base liquidity.
+    pub rebalance_threshold_bps: u16,
+
+    /// Obligation loan to value ratio in basis points, which allows to deleverage.
+    pub deleverage_ltv_bps: u16,
+
+    /// Share of single-sided deposit value held in base token, in basis points.
+    pub base_split_bps: u16,
+
+    /// Minimum time between withdrawal request and claim in seconds.
+    pub withdrawal_cooldown: u32,
+
+    /// Aggregator program allowed for `SwapChamber`, default key disables swaps.
+    /// Must be one of `utils::is_swap_program`.
+    pub swap_program: Pubkey,
+}
+
+impl ChamberConfig {
+    pub const LEN: usize = 2 + 2 + 2 + 2 + 2 + 2 + 4 + 32;
+
+    /// Hard cap for annualized management fee (5%).
+    pub const MAX_MANAGEMENT_FEE_BPS: u16 = 500;
+
+    /// Hard cap for entry fee (1%).
+    pub const MAX_ENTRY_FEE_BPS: u16 = 100;
+
+    /// Hard cap for exit fee (1%).
+    pub const MAX_EXIT_FEE_BPS: u16 = 100;
+
+    pub fn validate(&self) -> Result<(), ChamberError> {
+        if self.management_fee_bps > Self::MAX_MANAGEMENT_FEE_BPS
+            || self.entry_fee_bps > Self::MAX_ENTRY_FEE_BPS
+            || self.exit_fee_bps > Self::MAX_EXIT_FEE_BPS
+        {
+            return Err(ChamberError::InvalidFeeRate);
+        }
+
+        if self.rebalance_threshold_bps as u64 > math::BPS_DENOMINATOR
+            || self.deleverage_ltv_bps as u64 > math::BPS_DENOMINATOR
+            || self.base_split_bps as u64 > math::BPS_DENOMINATOR
+        {
+            return Err(ChamberError::InvalidConfig);
+        }
+
+        if self.swap_program != Pubkey::default() && !utils::is_swap_program(&self.swap_program) {
+            return Err(ChamberError::InvalidSwapProgram);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+//! Module define batch of queued deposits and withdrawals.
+
+use crate::ChamberError;
+use anchor_lang::prelude::*;
+use std::result::Result;
+
+#[account]
+pub struct ChamberEpoch {
+    pub chamber: Pubkey,
+
+    /// Sequential epoch number, matches `Chamber::epoch` while collecting requests.
+    pub index: u64,
+
+    /// Base amount queued for deposit.
+    pub pending_base_amount: u64,
+
+    /// Quote amount queued for deposit.
+    pub pending_quote_amount: u64,
+
+    /// Shares queued for withdrawal.
+    pub pending_withdraw_shares: u64,
+
+    /// Base price used to process epoch, scaled `Decimal`.
+    pub base_price: u128,
+
+    /// Quote price used to process epoch, scaled `Decimal`.
+    pub quote_price: u128,
+
+    /// Value of queued deposits at epoch prices, scaled `Decimal`.
+    pub deposit_value: u128,
+
+    /// Shares minted for queued deposits.
+    pub minted_shares: u64,
+
+    /// Quote amount reserved for queued withdrawals.
+    pub withdraw_quote_amount: u64,
+
+    pub processed: bool,
+    pub bump: u8,
+}
+
+impl ChamberEpoch {
+    pub const LEN: usize = 8
+        + 32 // chamber
+        + 8 // index
+        + 8 * 3 // pending amounts and shares
+        + 16 * 3 // prices and deposit value
+        + 8 * 2 // minted shares and withdraw amount
+        + 1 // processed
+        + 1; // bump
+
+    pub fn init(&mut self, chamber: &Pubkey, index: u64, bump: u8) {
+        self.chamber = chamber.clone();
+        self.index = index;
+        self.pending_base_amount = 0;
+        self.pending_quote_amount = 0;
+        self.pending_withdraw_shares = 0;
+        self.base_price = 0;
+        self.quote_price = 0;
+        self.deposit_value = 0;
+        self.minted_shares = 0;
+        self.withdraw_quote_amount = 0;
+        self.processed = false;
+        self.bump = bump;
+    }
+
+    pub fn queue_deposit(
+        &mut self,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<(), ChamberError> {
+        self.pending_base_amount = self
+            .pending_base_amount
+            .checked_add(base_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.pending_quote_amount = self
+            .pending_quote_amount
+            .checked_add(quote_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn queue_withdrawal(&mut self, shares: u64) -> Result<(), ChamberError> {
+        Ok(self.pending_withdraw_shares = self
+            .pending_withdraw_shares
+            .checked_add(shares)
+            .ok_or(ChamberError::MathOverflow)?)
+    }
+
+    pub fn cancel_withdrawal(&mut self, shares: u64) -> Result<(), ChamberError> {
+        Ok(self.pending_withdraw_shares = self
+            .pending_withdraw_shares
+            .checked_sub(shares)
+            .ok_or(ChamberError::MathOverflow)?)
+    }
+}
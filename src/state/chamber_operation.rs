@@ -0,0 +1,31 @@
+//! Module define `Chamber` settlement flow steps.
+
+use anchor_lang::prelude::*;
+
+/// Step of the `Chamber` deploy flow. Borrowing instructions move it to `Borrowed`,
+/// `CrankChamber` advances it one step at a time until `Staked`.
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub enum ChamberOperation {
+    Idle,
+    Borrowed,
+    Swapped,
+    LiquidityAdded,
+    Staked,
+}
+
+impl ChamberOperation {
+    /// Whether no deploy flow is in progress.
+    pub fn is_settled(&self) -> bool {
+        matches!(self, ChamberOperation::Idle | ChamberOperation::Staked)
+    }
+
+    /// Next step of the deploy flow, if any.
+    pub fn next(&self) -> Option<ChamberOperation> {
+        match self {
+            ChamberOperation::Borrowed => Some(ChamberOperation::Swapped),
+            ChamberOperation::Swapped => Some(ChamberOperation::LiquidityAdded),
+            ChamberOperation::LiquidityAdded => Some(ChamberOperation::Staked),
+            ChamberOperation::Idle | ChamberOperation::Staked => None,
+        }
+    }
+}
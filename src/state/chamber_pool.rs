@@ -0,0 +1,24 @@
+//! Module define AMM pool accounts of `Chamber` strategy.
+
+use anchor_lang::prelude::*;
+
+/// AMM pool liquidity is provided to, set by admin and used to validate pool accounts
+/// passed by clients.
+#[zero_copy]
+#[derive(Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct ChamberPool {
+    /// Raydium AMM or Orca swap account.
+    pub amm_id: Pubkey,
+
+    /// Pool token account holding base reserve.
+    pub coin_token_account: Pubkey,
+
+    /// Pool token account holding quote reserve.
+    pub pc_token_account: Pubkey,
+
+    pub lp_mint: Pubkey,
+}
+
+impl ChamberPool {
+    pub const LEN: usize = 32 * 4;
+}
@@ -0,0 +1,63 @@
+//! Module define account layouts of the first program release, which are upgraded in place
+//! by `MigrateLegacyChamber` and `MigrateUserPosition`.
+
+use super::{PositionStatus, UserPosition};
+use anchor_lang::prelude::*;
+
+/// `Chamber` of the first release, derived without `index` seed and created without admin.
+#[derive(AnchorDeserialize)]
+pub struct LegacyChamber {
+    pub leveraged_farm: Pubkey,
+    pub authority: Pubkey,
+    pub base_ata: Pubkey,
+    pub quote_ata: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+
+    /// `ProtocolType` encoded by Borsh.
+    pub protocol_type: u8,
+    pub bump: u8,
+    pub authority_bump: u8,
+}
+
+impl LegacyChamber {
+    pub const LEN: usize = 8 + (32 + 32 + 32 + 32 + 32 + 32 + 1 + 1 + 1);
+}
+
+/// `UserPosition` of the first release, holding plain token amounts.
+#[derive(AnchorDeserialize)]
+pub struct LegacyUserPosition {
+    pub owner: Pubkey,
+    pub chamber: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub bump: u8,
+}
+
+impl LegacyUserPosition {
+    pub const LEN: usize = 8 + (32 + 32 + 8 + 8 + 1);
+
+    /// Position of the current layout, amounts become units issued 1:1, as
+    /// `MigrateLegacyChamber` does for chamber totals.
+    pub fn upgrade(&self) -> UserPosition {
+        UserPosition {
+            owner: self.owner,
+            chamber: self.chamber,
+            base_amount: self.base_amount,
+            quote_amount: self.quote_amount,
+            shares: 0,
+            queued_epoch: 0,
+            queued_base_amount: 0,
+            queued_quote_amount: 0,
+            withdrawal_epoch: 0,
+            withdrawal_shares: 0,
+            withdrawal_requested_ts: 0,
+            status: PositionStatus::Pending,
+            last_deposit_ts: 0,
+            last_withdraw_ts: 0,
+            bump: self.bump,
+            version: UserPosition::VERSION,
+            reserved: [0; UserPosition::RESERVED_LEN],
+        }
+    }
+}
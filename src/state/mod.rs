@@ -1,7 +1,29 @@
+mod amm_type;
 mod chamber;
+mod chamber_config;
+mod chamber_epoch;
+mod chamber_operation;
+mod chamber_pool;
+mod legacy;
+mod position_status;
 mod protocol_type;
+mod strategy_leg;
+mod token_side;
+mod tulip_nav;
+mod tulip_obligation;
 mod user_position;
 
+pub use amm_type::*;
 pub use chamber::*;
+pub use chamber_config::*;
+pub use chamber_epoch::*;
+pub use chamber_operation::*;
+pub use chamber_pool::*;
+pub use legacy::*;
+pub use position_status::*;
 pub use protocol_type::*;
+pub use strategy_leg::*;
+pub use token_side::*;
+pub use tulip_nav::*;
+pub use tulip_obligation::*;
 pub use user_position::*;
@@ -0,0 +1,31 @@
+//! Module define `UserPosition` lifecycle states.
+
+use anchor_lang::prelude::*;
+
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub enum PositionStatus {
+    /// Funds are idle or queued, nothing deployed yet.
+    Pending,
+
+    /// Funds are deployed into `Chamber` strategy.
+    Deployed,
+
+    /// Shares are locked by withdrawal request.
+    WithdrawRequested,
+
+    /// Everything is withdrawn, position can't be used anymore.
+    Closed,
+}
+
+impl PositionStatus {
+    pub fn can_transition(&self, to: PositionStatus) -> bool {
+        use PositionStatus::*;
+
+        match (self, to) {
+            (Pending, Pending | Deployed | Closed) => true,
+            (Deployed, Deployed | WithdrawRequested) => true,
+            (WithdrawRequested, WithdrawRequested | Deployed | Closed) => true,
+            _ => false,
+        }
+    }
+}
@@ -2,8 +2,9 @@
 
 use anchor_lang::prelude::*;
 
-#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
 pub enum ProtocolType {
     Tulip,
     Francium,
+    Solend,
 }
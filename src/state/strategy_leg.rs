@@ -0,0 +1,71 @@
+//! Module define accounting for single strategy obligation.
+
+use crate::ChamberError;
+use anchor_lang::prelude::*;
+use std::result::Result;
+
+#[zero_copy]
+#[derive(Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct StrategyLeg {
+    /// Base amount supplied as collateral.
+    pub base_collateral: u64,
+
+    /// Quote amount supplied as collateral.
+    pub quote_collateral: u64,
+
+    /// Base amount borrowed.
+    pub base_debt: u64,
+
+    /// Quote amount borrowed.
+    pub quote_debt: u64,
+}
+
+impl StrategyLeg {
+    pub const LEN: usize = 8 + 8 + 8 + 8;
+
+    pub fn deposit_borrow(
+        &mut self,
+        base_amount: u64,
+        quote_amount: u64,
+        base_borrow_amount: u64,
+        quote_borrow_amount: u64,
+    ) -> Result<(), ChamberError> {
+        self.base_collateral = self
+            .base_collateral
+            .checked_add(base_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.quote_collateral = self
+            .quote_collateral
+            .checked_add(quote_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.base_debt = self
+            .base_debt
+            .checked_add(base_borrow_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.quote_debt = self
+            .quote_debt
+            .checked_add(quote_borrow_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Reduce leg collateral and debt by `percent` of unwound liquidity.
+    pub fn unwind(&mut self, percent: u8) -> Result<(), ChamberError> {
+        let unwind = |amount: u64| -> Result<u64, ChamberError> {
+            let unwound = (amount as u128)
+                .checked_mul(percent as u128)
+                .ok_or(ChamberError::MathOverflow)?
+                / 100;
+
+            Ok(amount - unwound as u64)
+        };
+
+        self.base_collateral = unwind(self.base_collateral)?;
+        self.quote_collateral = unwind(self.quote_collateral)?;
+        self.base_debt = unwind(self.base_debt)?;
+        self.quote_debt = unwind(self.quote_debt)?;
+
+        Ok(())
+    }
+}
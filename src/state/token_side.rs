@@ -0,0 +1,9 @@
+//! Module define `Chamber` token sides.
+
+use anchor_lang::prelude::*;
+
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub enum TokenSide {
+    Base,
+    Quote,
+}
@@ -0,0 +1,104 @@
+//! Module price shares of Tulip strategy chamber.
+
+use super::{Chamber, ChamberEpoch, TulipObligation};
+use crate::{math, ChamberError};
+use anchor_lang::prelude::*;
+use tulipv2_sdk_common::{
+    math::{common::TryAdd, decimal::Decimal},
+    pyth,
+};
+
+/// Net asset value of chamber shareholders, shared by every instruction which prices shares.
+#[derive(Debug, Clone)]
+pub struct TulipNav {
+    /// Obligations equity and idle funds value.
+    pub nav: Decimal,
+
+    pub base_price: Decimal,
+    pub quote_price: Decimal,
+
+    /// Base amount in chamber token account, which doesn't belong to idle balances with
+    /// accrued fees or queued deposits.
+    pub idle_base_amount: u64,
+
+    /// Quote amount in chamber token account, which doesn't belong to idle balances with
+    /// accrued fees, queued deposits or claims.
+    pub idle_quote_amount: u64,
+}
+
+impl TulipNav {
+    /// Load NAV from accounts expected by Tulip at indexes of `deposit_borrow` instruction:
+    /// chamber farm `0`, obligations `2` and `3`, base and quote oracles `8` and `9`.
+    ///
+    /// Every account must be the chamber one, so clients can't forge share price.
+    pub fn load(
+        chamber: &Chamber,
+        epoch: &ChamberEpoch,
+        remaining_accounts: &[AccountInfo],
+        base_ata_amount: u64,
+        quote_ata_amount: u64,
+    ) -> Result<Self> {
+        let (base_price, quote_price) = Self::load_prices(chamber, remaining_accounts)?;
+
+        let chamber_farm = remaining_accounts[0].key();
+        let obligation_0 = TulipObligation::load_checked(
+            &remaining_accounts[2],
+            &chamber.authority,
+            &chamber_farm,
+            0,
+        )?;
+        let obligation_1 = TulipObligation::load_checked(
+            &remaining_accounts[3],
+            &chamber.authority,
+            &chamber_farm,
+            1,
+        )?;
+
+        let idle_base_amount = base_ata_amount
+            .saturating_sub(chamber.total_base_amount)
+            .saturating_sub(epoch.pending_base_amount);
+        let idle_quote_amount = quote_ata_amount
+            .saturating_sub(chamber.total_quote_amount)
+            .saturating_sub(epoch.pending_quote_amount)
+            .saturating_sub(chamber.claimable_quote_amount);
+
+        let nav = obligation_0
+            .equity_value()?
+            .try_add(obligation_1.equity_value()?)?
+            .try_add(math::calculate_value(
+                idle_base_amount,
+                base_price,
+                chamber.base_decimals,
+            )?)?
+            .try_add(math::calculate_value(
+                idle_quote_amount,
+                quote_price,
+                chamber.quote_decimals,
+            )?)?;
+
+        Ok(TulipNav {
+            nav,
+            base_price,
+            quote_price,
+            idle_base_amount,
+            idle_quote_amount,
+        })
+    }
+
+    /// Load base and quote prices from chamber oracles at indexes `8` and `9`.
+    pub fn load_prices(
+        chamber: &Chamber,
+        remaining_accounts: &[AccountInfo],
+    ) -> Result<(Decimal, Decimal)> {
+        if remaining_accounts[8].key() != chamber.base_oracle
+            || remaining_accounts[9].key() != chamber.quote_oracle
+        {
+            return Err(ChamberError::InvalidOracle.into());
+        }
+
+        Ok((
+            pyth::load_pyth_price(remaining_accounts[8].data.borrow().as_ref())?,
+            pyth::load_pyth_price(remaining_accounts[9].data.borrow().as_ref())?,
+        ))
+    }
+}
@@ -0,0 +1,94 @@
+//! Module decode Tulip levfarm obligation state.
+
+use crate::{math, ChamberError};
+use anchor_lang::prelude::*;
+use tulipv2_sdk_common::math::{
+    common::{TryDiv, TryMul, TrySub},
+    decimal::Decimal,
+};
+use tulipv2_sdk_levfarm::accounts::{
+    derivations::derive_user_farm_obligation_address, obligation_account::ObligationAccount,
+};
+
+/// Read-only view over Tulip levfarm obligation, decoded with SDK `ObligationAccount`.
+#[derive(Debug, Clone)]
+pub struct TulipObligation {
+    /// Market value of obligation collateral.
+    pub deposited_value: Decimal,
+
+    /// Market value of obligation debt.
+    pub borrowed_value: Decimal,
+
+    /// Debt value which can be borrowed against collateral.
+    pub allowed_borrow_value: Decimal,
+
+    /// Debt value at which obligation becomes liquidatable.
+    pub unhealthy_borrow_value: Decimal,
+}
+
+impl TulipObligation {
+    fn load(account: &AccountInfo) -> Result<Self> {
+        Self::unpack(account.data.borrow().as_ref())
+    }
+
+    /// Load obligation `index` of `chamber_farm` opened by chamber `authority`.
+    ///
+    /// Account must be owned by levfarm program and match derived obligation address,
+    /// so clients can't pass forged obligation values.
+    pub fn load_checked(
+        account: &AccountInfo,
+        authority: &Pubkey,
+        chamber_farm: &Pubkey,
+        index: u8,
+    ) -> Result<Self> {
+        if account.owner != &tulipv2_sdk_levfarm::ID {
+            return Err(ChamberError::InvalidObligation.into());
+        }
+
+        let (obligation, _) = derive_user_farm_obligation_address(
+            *authority,
+            *chamber_farm,
+            tulipv2_sdk_levfarm::ID,
+            index,
+        );
+        if account.key() != obligation {
+            return Err(ChamberError::InvalidObligation.into());
+        }
+
+        Self::load(account)
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self> {
+        let obligation = ObligationAccount::try_deserialize(&mut &data[..])
+            .map_err(|_| ChamberError::InvalidObligation)?;
+
+        Ok(TulipObligation {
+            deposited_value: obligation.deposited_value,
+            borrowed_value: obligation.borrowed_value,
+            allowed_borrow_value: obligation.allowed_borrow_value,
+            unhealthy_borrow_value: obligation.unhealthy_borrow_value,
+        })
+    }
+
+    /// Collateral value net of debt.
+    pub fn equity_value(&self) -> Result<Decimal> {
+        if self.borrowed_value > self.deposited_value {
+            return Ok(Decimal::zero());
+        }
+
+        Ok(self.deposited_value.try_sub(self.borrowed_value)?)
+    }
+
+    /// Current loan to value ratio in basis points.
+    pub fn ltv_bps(&self) -> Result<u64> {
+        if self.deposited_value == Decimal::zero() {
+            return Ok(0);
+        }
+
+        Ok(self
+            .borrowed_value
+            .try_mul(math::BPS_DENOMINATOR)?
+            .try_div(self.deposited_value)?
+            .try_floor_u64()?)
+    }
+}
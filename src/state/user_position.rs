@@ -1,6 +1,7 @@
 //! Module define representation of user position.
 
-use crate::ChamberError;
+use super::{Chamber, PositionStatus};
+use crate::{math, ChamberError};
 use anchor_lang::prelude::*;
 use std::result::Result;
 
@@ -8,47 +9,268 @@ use std::result::Result;
 pub struct UserPosition {
     pub owner: Pubkey,
     pub chamber: Pubkey,
+
+    /// Units of idle base balance, see `Chamber::base_units_amount()`.
     pub base_amount: u64,
+
+    /// Units of idle quote balance, see `Chamber::quote_units_amount()`.
     pub quote_amount: u64,
+
+    /// Shares of deployed strategy.
+    pub shares: u64,
+
+    /// `ChamberEpoch` holding queued deposit.
+    pub queued_epoch: u64,
+    pub queued_base_amount: u64,
+    pub queued_quote_amount: u64,
+
+    /// `ChamberEpoch` holding withdrawal request.
+    pub withdrawal_epoch: u64,
+
+    /// Shares locked by withdrawal request.
+    pub withdrawal_shares: u64,
+
+    /// Unix timestamp of withdrawal request.
+    pub withdrawal_requested_ts: i64,
+
+    pub status: PositionStatus,
+
+    /// Unix timestamp of the last deposit.
+    pub last_deposit_ts: i64,
+
+    /// Unix timestamp of the last withdrawal.
+    pub last_withdraw_ts: i64,
+
     pub bump: u8,
+
+    /// Layout version, accounts created before versioning are upgraded by `MigrateUserPosition`.
+    pub version: u8,
+
+    /// Space for new fields, so they don't change account size.
+    pub reserved: [u8; 32],
 }
 
 impl UserPosition {
-    pub const LEN: usize = 8 + (32 + 32 + 8 + 8 + 1);
+    pub const VERSION: u8 = 1;
+    pub const RESERVED_LEN: usize = 32;
+
+    pub const LEN: usize = Self::V0_LEN + 1 + Self::RESERVED_LEN;
 
-    pub fn init(&mut self, owner: &Pubkey, chamber: &Pubkey, bump: u8) {
+    /// Size of layout without `version` and `reserved` fields.
+    pub const V0_LEN: usize = 8 + (32 + 32 + 8 + 8 + 8 + 8 * 3 + 8 * 3 + 1 + 8 * 2 + 1);
+
+    pub fn init(&mut self, owner: &Pubkey, chamber: &Pubkey, timestamp: i64, bump: u8) {
         self.owner = owner.clone();
         self.chamber = chamber.clone();
         self.base_amount = 0;
         self.quote_amount = 0;
+        self.shares = 0;
+        self.queued_epoch = 0;
+        self.queued_base_amount = 0;
+        self.queued_quote_amount = 0;
+        self.withdrawal_epoch = 0;
+        self.withdrawal_shares = 0;
+        self.withdrawal_requested_ts = 0;
+        self.status = PositionStatus::Pending;
+        self.last_deposit_ts = timestamp;
+        self.last_withdraw_ts = 0;
         self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; Self::RESERVED_LEN];
     }
 
-    pub fn deposit_base(&mut self, amount: u64) -> Result<(), ChamberError> {
+    /// Upgrade state decoded from previous layout to `UserPosition::VERSION`.
+    pub fn migrate(&mut self) -> Result<(), ChamberError> {
+        if self.version >= Self::VERSION {
+            return Err(ChamberError::AlreadyMigrated);
+        }
+
+        // Version 0 -> 1: `version` and `reserved` are appended, zeroed by realloc
+        self.version = Self::VERSION;
+
+        Ok(())
+    }
+
+    /// Move position to `status`, rejecting transitions not allowed by lifecycle.
+    pub fn transition(&mut self, status: PositionStatus) -> Result<(), ChamberError> {
+        if !self.status.can_transition(status) {
+            return Err(ChamberError::InvalidPositionStatus);
+        }
+
+        Ok(self.status = status)
+    }
+
+    /// Whether position holds no idle, queued or deployed funds.
+    pub fn is_empty(&self) -> bool {
+        self.base_amount == 0
+            && self.quote_amount == 0
+            && self.shares == 0
+            && !self.has_queued_deposit()
+            && !self.has_withdrawal_request()
+    }
+
+    pub fn deposit_base(&mut self, units: u64) -> Result<(), ChamberError> {
         Ok(self.base_amount = self
             .base_amount
-            .checked_add(amount)
+            .checked_add(units)
             .ok_or(ChamberError::MathOverflow)?)
     }
 
-    pub fn withdraw_base(&mut self, amount: u64) -> Result<(), ChamberError> {
+    pub fn withdraw_base(&mut self, units: u64) -> Result<(), ChamberError> {
         Ok(self.base_amount = self
             .base_amount
-            .checked_sub(amount)
+            .checked_sub(units)
             .ok_or(ChamberError::MathOverflow)?)
     }
 
-    pub fn deposit_quote(&mut self, amount: u64) -> Result<(), ChamberError> {
+    pub fn deposit_quote(&mut self, units: u64) -> Result<(), ChamberError> {
         Ok(self.quote_amount = self
             .quote_amount
-            .checked_add(amount)
+            .checked_add(units)
             .ok_or(ChamberError::MathOverflow)?)
     }
 
-    pub fn withdraw_quote(&mut self, amount: u64) -> Result<(), ChamberError> {
+    pub fn withdraw_quote(&mut self, units: u64) -> Result<(), ChamberError> {
         Ok(self.quote_amount = self
             .quote_amount
-            .checked_sub(amount)
+            .checked_sub(units)
             .ok_or(ChamberError::MathOverflow)?)
     }
+
+    /// Take `base_amount` and `quote_amount` out of idle balance and `chamber` totals.
+    ///
+    /// Returns base and quote exit fees, which are part of withdrawn amounts and must be
+    /// transferred to treasury. Position without deployed funds is closed once emptied.
+    pub fn withdraw_idle(
+        &mut self,
+        chamber: &mut Chamber,
+        base_amount: u64,
+        quote_amount: u64,
+        timestamp: i64,
+    ) -> Result<(u64, u64), ChamberError> {
+        if base_amount > chamber.base_units_amount(self.base_amount)?
+            || quote_amount > chamber.quote_units_amount(self.quote_amount)?
+        {
+            return Err(ChamberError::InsufficientUserPositionFunds);
+        }
+
+        let base_fee = math::calculate_fee(base_amount, chamber.config.exit_fee_bps)?;
+        let quote_fee = math::calculate_fee(quote_amount, chamber.config.exit_fee_bps)?;
+
+        let base_units = chamber.withdraw_base(base_amount)?;
+        self.withdraw_base(base_units)?;
+        let quote_units = chamber.withdraw_quote(quote_amount)?;
+        self.withdraw_quote(quote_units)?;
+
+        let status = if self.status == PositionStatus::Pending && self.is_empty() {
+            PositionStatus::Closed
+        } else {
+            self.status
+        };
+        self.transition(status)?;
+        self.last_withdraw_ts = timestamp;
+
+        Ok((base_fee, quote_fee))
+    }
+
+    pub fn has_queued_deposit(&self) -> bool {
+        self.queued_base_amount > 0 || self.queued_quote_amount > 0
+    }
+
+    pub fn has_withdrawal_request(&self) -> bool {
+        self.withdrawal_shares > 0
+    }
+
+    /// Move idle balance `units` worth `base_amount` and `quote_amount` into `epoch` deposit queue.
+    pub fn queue_deposit(
+        &mut self,
+        epoch: u64,
+        base_units: u64,
+        quote_units: u64,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<(), ChamberError> {
+        self.withdraw_base(base_units)?;
+        self.withdraw_quote(quote_units)?;
+
+        self.queued_epoch = epoch;
+        self.queued_base_amount = self
+            .queued_base_amount
+            .checked_add(base_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.queued_quote_amount = self
+            .queued_quote_amount
+            .checked_add(quote_amount)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Move idle balance `units` into strategy for `shares` minted at NAV.
+    pub fn deploy(
+        &mut self,
+        base_units: u64,
+        quote_units: u64,
+        shares: u64,
+    ) -> Result<(), ChamberError> {
+        self.withdraw_base(base_units)?;
+        self.withdraw_quote(quote_units)?;
+
+        self.shares = self
+            .shares
+            .checked_add(shares)
+            .ok_or(ChamberError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Credit shares minted for queued deposit.
+    pub fn claim_deposit(&mut self, shares: u64) -> Result<(), ChamberError> {
+        self.shares = self
+            .shares
+            .checked_add(shares)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.queued_base_amount = 0;
+        self.queued_quote_amount = 0;
+
+        Ok(())
+    }
+
+    /// Lock `shares` in `epoch` withdrawal queue.
+    pub fn request_withdrawal(
+        &mut self,
+        epoch: u64,
+        shares: u64,
+        timestamp: i64,
+    ) -> Result<(), ChamberError> {
+        self.shares = self
+            .shares
+            .checked_sub(shares)
+            .ok_or(ChamberError::InsufficientUserPositionFunds)?;
+
+        self.withdrawal_epoch = epoch;
+        self.withdrawal_shares = self
+            .withdrawal_shares
+            .checked_add(shares)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.withdrawal_requested_ts = timestamp;
+
+        Ok(())
+    }
+
+    /// Unlock shares of not processed withdrawal request.
+    pub fn cancel_withdrawal(&mut self) -> Result<(), ChamberError> {
+        self.shares = self
+            .shares
+            .checked_add(self.withdrawal_shares)
+            .ok_or(ChamberError::MathOverflow)?;
+        self.clear_withdrawal();
+
+        Ok(())
+    }
+
+    pub fn clear_withdrawal(&mut self) {
+        self.withdrawal_shares = 0;
+        self.withdrawal_requested_ts = 0;
+    }
 }
@@ -3,8 +3,39 @@ use anchor_lang::prelude::*;
 pub const CHAMBER_PREFIX: &str = "chamber";
 pub const CHAMBER_AUTHORITY_PREFIX: &str = "chamber_authority";
 pub const USER_POSITION_PREFIX: &str = "user_position";
+pub const NATIVE_UNWRAP_PREFIX: &str = "native_unwrap";
+pub const CHAMBER_EPOCH_PREFIX: &str = "chamber_epoch";
 
-pub fn derive_chamber_address(leveraged_farm: &Pubkey) -> (Pubkey, u8) {
+pub mod jupiter_v3_program {
+    use anchor_lang::declare_id;
+
+    declare_id!("JUP3c2Uh3WA4Ng34tw6kPd2G4C5BB21Xo36Je1s32Ph");
+}
+
+pub mod jupiter_v4_program {
+    use anchor_lang::declare_id;
+
+    declare_id!("JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB");
+}
+
+/// Programs `SwapChamber` can route through. Allowlist is fixed in program,
+/// so admin can't configure own program signed by chamber authority.
+pub fn is_swap_program(program: &Pubkey) -> bool {
+    *program == jupiter_v3_program::id()
+        || *program == jupiter_v4_program::id()
+        // Swaps rewards directly on Raydium AMM v4 pools
+        || *program == tulipv2_sdk_common::config::RAYDIUM_LIQUIDITY_V4
+}
+
+pub fn derive_chamber_address(leveraged_farm: &Pubkey, index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CHAMBER_PREFIX.as_bytes(), leveraged_farm.as_ref(), &[index]],
+        &crate::id(),
+    )
+}
+
+/// Address of chamber created by the first release, before `index` seed was added.
+pub fn derive_legacy_chamber_address(leveraged_farm: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[CHAMBER_PREFIX.as_bytes(), leveraged_farm.as_ref()],
         &crate::id(),
@@ -28,3 +59,25 @@ pub fn derive_user_position(wallet: &Pubkey, chamber: &Pubkey) -> (Pubkey, u8) {
         &crate::id(),
     )
 }
+
+pub fn derive_native_unwrap_account(chamber: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            NATIVE_UNWRAP_PREFIX.as_bytes(),
+            chamber.as_ref(),
+            wallet.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn derive_chamber_epoch(chamber: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CHAMBER_EPOCH_PREFIX.as_bytes(),
+            chamber.as_ref(),
+            &index.to_le_bytes(),
+        ],
+        &crate::id(),
+    )
+}
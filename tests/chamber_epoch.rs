@@ -0,0 +1,332 @@
+mod test_state;
+mod test_utils;
+
+use cetra_chamber::{
+    error::ChamberError,
+    math::{calculate_shares, calculate_shares_value},
+};
+use cetra_chamber_client::instruction;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use test_state::*;
+use test_utils::*;
+use tulipv2_sdk_common::math::decimal::Decimal;
+
+#[test]
+fn shares_are_priced_at_nav() {
+    // First deposit mints shares 1:1 with value
+    assert_eq!(
+        calculate_shares(Decimal::from(100u64), 0, Decimal::zero()).unwrap(),
+        100000000
+    );
+
+    // NAV doubled, so the same value mints half of the shares
+    assert_eq!(
+        calculate_shares(Decimal::from(100u64), 100000000, Decimal::from(200u64)).unwrap(),
+        50000000
+    );
+
+    assert_eq!(
+        calculate_shares_value(50000000, 150000000, Decimal::from(300u64))
+            .unwrap()
+            .try_floor_u64()
+            .unwrap(),
+        100
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+
+    let pyth_price_account = test_context
+        .get_account(&test_chamber_tulip.farm_config.coin_price_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let pyth_price =
+        tulipv2_sdk_common::pyth::load::<tulipv2_sdk_common::pyth::Price>(&pyth_price_account.data)
+            .unwrap();
+
+    test_context
+        .context
+        .warp_to_slot(pyth_price.valid_slot)
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let first_user = TestUser::new();
+    let second_user = TestUser::new();
+
+    for (test_user, multiplier) in [(&first_user, 1), (&second_user, 2)] {
+        test_user
+            .fund(&mut test_context, &payer, 1000000000)
+            .await
+            .unwrap();
+
+        test_user
+            .create_ata(
+                &mut test_context,
+                &test_utils::wsol_mint::id(),
+                1250000000 * multiplier,
+            )
+            .await
+            .unwrap();
+
+        test_user
+            .create_ata(
+                &mut test_context,
+                &test_utils::usdc_mint::id(),
+                50000000 * multiplier,
+            )
+            .await
+            .unwrap();
+
+        test_user
+            .initialize_user_position(
+                &mut test_context,
+                &test_chamber_tulip,
+                1250000000 * multiplier,
+                50000000 * multiplier,
+            )
+            .await
+            .unwrap();
+
+        test_user
+            .queue_deposit(
+                &mut test_context,
+                &test_chamber_tulip,
+                1250000000 * multiplier,
+                50000000 * multiplier,
+            )
+            .await
+            .unwrap();
+    }
+
+    let epoch = test_chamber_tulip
+        .load_epoch(&mut test_context, 0)
+        .await
+        .unwrap();
+    assert_eq!(epoch.pending_base_amount, 3750000000);
+    assert_eq!(epoch.pending_quote_amount, 150000000);
+
+    // Single batch for both users
+    test_chamber_tulip
+        .process_chamber_epoch(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let chamber_state = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!({ chamber_state.epoch }, 1);
+
+    let epoch = test_chamber_tulip
+        .load_epoch(&mut test_context, 0)
+        .await
+        .unwrap();
+    assert!(epoch.processed);
+    assert_eq!(epoch.minted_shares, { chamber_state.total_shares });
+
+    first_user
+        .claim_deposit(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+    second_user
+        .claim_deposit(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+
+    let first_position = first_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+    let second_position = second_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+
+    // Shares are fair within batch
+    assert!(first_position.shares > 0);
+    assert!(second_position.shares.abs_diff(first_position.shares * 2) <= 1);
+    assert!(first_position.shares + second_position.shares <= { chamber_state.total_shares });
+    assert_eq!(first_position.queued_base_amount, 0);
+    assert_eq!(second_position.queued_quote_amount, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_claim_before_processing() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 2500000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    test_user
+        .queue_deposit(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    assert!(test_user
+        .claim_deposit(&mut test_context, &test_chamber_tulip)
+        .await
+        .is_err());
+}
+
+/// Sends `ProcessChamberEpoch` signed by payer with account `forged` replaced by `account`.
+async fn process_chamber_epoch_forged(
+    test_context: &mut TestContext,
+    test_chamber_tulip: &TestChamberTulip,
+    forged: Pubkey,
+    account: Pubkey,
+) -> Option<u32> {
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut ix = instruction::process_chamber_epoch_ix(test_chamber_tulip, &payer.pubkey(), 0);
+
+    for meta in ix.accounts.iter_mut() {
+        if meta.pubkey == forged {
+            meta.pubkey = account;
+        }
+    }
+
+    transaction_error_code(
+        test_chamber_tulip
+            .process(test_context, &payer, Some(400000), ix)
+            .await,
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_forged_nav_accounts() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    // Oracles are swapped, so base would be priced as quote
+    let farm_config = &test_chamber_tulip.farm_config;
+    assert_eq!(
+        process_chamber_epoch_forged(
+            &mut test_context,
+            &test_chamber_tulip,
+            farm_config.coin_price_account,
+            farm_config.pc_price_account,
+        )
+        .await,
+        Some(ChamberError::InvalidOracle.into())
+    );
+
+    // Obligations with inflated equity
+    let forged_obligation = set_forged_obligation(&mut test_context, 1000000, 0).await;
+    for index in 0..2 {
+        assert_eq!(
+            process_chamber_epoch_forged(
+                &mut test_context,
+                &test_chamber_tulip,
+                test_chamber_tulip.derive_chamber_obligation(index).0,
+                forged_obligation,
+            )
+            .await,
+            Some(ChamberError::InvalidObligation.into())
+        );
+    }
+
+    // Obligation of the other leg is derived for chamber, but doesn't match index
+    assert_eq!(
+        process_chamber_epoch_forged(
+            &mut test_context,
+            &test_chamber_tulip,
+            test_chamber_tulip.derive_chamber_obligation(0).0,
+            test_chamber_tulip.derive_chamber_obligation(1).0,
+        )
+        .await,
+        Some(ChamberError::InvalidObligation.into())
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_not_keeper() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    let result = test_chamber_tulip
+        .process_chamber_epoch(&mut test_context, &test_user.wallet)
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::InvalidKeeper.into())
+    );
+}
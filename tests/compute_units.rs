@@ -0,0 +1,150 @@
+mod test_state;
+mod test_utils;
+
+use cetra_chamber::state::ChamberOperation;
+use cetra_chamber_client::instruction;
+use mock_levfarm::Journal;
+use solana_sdk::{account::Account, signer::Signer};
+use test_state::*;
+use test_utils::*;
+
+/// Budgets requested by clients, see `cli/src/main.rs`.
+const DEPOSIT_COMPUTE_UNITS: u32 = 350000;
+const CRANK_COMPUTE_UNITS: u32 = 530000;
+
+/// Baseline of Borsh `Chamber` flows, which client budgets above were sized for.
+/// Units weren't recorded per step before the zero-copy switch, so budgets are the baseline.
+const BORSH_DEPOSIT_COMPUTE_UNITS: u32 = DEPOSIT_COMPUTE_UNITS;
+const BORSH_CRANK_COMPUTE_UNITS: u32 = CRANK_COMPUTE_UNITS;
+
+/// Chamber against mock levfarm, so measured units are spent by chamber itself
+/// rather than by Tulip instructions.
+async fn setup() -> (TestContext, TestChamberTulip, TestUser) {
+    let mut test_context = start_mock_levfarm_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+
+    let pyth_price_account = test_context
+        .get_account(&test_chamber_tulip.farm_config.coin_price_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let pyth_price =
+        tulipv2_sdk_common::pyth::load::<tulipv2_sdk_common::pyth::Price>(&pyth_price_account.data)
+            .unwrap();
+
+    test_context
+        .context
+        .warp_to_slot(pyth_price.valid_slot)
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let (chamber_farm, _) = test_chamber_tulip.derive_chamber_farm();
+    let journal = Account {
+        lamports: test_context.get_rent().await.minimum_balance(Journal::LEN),
+        data: Journal::new(None).pack().unwrap(),
+        owner: mock_levfarm::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    test_context
+        .context
+        .set_account(&chamber_farm, &journal.into());
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 2500000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    (test_context, test_chamber_tulip, test_user)
+}
+
+/// Run with `--nocapture` to print units consumed by deposit and each settlement step.
+///
+/// Every step must save at least `COMPUTE_UNITS_STEP` units against Borsh baseline.
+#[tokio::test(flavor = "multi_thread")]
+async fn deposit_and_settle() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup().await;
+
+    let deposit_units = test_context
+        .measure_compute_units(
+            &test_user.wallet,
+            instruction::deposit_chamber_ix(
+                &test_chamber_tulip,
+                &test_user.wallet.pubkey(),
+                0,
+                2500000000,
+                100000000,
+            ),
+            DEPOSIT_COMPUTE_UNITS,
+        )
+        .await
+        .unwrap();
+    assert!(
+        deposit_units + COMPUTE_UNITS_STEP <= BORSH_DEPOSIT_COMPUTE_UNITS,
+        "DepositChamber: <= {} compute units, Borsh baseline {}",
+        deposit_units,
+        BORSH_DEPOSIT_COMPUTE_UNITS
+    );
+
+    for operation in [
+        ChamberOperation::Borrowed,
+        ChamberOperation::Swapped,
+        ChamberOperation::LiquidityAdded,
+    ] {
+        let crank_units = test_context
+            .measure_compute_units(
+                &test_user.wallet,
+                instruction::crank_chamber_ix(
+                    &test_chamber_tulip,
+                    &test_user.wallet.pubkey(),
+                    operation,
+                ),
+                CRANK_COMPUTE_UNITS,
+            )
+            .await
+            .unwrap();
+        assert!(
+            crank_units + COMPUTE_UNITS_STEP <= BORSH_CRANK_COMPUTE_UNITS,
+            "CrankChamber {:?}: <= {} compute units, Borsh baseline {}",
+            operation,
+            crank_units,
+            BORSH_CRANK_COMPUTE_UNITS
+        );
+    }
+
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!(chamber.operation().unwrap(), ChamberOperation::Staked);
+}
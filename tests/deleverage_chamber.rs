@@ -0,0 +1,193 @@
+mod test_state;
+mod test_utils;
+
+use anchor_lang::AccountSerialize;
+use cetra_chamber::{error::ChamberError, state::TulipObligation};
+use cetra_chamber_client::instruction;
+use solana_sdk::signer::Signer;
+use test_state::*;
+use test_utils::*;
+use tulipv2_sdk_common::math::decimal::Decimal;
+use tulipv2_sdk_levfarm::accounts::obligation_account::ObligationAccount;
+
+#[test]
+fn obligation_ltv() {
+    // 300$ deposited, 200$ borrowed
+    let mut data = vec![];
+    ObligationAccount {
+        deposited_value: Decimal::from(300u64),
+        borrowed_value: Decimal::from(200u64),
+        ..Default::default()
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+
+    let obligation = TulipObligation::unpack(&data).unwrap();
+    assert_eq!(obligation.ltv_bps().unwrap(), 6666);
+    assert_eq!(
+        obligation.equity_value().unwrap().try_floor_u64().unwrap(),
+        100
+    );
+
+    assert!(TulipObligation::unpack(&data[..data.len() - 1]).is_err());
+
+    // Account discriminator is checked
+    data[0] ^= 1;
+    assert!(TulipObligation::unpack(&data).is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_threshold_not_reached() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.deleverage_ltv_bps = 9000;
+
+    let pyth_price_account = test_context
+        .get_account(&test_chamber_tulip.farm_config.coin_price_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let pyth_price =
+        tulipv2_sdk_common::pyth::load::<tulipv2_sdk_common::pyth::Price>(&pyth_price_account.data)
+            .unwrap();
+
+    test_context
+        .context
+        .warp_to_slot(pyth_price.valid_slot)
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 2500000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .deposit_chamber(&mut test_context, &test_user, 2500000000, 100000000)
+        .await
+        .unwrap();
+
+    for _ in 0..3 {
+        test_chamber_tulip
+            .crank_chamber(&mut test_context, &payer)
+            .await
+            .unwrap();
+    }
+
+    // 3x leveraged legs stay far below 90% LTV
+    let result = test_chamber_tulip
+        .deleverage_chamber(&mut test_context, &payer, 50)
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::DeleverageNotRequired.into())
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_forged_obligation() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.deleverage_ltv_bps = 9000;
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    // Obligation reporting 99% LTV, owned by levfarm, but not derived for chamber
+    let forged_obligation = set_forged_obligation(&mut test_context, 100, 99).await;
+
+    let mut ix = instruction::deleverage_chamber_ix(&test_chamber_tulip, &payer.pubkey(), 50);
+    let obligation = test_chamber_tulip.derive_chamber_obligation(0).0;
+    for account in ix.accounts.iter_mut() {
+        if account.pubkey == obligation {
+            account.pubkey = forged_obligation;
+        }
+    }
+
+    let result = test_chamber_tulip
+        .process(&mut test_context, &payer, Some(530000), ix)
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::InvalidObligation.into())
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_not_keeper() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.deleverage_ltv_bps = 9000;
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    assert_ne!(test_user.wallet.pubkey(), payer.pubkey());
+
+    // Unwinding percent is chosen by caller, so only keeper may unwind liquidity
+    let result = test_chamber_tulip
+        .deleverage_chamber(&mut test_context, &test_user.wallet, 100)
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::InvalidKeeper.into())
+    );
+}
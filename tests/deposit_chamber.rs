@@ -1,26 +1,15 @@
 mod test_state;
 mod test_utils;
 
-use cetra_program_test::{solana_program_test::*, *};
+use cetra_chamber::{error::ChamberError, state::ChamberOperation};
 use test_state::*;
 use test_utils::*;
 
-#[tokio::test(flavor = "multi_thread")]
-async fn success() {
-    let rpc_account_loader = RpcAccountLoader::default();
-    let mut program_test_loader = ProgramTestLoader::default();
-
-    program_test_loader
-        .program_test
-        .add_program("cetra_chamber", cetra_chamber::id(), None);
-    program_test_loader.load().unwrap();
-
-    let mut test_context = program_test_loader
-        .start_with_context(Box::new(rpc_account_loader))
-        .await;
+async fn setup() -> (TestContext, TestChamberTulip, TestUser) {
+    let mut test_context = start_test_context().await;
 
     let payer = clone_keypair(&test_context.context.payer);
-    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium();
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
 
     let pyth_price_account = test_context
         .get_account(&test_chamber_tulip.farm_config.coin_price_account)
@@ -78,18 +67,233 @@ async fn success() {
         .await
         .unwrap();
 
+    (test_context, test_chamber_tulip, test_user)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup().await;
+
+    test_chamber_tulip
+        .deposit_chamber(&mut test_context, &test_user, 2500000000, 100000000)
+        .await
+        .unwrap();
+
+    // Deploy flow is advanced one step per crank
+    for operation in [
+        ChamberOperation::Swapped,
+        ChamberOperation::LiquidityAdded,
+        ChamberOperation::Staked,
+    ] {
+        test_chamber_tulip
+            .crank_chamber(&mut test_context, &test_user.wallet)
+            .await
+            .unwrap();
+
+        let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+        assert_eq!(chamber.operation().unwrap(), operation);
+    }
+
+    // Nothing left to crank, staked chamber has no LP outside of vault
+    let result = test_chamber_tulip
+        .crank_chamber(&mut test_context, &test_user.wallet)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_withdraw_deployed() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup().await;
+
+    test_chamber_tulip
+        .deposit_chamber(&mut test_context, &test_user, 2500000000, 100000000)
+        .await
+        .unwrap();
+
+    let user_position = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+    assert_eq!(user_position.base_amount, 0);
+    assert_eq!(user_position.quote_amount, 0);
+    assert!(user_position.shares > 0);
+
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!({ chamber.total_base_amount }, 0);
+    assert_eq!({ chamber.total_quote_amount }, 0);
+    assert_eq!({ chamber.total_shares }, user_position.shares);
+
+    // Deployed funds are not idle anymore
+    let result = test_user
+        .withdraw_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::InsufficientUserPositionFunds.into())
+    );
+
+    // Nor can be deployed once again
+    let result = test_chamber_tulip
+        .deposit_chamber(&mut test_context, &test_user, 2500000000, 100000000)
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::InsufficientUserPositionFunds.into())
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_shares_at_nav() {
+    let (mut test_context, test_chamber_tulip, first_user) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    test_chamber_tulip
+        .deposit_chamber(&mut test_context, &first_user, 2500000000, 100000000)
+        .await
+        .unwrap();
+
+    for _ in 0..3 {
+        test_chamber_tulip
+            .crank_chamber(&mut test_context, &first_user.wallet)
+            .await
+            .unwrap();
+    }
+
+    let second_user = TestUser::new();
+
+    second_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    second_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 2500000000)
+        .await
+        .unwrap();
+
+    second_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    second_user
+        .initialize_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    // Second deposit is priced at NAV of deployed strategy, which excludes idle balances
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    let first_shares = chamber.total_shares;
+
+    test_chamber_tulip
+        .deposit_chamber(&mut test_context, &second_user, 2500000000, 100000000)
+        .await
+        .unwrap();
+
+    let second_position = second_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!(
+        { chamber.total_shares },
+        first_shares + second_position.shares
+    );
+    assert!(second_position.shares > 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_restake_stranded_lp() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup().await;
+
     test_chamber_tulip
         .deposit_chamber(&mut test_context, &test_user, 2500000000, 100000000)
         .await
         .unwrap();
 
+    for _ in 0..3 {
+        test_chamber_tulip
+            .crank_chamber(&mut test_context, &test_user.wallet)
+            .await
+            .unwrap();
+    }
+
+    // LP left outside of vault, e.g. by interrupted unwinding
     test_chamber_tulip
-        .settle_chamber_position(&mut test_context, &test_user.wallet)
+        .add_lp_ata_balance(&mut test_context, 0, 1000)
         .await
         .unwrap();
 
     test_chamber_tulip
-        .settle_chamber_position2(&mut test_context, &test_user.wallet)
+        .crank_chamber(&mut test_context, &test_user.wallet)
         .await
         .unwrap();
+
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!(chamber.operation().unwrap(), ChamberOperation::Staked);
+    assert_eq!(
+        test_chamber_tulip
+            .get_token_amount(&mut test_context, test_chamber_tulip.get_lp_ata(0))
+            .await
+            .unwrap(),
+        0
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_deploy_free_idle() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    test_chamber_tulip
+        .deposit_chamber(&mut test_context, &test_user, 2500000000, 100000000)
+        .await
+        .unwrap();
+
+    for _ in 0..3 {
+        test_chamber_tulip
+            .crank_chamber(&mut test_context, &test_user.wallet)
+            .await
+            .unwrap();
+    }
+
+    // 50$ of quote, which doesn't belong to any user balance
+    test_chamber_tulip
+        .add_quote_ata_balance(&mut test_context, 50000000)
+        .await
+        .unwrap();
+
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    let quote_collateral =
+        { chamber.legs[0].quote_collateral } + { chamber.legs[1].quote_collateral };
+
+    test_chamber_tulip
+        .process_chamber_epoch(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!(chamber.operation().unwrap(), ChamberOperation::Borrowed);
+    assert_eq!(
+        { chamber.legs[0].quote_collateral } + { chamber.legs[1].quote_collateral },
+        quote_collateral + 50000000
+    );
+    assert_eq!(
+        test_chamber_tulip
+            .get_token_amount(&mut test_context, test_chamber_tulip.get_quote_ata())
+            .await
+            .unwrap(),
+        { chamber.total_quote_amount } + { chamber.claimable_quote_amount }
+    );
 }
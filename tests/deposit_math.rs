@@ -0,0 +1,183 @@
+use cetra_chamber::{
+    math::{self, TULIP_DEPOSIT_LEVERAGE},
+    state::StrategyLeg,
+};
+use proptest::prelude::*;
+use tulipv2_sdk_common::math::{
+    common::{TryAdd, TryDiv, TryMul},
+    decimal::Decimal,
+};
+
+/// Price of `units / 10^expo`, covers pyth prices from `1e-18` to `u64::MAX`.
+fn price() -> impl Strategy<Value = Decimal> {
+    (1..=u64::MAX, 0u8..=18).prop_map(|(units, expo)| {
+        Decimal::from(units)
+            .try_div(10u64.pow(expo as u32))
+            .unwrap()
+    })
+}
+
+fn deposit_value(
+    leg: &StrategyLeg,
+    base_price: Decimal,
+    quote_price: Decimal,
+    base_decimals: u8,
+    quote_decimals: u8,
+) -> Decimal {
+    math::calculate_value(leg.base_collateral, base_price, base_decimals)
+        .unwrap()
+        .try_add(math::calculate_value(leg.quote_collateral, quote_price, quote_decimals).unwrap())
+        .unwrap()
+}
+
+#[test]
+fn tulip_deposit_sol_usdc() {
+    let base_price = Decimal::from(40u64);
+    let quote_price = Decimal::from(1u64);
+
+    let legs = math::calculate_tulip_deposit(2500000000, 100000000, base_price, quote_price, 9, 6)
+        .unwrap();
+
+    // 1st leg: 25$ SOL + 25$ USDC, borrows 100$ USDC
+    assert_eq!(
+        legs[0],
+        StrategyLeg {
+            base_collateral: 625000000,
+            quote_collateral: 25000000,
+            base_debt: 0,
+            quote_debt: 100000000,
+        }
+    );
+
+    // 2nd leg: 75$ SOL + 75$ USDC, borrows 300$ SOL
+    assert_eq!(
+        legs[1],
+        StrategyLeg {
+            base_collateral: 1875000000,
+            quote_collateral: 75000000,
+            base_debt: 7500000000,
+            quote_debt: 0,
+        }
+    );
+}
+
+#[test]
+fn tulip_deposit_zero_price() {
+    assert!(
+        math::calculate_tulip_deposit(1, 1, Decimal::zero(), Decimal::from(1u64), 9, 6).is_err()
+    );
+    assert!(math::calculate_value(1, Decimal::from(1u64), 20).is_err());
+}
+
+proptest! {
+    #[test]
+    fn tulip_deposit_never_panics(
+        base_amount in any::<u64>(),
+        quote_amount in any::<u64>(),
+        base_price in prop_oneof![Just(Decimal::zero()), price()],
+        quote_price in prop_oneof![Just(Decimal::zero()), price()],
+        base_decimals in any::<u8>(),
+        quote_decimals in any::<u8>(),
+    ) {
+        let _ = math::calculate_tulip_deposit(
+            base_amount,
+            quote_amount,
+            base_price,
+            quote_price,
+            base_decimals,
+            quote_decimals,
+        );
+    }
+
+    #[test]
+    fn tulip_deposit_splits_whole_amount(
+        base_amount in any::<u64>(),
+        quote_amount in any::<u64>(),
+        base_price in price(),
+        quote_price in price(),
+        base_decimals in 0u8..=12,
+        quote_decimals in 0u8..=12,
+    ) {
+        if let Ok(legs) = math::calculate_tulip_deposit(
+            base_amount,
+            quote_amount,
+            base_price,
+            quote_price,
+            base_decimals,
+            quote_decimals,
+        ) {
+            prop_assert_eq!(legs[0].base_collateral + legs[1].base_collateral, base_amount);
+            prop_assert_eq!(legs[0].quote_collateral + legs[1].quote_collateral, quote_amount);
+            prop_assert_eq!(legs[0].base_debt, 0);
+            prop_assert_eq!(legs[1].quote_debt, 0);
+        }
+    }
+
+    #[test]
+    fn tulip_deposit_borrow_monotonic(
+        base_amounts in (any::<u64>(), any::<u64>()),
+        quote_amounts in (any::<u64>(), any::<u64>()),
+        base_price in price(),
+        quote_price in price(),
+        base_decimals in 0u8..=12,
+        quote_decimals in 0u8..=12,
+    ) {
+        let (base_low, base_high) = (base_amounts.0.min(base_amounts.1), base_amounts.0.max(base_amounts.1));
+        let (quote_low, quote_high) = (quote_amounts.0.min(quote_amounts.1), quote_amounts.0.max(quote_amounts.1));
+
+        let low = math::calculate_tulip_deposit(
+            base_low,
+            quote_low,
+            base_price,
+            quote_price,
+            base_decimals,
+            quote_decimals,
+        );
+        let high = math::calculate_tulip_deposit(
+            base_high,
+            quote_high,
+            base_price,
+            quote_price,
+            base_decimals,
+            quote_decimals,
+        );
+
+        // Larger deposit may only fail with overflow, never borrow less
+        if let (Ok(low), Ok(high)) = (low, high) {
+            prop_assert!(low[0].quote_debt <= high[0].quote_debt);
+            prop_assert!(low[1].base_debt <= high[1].base_debt);
+        }
+    }
+
+    #[test]
+    fn tulip_deposit_leverage_bounded(
+        base_amount in any::<u64>(),
+        quote_amount in any::<u64>(),
+        base_price in price(),
+        quote_price in price(),
+        base_decimals in 0u8..=12,
+        quote_decimals in 0u8..=12,
+    ) {
+        if let Ok(legs) = math::calculate_tulip_deposit(
+            base_amount,
+            quote_amount,
+            base_price,
+            quote_price,
+            base_decimals,
+            quote_decimals,
+        ) {
+            for leg in legs.iter() {
+                let max_borrow_value =
+                    deposit_value(leg, base_price, quote_price, base_decimals, quote_decimals)
+                        .try_mul(TULIP_DEPOSIT_LEVERAGE - 1)
+                        .unwrap();
+                let borrow_value = math::calculate_value(leg.base_debt, base_price, base_decimals)
+                    .unwrap()
+                    .try_add(math::calculate_value(leg.quote_debt, quote_price, quote_decimals).unwrap())
+                    .unwrap();
+
+                prop_assert!(borrow_value <= max_borrow_value);
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+mod test_utils;
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{bpf_loader_upgradeable, pubkey::Pubkey};
+use std::{env, fs, str::FromStr};
+use test_utils::*;
+
+const DEFAULT_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+
+/// Refreshes every fixture under `tests/fixtures` from RPC and dumps extra accounts
+/// listed in comma separated `FIXTURE_ACCOUNTS`. Run explicitly with
+/// `cargo test --test dump_fixtures -- --ignored`.
+#[test]
+#[ignore]
+fn dump_fixtures() {
+    let rpc_url = env::var("FIXTURE_RPC_URL").unwrap_or_else(|_| DEFAULT_RPC_URL.to_string());
+    let rpc_client = RpcClient::new(rpc_url);
+
+    let mut pubkeys: Vec<Pubkey> = fs::read_dir(fixtures_dir())
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.unwrap().path();
+            let file_stem = path.file_stem()?.to_str()?.to_string();
+            let pubkey = file_stem
+                .strip_prefix("account_")
+                .or_else(|| file_stem.strip_prefix("program_"))?;
+
+            Pubkey::from_str(pubkey).ok()
+        })
+        .collect();
+
+    if let Ok(fixture_accounts) = env::var("FIXTURE_ACCOUNTS") {
+        pubkeys.extend(
+            fixture_accounts
+                .split(',')
+                .map(|pubkey| Pubkey::from_str(pubkey.trim()).unwrap()),
+        );
+    }
+
+    pubkeys.sort();
+    pubkeys.dedup();
+
+    for pubkey in pubkeys {
+        let account = rpc_client.get_account(&pubkey).unwrap();
+
+        if account.executable && account.owner == bpf_loader_upgradeable::id() {
+            // UpgradeableLoaderState::Program stores programdata address after 4 bytes of enum tag.
+            let buffer_pubkey = Pubkey::new(&account.data[4..36]);
+            let buffer = rpc_client.get_account_data(&buffer_pubkey).unwrap();
+            let fixture = FixtureProgram {
+                account: account.into(),
+                buffer_pubkey: buffer_pubkey.to_bytes(),
+                buffer,
+            };
+
+            fs::write(
+                fixtures_dir().join(format!("program_{}.json", pubkey)),
+                serde_json::to_vec(&fixture).unwrap(),
+            )
+            .unwrap();
+        } else {
+            let fixture: FixtureAccount = account.into();
+
+            fs::write(
+                fixtures_dir().join(format!("account_{}.json", pubkey)),
+                serde_json::to_vec(&fixture).unwrap(),
+            )
+            .unwrap();
+        }
+    }
+}
@@ -0,0 +1,226 @@
+mod test_state;
+mod test_utils;
+
+use anchor_lang::{prelude::Pubkey, AnchorDeserialize, Discriminator};
+use cetra_chamber::{
+    event::{
+        ChamberCranked, ChamberDeposited, PositionOpened, PositionSettled, PositionWithdrawn,
+        VaultDeposited,
+    },
+    state::ChamberOperation,
+};
+use once_cell::sync::Lazy;
+use solana_sdk::signer::Signer;
+use std::sync::Mutex;
+use test_state::*;
+use test_utils::*;
+
+/// `emit!` payloads logged by every bank of this test binary.
+static PROGRAM_DATA: Lazy<Mutex<Vec<Vec<u8>>>> = Lazy::new(Default::default);
+
+/// Collects `Program data:` logs, which bank writes to `log` at debug level.
+struct ProgramDataLogger;
+
+impl log::Log for ProgramDataLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Debug
+    }
+
+    fn log(&self, record: &log::Record) {
+        let message = record.args().to_string();
+
+        if let Some(data) = message.strip_prefix("Program data: ") {
+            let mut program_data = PROGRAM_DATA.lock().unwrap();
+
+            for chunk in data.split(' ') {
+                program_data.push(base64::decode(chunk).unwrap());
+            }
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Same as `start_test_context`, but captures program logs. Logger must be installed
+/// before `ProgramTest` installs its own one.
+async fn start_event_test_context() -> TestContext {
+    let _ = log::set_boxed_logger(Box::new(ProgramDataLogger));
+
+    let test_context = start_test_context().await;
+    log::set_max_level(log::LevelFilter::Debug);
+
+    test_context
+}
+
+/// Events of type `T` emitted for `user`.
+///
+/// Every chamber event starts with `chamber` and `user` fields, so `user` follows
+/// discriminator and chamber key, which keeps concurrent tests apart.
+fn events<T: AnchorDeserialize + Discriminator>(user: &Pubkey) -> Vec<T> {
+    PROGRAM_DATA
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|data| {
+            data.len() >= 72 && data[..8] == T::discriminator() && data[40..72] == user.to_bytes()
+        })
+        .map(|data| T::try_from_slice(&data[8..]).unwrap())
+        .collect()
+}
+
+async fn setup() -> (TestContext, TestChamberTulip, TestUser) {
+    let mut test_context = start_event_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+
+    let pyth_price_account = test_context
+        .get_account(&test_chamber_tulip.farm_config.coin_price_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let pyth_price =
+        tulipv2_sdk_common::pyth::load::<tulipv2_sdk_common::pyth::Price>(&pyth_price_account.data)
+            .unwrap();
+
+    test_context
+        .context
+        .warp_to_slot(pyth_price.valid_slot)
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 2500000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    (test_context, test_chamber_tulip, test_user)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_deposit_and_crank() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup().await;
+    let user = test_user.wallet.pubkey();
+
+    let opened: Vec<PositionOpened> = events(&user);
+    assert_eq!(opened.len(), 1);
+    assert_eq!(opened[0].chamber, test_chamber_tulip.pubkey);
+    assert_eq!(opened[0].base_amount, 2500000000);
+    assert_eq!(opened[0].quote_amount, 100000000);
+    assert_eq!(opened[0].base_fee, 0);
+    assert_eq!(opened[0].quote_fee, 0);
+
+    test_chamber_tulip
+        .deposit_chamber(&mut test_context, &test_user, 2500000000, 100000000)
+        .await
+        .unwrap();
+
+    let user_position = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+
+    let deposited: Vec<ChamberDeposited> = events(&user);
+    assert_eq!(deposited.len(), 1);
+    assert_eq!(deposited[0].base_amount, 2500000000);
+    assert_eq!(deposited[0].quote_amount, 100000000);
+    assert_eq!(deposited[0].minted_shares, user_position.shares);
+
+    for _ in 0..3 {
+        test_chamber_tulip
+            .crank_chamber(&mut test_context, &test_user.wallet)
+            .await
+            .unwrap();
+    }
+
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    let lp_amounts = chamber.lp_amounts;
+
+    let cranked: Vec<ChamberCranked> = events(&user);
+    assert_eq!(
+        cranked
+            .iter()
+            .map(|event| event.operation)
+            .collect::<Vec<_>>(),
+        vec![
+            ChamberOperation::Swapped,
+            ChamberOperation::LiquidityAdded,
+            ChamberOperation::Staked,
+        ]
+    );
+
+    let settled: Vec<PositionSettled> = events(&user);
+    assert_eq!(settled.len(), 1);
+    assert!(settled[0].base_amount > 0);
+    assert!(settled[0].quote_amount > 0);
+    assert_eq!(settled[0].lp_amount, lp_amounts[0] + lp_amounts[1]);
+
+    let vault_deposited: Vec<VaultDeposited> = events(&user);
+    assert_eq!(vault_deposited.len(), 1);
+    assert!(vault_deposited[0].lp_amount > 0);
+    assert!(vault_deposited[0].lp_amount <= settled[0].lp_amount);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_withdraw() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup().await;
+    let user = test_user.wallet.pubkey();
+
+    test_user
+        .withdraw_user_position(&mut test_context, &test_chamber_tulip, 1250000000, 50000000)
+        .await
+        .unwrap();
+
+    let withdrawn: Vec<PositionWithdrawn> = events(&user);
+    assert_eq!(withdrawn.len(), 1);
+    assert_eq!(withdrawn[0].chamber, test_chamber_tulip.pubkey);
+    assert_eq!(withdrawn[0].base_amount, 1250000000);
+    assert_eq!(withdrawn[0].quote_amount, 50000000);
+    assert_eq!(withdrawn[0].base_fee, 0);
+    assert_eq!(withdrawn[0].quote_fee, 0);
+
+    // Rejected withdrawal emits nothing
+    let result = test_user
+        .withdraw_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await;
+    assert!(result.is_err());
+
+    let withdrawn: Vec<PositionWithdrawn> = events(&user);
+    assert_eq!(withdrawn.len(), 1);
+}
@@ -1,27 +1,73 @@
 mod test_state;
 mod test_utils;
 
-use cetra_chamber::state::ProtocolType;
-use cetra_program_test::{solana_program_test::*, *};
+use cetra_chamber::state::{AmmType, ProtocolType};
 use test_state::*;
 use test_utils::*;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn success() {
-    let rpc_account_loader = RpcAccountLoader::default();
-    let mut program_test_loader = ProgramTestLoader::default();
+    let mut test_context = start_test_context().await;
 
-    program_test_loader
-        .program_test
-        .add_program("cetra_chamber", cetra_chamber::id(), None);
-    program_test_loader.load().unwrap();
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_chamber_tulip_state = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!(
+        test_chamber_tulip_state.protocol_type().unwrap(),
+        ProtocolType::Tulip
+    );
+    assert_eq!(
+        test_chamber_tulip_state.amm_type().unwrap(),
+        AmmType::Raydium
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_multiple_chambers_per_farm() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let first_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    let mut second_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(1);
+    second_chamber_tulip.config.rebalance_threshold_bps = 500;
+
+    assert_ne!(first_chamber_tulip.pubkey, second_chamber_tulip.pubkey);
+
+    first_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    second_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
 
-    let mut test_context = program_test_loader
-        .start_with_context(Box::new(rpc_account_loader))
-        .await;
+    let first_chamber_state = first_chamber_tulip.load(&mut test_context).await.unwrap();
+    let second_chamber_state = second_chamber_tulip.load(&mut test_context).await.unwrap();
+
+    assert_eq!(
+        first_chamber_state.leveraged_farm,
+        second_chamber_state.leveraged_farm
+    );
+    assert_eq!(first_chamber_state.index, 0);
+    assert_eq!(second_chamber_state.index, 1);
+    assert_eq!({ second_chamber_state.config.rebalance_threshold_bps }, 500);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_solend() {
+    let mut test_context = start_test_context().await;
 
     let payer = clone_keypair(&test_context.context.payer);
-    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium();
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.protocol_type = ProtocolType::Solend;
 
     test_chamber_tulip
         .initialize_chamber(&mut test_context, &payer)
@@ -29,5 +75,27 @@ async fn success() {
         .unwrap();
 
     let test_chamber_tulip_state = test_chamber_tulip.load(&mut test_context).await.unwrap();
-    assert_eq!(test_chamber_tulip_state.protocol_type, ProtocolType::Tulip);
+    assert_eq!(
+        test_chamber_tulip_state.protocol_type().unwrap(),
+        ProtocolType::Solend
+    );
+    assert_eq!(
+        test_chamber_tulip_state.amm_type().unwrap(),
+        AmmType::Raydium
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fail_solend_unsupported_amm() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.protocol_type = ProtocolType::Solend;
+    test_chamber_tulip.amm_type = AmmType::Orca;
+
+    assert!(test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .is_err());
 }
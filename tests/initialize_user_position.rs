@@ -1,26 +1,45 @@
 mod test_state;
 mod test_utils;
 
-use cetra_program_test::{solana_program_test::*, *};
+use cetra_chamber::{
+    error::ChamberError,
+    math::calculate_swap_amount,
+    state::{PositionStatus, TokenSide},
+};
+use cetra_chamber_client::instruction;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
 use test_state::*;
 use test_utils::*;
 
-#[tokio::test(flavor = "multi_thread")]
-async fn success() {
-    let rpc_account_loader = RpcAccountLoader::default();
-    let mut program_test_loader = ProgramTestLoader::default();
+#[test]
+fn swap_amount_follows_split() {
+    // Half of value is held in base
+    assert_eq!(
+        calculate_swap_amount(100000000, TokenSide::Quote, 5000).unwrap(),
+        50000000
+    );
+    assert_eq!(
+        calculate_swap_amount(2500000000, TokenSide::Base, 5000).unwrap(),
+        1250000000
+    );
 
-    program_test_loader
-        .program_test
-        .add_program("cetra_chamber", cetra_chamber::id(), None);
-    program_test_loader.load().unwrap();
+    // Whole value is held in base
+    assert_eq!(
+        calculate_swap_amount(100000000, TokenSide::Quote, 10000).unwrap(),
+        100000000
+    );
+    assert_eq!(
+        calculate_swap_amount(2500000000, TokenSide::Base, 10000).unwrap(),
+        0
+    );
+}
 
-    let mut test_context = program_test_loader
-        .start_with_context(Box::new(rpc_account_loader))
-        .await;
+#[tokio::test(flavor = "multi_thread")]
+async fn success() {
+    let mut test_context = start_test_context().await;
 
     let payer = clone_keypair(&test_context.context.payer);
-    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium();
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
 
     test_chamber_tulip
         .initialize_chamber(&mut test_context, &payer)
@@ -65,4 +84,148 @@ async fn success() {
     assert_eq!(user_position.base_amount, 2500000000);
     assert_eq!(user_position.quote_amount, 100000000);
     assert_eq!(user_position.chamber, test_chamber_tulip.pubkey);
+    assert_eq!(user_position.status, PositionStatus::Pending);
+    assert!(user_position.last_deposit_ts > 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_single_sided_quote() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.base_split_bps = 5000;
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    // USDC with 100$, no SOL
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position_single_sided(
+            &mut test_context,
+            &test_chamber_tulip,
+            TokenSide::Quote,
+            100000000,
+            1,
+        )
+        .await
+        .unwrap();
+
+    let user_position = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+
+    assert_eq!(user_position.quote_amount, 50000000);
+    assert!(user_position.base_amount > 0);
+
+    let chamber_state = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!({ chamber_state.total_quote_amount }, 50000000);
+    assert_eq!(
+        { chamber_state.total_base_amount },
+        user_position.base_amount
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_single_sided_minimum_amount_out() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.base_split_bps = 5000;
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    // 50$ never buys 100 SOL
+    assert!(test_user
+        .initialize_user_position_single_sided(
+            &mut test_context,
+            &test_chamber_tulip,
+            TokenSide::Quote,
+            100000000,
+            100000000000,
+        )
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_single_sided_invalid_pool() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.base_split_bps = 5000;
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    let pool = test_chamber_tulip.get_pool();
+    let forged = [pool.amm_id, pool.coin_token_account, pool.pc_token_account];
+
+    // Swap must go through chamber pool and its reserves
+    for forged_account in forged {
+        let mut ix = instruction::initialize_user_position_single_sided_ix(
+            &test_chamber_tulip,
+            &test_user.wallet.pubkey(),
+            TokenSide::Quote,
+            100000000,
+            0,
+        );
+        for account in ix.accounts.iter_mut() {
+            if account.pubkey == forged_account {
+                account.pubkey = Pubkey::new_unique();
+            }
+        }
+
+        let result = test_user.process(&mut test_context, ix).await;
+        assert_eq!(
+            transaction_error_code(result),
+            Some(ChamberError::InvalidPool.into())
+        );
+    }
 }
@@ -0,0 +1,333 @@
+mod test_state;
+mod test_utils;
+
+use anchor_lang::error::ErrorCode;
+use cetra_chamber::{
+    error::ChamberError,
+    math::{calculate_management_fee, SECONDS_PER_YEAR},
+};
+use test_state::*;
+use test_utils::*;
+
+#[test]
+fn accrual_does_not_drift() {
+    let amount = 123_456_789u64;
+    let fee_bps = 150;
+
+    let (expected_fee, _) = calculate_management_fee(amount, fee_bps, SECONDS_PER_YEAR, 0).unwrap();
+
+    let mut total_fee = 0;
+    let mut remainder = 0;
+
+    for _ in 0..SECONDS_PER_YEAR / 60 {
+        let (fee, next_remainder) =
+            calculate_management_fee(amount, fee_bps, 60, remainder).unwrap();
+        total_fee += fee;
+        remainder = next_remainder;
+    }
+
+    assert_eq!(total_fee, expected_fee);
+}
+
+async fn setup() -> (TestContext, TestChamberTulip, TestUser) {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.management_fee_bps = 200;
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 2500000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    (test_context, test_chamber_tulip, test_user)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success() {
+    let (mut test_context, test_chamber_tulip, _) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    let chamber_state = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!({ chamber_state.accrued_base_fee }, 0);
+    assert_eq!({ chamber_state.accrued_quote_fee }, 0);
+
+    // Half a year later any state-changing instruction charges the fee
+    warp_clock(&mut test_context, (SECONDS_PER_YEAR / 2) as i64).await;
+
+    let other_user = TestUser::new();
+
+    other_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    other_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 0)
+        .await
+        .unwrap();
+
+    other_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 0)
+        .await
+        .unwrap();
+
+    other_user
+        .initialize_user_position(&mut test_context, &test_chamber_tulip, 0, 0)
+        .await
+        .unwrap();
+
+    let chamber_state = test_chamber_tulip.load(&mut test_context).await.unwrap();
+
+    // 2% annually for half a year
+    assert_eq!({ chamber_state.accrued_base_fee }, 25000000);
+    assert_eq!({ chamber_state.accrued_quote_fee }, 1000000);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_collect() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    warp_clock(&mut test_context, (SECONDS_PER_YEAR / 2) as i64).await;
+
+    test_chamber_tulip
+        .collect_management_fee(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let (treasury_base_ata, _) = test_chamber_tulip
+        .treasury
+        .get_ata(&mut test_context, &test_utils::wsol_mint::id())
+        .await
+        .unwrap();
+    let (treasury_quote_ata, _) = test_chamber_tulip
+        .treasury
+        .get_ata(&mut test_context, &test_utils::usdc_mint::id())
+        .await
+        .unwrap();
+    assert_eq!(treasury_base_ata.amount, 25000000);
+    assert_eq!(treasury_quote_ata.amount, 1000000);
+
+    let chamber_state = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!({ chamber_state.accrued_base_fee }, 0);
+    assert_eq!({ chamber_state.accrued_quote_fee }, 0);
+    assert_eq!({ chamber_state.total_base_amount }, 2475000000);
+    assert_eq!({ chamber_state.total_quote_amount }, 99000000);
+
+    // Collected fee is paid by user, deposited amounts can't be withdrawn anymore
+    let result = test_user
+        .withdraw_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::InsufficientUserPositionFunds.into())
+    );
+
+    test_user
+        .withdraw_user_position(&mut test_context, &test_chamber_tulip, 2475000000, 99000000)
+        .await
+        .unwrap();
+
+    let chamber_state = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!({ chamber_state.total_base_amount }, 0);
+    assert_eq!({ chamber_state.total_quote_amount }, 0);
+    assert_eq!({ chamber_state.total_base_units }, 0);
+    assert_eq!({ chamber_state.total_quote_units }, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_deployed() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.management_fee_bps = 200;
+
+    let pyth_price_account = test_context
+        .get_account(&test_chamber_tulip.farm_config.coin_price_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let pyth_price =
+        tulipv2_sdk_common::pyth::load::<tulipv2_sdk_common::pyth::Price>(&pyth_price_account.data)
+            .unwrap();
+
+    test_context
+        .context
+        .warp_to_slot(pyth_price.valid_slot)
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 2500000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    test_user
+        .queue_deposit(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .process_chamber_epoch(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let chamber_state = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    let user_shares = chamber_state.total_shares;
+    assert!(user_shares > 0);
+    assert_eq!({ chamber_state.fee_shares }, 0);
+
+    // Half a year later fee is charged on deployed strategy, while idle balances are empty
+    warp_clock(&mut test_context, (SECONDS_PER_YEAR / 2) as i64).await;
+
+    let other_user = TestUser::new();
+
+    other_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    other_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 0)
+        .await
+        .unwrap();
+
+    other_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 0)
+        .await
+        .unwrap();
+
+    other_user
+        .initialize_user_position(&mut test_context, &test_chamber_tulip, 0, 0)
+        .await
+        .unwrap();
+
+    let (expected_fee_shares, _) =
+        calculate_management_fee(user_shares, 200, SECONDS_PER_YEAR / 2, 0).unwrap();
+
+    let chamber_state = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert!(expected_fee_shares > 0);
+    assert_eq!({ chamber_state.fee_shares }, expected_fee_shares);
+    assert_eq!(
+        { chamber_state.total_shares },
+        user_shares + expected_fee_shares
+    );
+    assert_eq!({ chamber_state.accrued_base_fee }, 0);
+    assert_eq!({ chamber_state.accrued_quote_fee }, 0);
+
+    // Idle quote outside of users balances lets the next epoch settle fee shares at NAV
+    test_chamber_tulip
+        .add_quote_ata_balance(&mut test_context, 100000000)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .process_chamber_epoch(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let chamber_state = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    let fee = chamber_state.accrued_quote_fee;
+    assert!(fee > 0 && fee <= 100000000);
+    assert_eq!({ chamber_state.fee_shares }, 0);
+    assert_eq!({ chamber_state.total_shares }, user_shares);
+    assert_eq!({ chamber_state.total_quote_amount }, fee);
+
+    test_chamber_tulip
+        .collect_management_fee(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let (treasury_quote_ata, _) = test_chamber_tulip
+        .treasury
+        .get_ata(&mut test_context, &test_utils::usdc_mint::id())
+        .await
+        .unwrap();
+    assert_eq!(treasury_quote_ata.amount, fee);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_collect_not_admin() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup().await;
+
+    warp_clock(&mut test_context, (SECONDS_PER_YEAR / 2) as i64).await;
+
+    let result = test_chamber_tulip
+        .collect_management_fee(&mut test_context, &test_user.wallet)
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ErrorCode::ConstraintHasOne.into())
+    );
+}
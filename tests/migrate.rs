@@ -0,0 +1,739 @@
+mod test_state;
+mod test_utils;
+
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::spl_token::{
+        self,
+        state::{Account as TokenAccount, AccountState},
+    },
+};
+use cetra_chamber::{
+    error::ChamberError,
+    state::{
+        AmmType, Chamber, ChamberConfig, ChamberOperation, ChamberPool, LegacyChamber,
+        LegacyUserPosition, PositionStatus, ProtocolType, StrategyLeg, UserPosition,
+    },
+    utils::derive_user_position,
+};
+use solana_sdk::{account::Account, program_option::COption, program_pack::Pack, signer::Signer};
+use test_state::*;
+use test_utils::*;
+
+/// Borsh `Chamber` layout of version 1, before the zero-copy switch.
+#[derive(AnchorSerialize)]
+struct BorshChamber {
+    leveraged_farm: Pubkey,
+    index: u8,
+    authority: Pubkey,
+    admin: Pubkey,
+    base_ata: Pubkey,
+    quote_ata: Pubkey,
+    base_mint: Pubkey,
+    quote_mint: Pubkey,
+    base_decimals: u8,
+    quote_decimals: u8,
+    treasury_base_ata: Pubkey,
+    treasury_quote_ata: Pubkey,
+    protocol_type: ProtocolType,
+    amm_type: AmmType,
+    config: ChamberConfig,
+    legs: [StrategyLeg; 2],
+    total_base_amount: u64,
+    total_quote_amount: u64,
+    accrued_base_fee: u64,
+    accrued_quote_fee: u64,
+    base_fee_remainder: u128,
+    quote_fee_remainder: u128,
+    last_fee_accrual_ts: i64,
+    total_shares: u64,
+    epoch: u64,
+    claimable_quote_amount: u64,
+    operation: ChamberOperation,
+    bump: u8,
+    authority_bump: u8,
+    version: u8,
+    reserved: [u8; 64],
+}
+
+/// `Chamber` layout of the first release, derived without `index` and created without admin.
+#[derive(AnchorSerialize)]
+struct BaselineChamber {
+    leveraged_farm: Pubkey,
+    authority: Pubkey,
+    base_ata: Pubkey,
+    quote_ata: Pubkey,
+    base_mint: Pubkey,
+    quote_mint: Pubkey,
+    protocol_type: ProtocolType,
+    bump: u8,
+    authority_bump: u8,
+}
+
+/// `UserPosition` layout of the first release.
+#[derive(AnchorSerialize)]
+struct BaselineUserPosition {
+    owner: Pubkey,
+    chamber: Pubkey,
+    base_amount: u64,
+    quote_amount: u64,
+    bump: u8,
+}
+
+/// Rewrites account data with given size, e.g. truncated to layout before versioning.
+async fn resize_account(test_context: &mut TestContext, address: Pubkey, len: usize) {
+    let mut account = test_context.get_account(&address).await.unwrap().unwrap();
+
+    account.data.resize(len, 0);
+    account.lamports = test_context.get_rent().await.minimum_balance(len);
+
+    test_context.context.set_account(&address, &account.into());
+}
+
+async fn setup() -> (TestContext, TestChamberTulip, TestUser) {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position(&mut test_context, &test_chamber_tulip, 0, 100000000)
+        .await
+        .unwrap();
+
+    (test_context, test_chamber_tulip, test_user)
+}
+
+#[test]
+fn borsh_chamber_loads_zero_copy() {
+    let leg = |seed: u64| StrategyLeg {
+        base_collateral: seed + 1,
+        quote_collateral: seed + 2,
+        base_debt: seed + 3,
+        quote_debt: seed + 4,
+    };
+
+    // Every field holds distinct value, so any offset mismatch shows up
+    let borsh_chamber = BorshChamber {
+        leveraged_farm: Pubkey::new_unique(),
+        index: 3,
+        authority: Pubkey::new_unique(),
+        admin: Pubkey::new_unique(),
+        base_ata: Pubkey::new_unique(),
+        quote_ata: Pubkey::new_unique(),
+        base_mint: Pubkey::new_unique(),
+        quote_mint: Pubkey::new_unique(),
+        base_decimals: 9,
+        quote_decimals: 6,
+        treasury_base_ata: Pubkey::new_unique(),
+        treasury_quote_ata: Pubkey::new_unique(),
+        protocol_type: ProtocolType::Solend,
+        amm_type: AmmType::Orca,
+        config: ChamberConfig {
+            management_fee_bps: 101,
+            entry_fee_bps: 102,
+            exit_fee_bps: 103,
+            rebalance_threshold_bps: 104,
+            deleverage_ltv_bps: 105,
+            base_split_bps: 106,
+            withdrawal_cooldown: 107,
+            swap_program: Pubkey::new_unique(),
+        },
+        legs: [leg(10), leg(20)],
+        total_base_amount: 31,
+        total_quote_amount: 32,
+        accrued_base_fee: 33,
+        accrued_quote_fee: 34,
+        base_fee_remainder: 35,
+        quote_fee_remainder: 36,
+        last_fee_accrual_ts: -37,
+        total_shares: 38,
+        epoch: 39,
+        claimable_quote_amount: 40,
+        operation: ChamberOperation::LiquidityAdded,
+        bump: 41,
+        authority_bump: 42,
+        version: 1,
+        reserved: [0; 64],
+    };
+
+    let mut data = Chamber::discriminator().to_vec();
+    borsh_chamber.serialize(&mut data).unwrap();
+    assert_eq!(data.len(), Chamber::V1_LEN);
+
+    // `MigrateChamber` reallocs previous layout with zeroes
+    data.resize(Chamber::LEN, 0);
+
+    let key = Pubkey::new_unique();
+    let owner = cetra_chamber::id();
+    let mut lamports = 0;
+    let account_info = AccountInfo::new(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+    let loader = AccountLoader::<Chamber>::try_from(&account_info).unwrap();
+    let chamber = loader.load().unwrap();
+
+    assert_eq!(chamber.leveraged_farm, borsh_chamber.leveraged_farm);
+    assert_eq!(chamber.index, borsh_chamber.index);
+    assert_eq!(chamber.authority, borsh_chamber.authority);
+    assert_eq!(chamber.admin, borsh_chamber.admin);
+    assert_eq!(chamber.base_ata, borsh_chamber.base_ata);
+    assert_eq!(chamber.quote_ata, borsh_chamber.quote_ata);
+    assert_eq!(chamber.base_mint, borsh_chamber.base_mint);
+    assert_eq!(chamber.quote_mint, borsh_chamber.quote_mint);
+    assert_eq!(chamber.base_decimals, borsh_chamber.base_decimals);
+    assert_eq!(chamber.quote_decimals, borsh_chamber.quote_decimals);
+    assert_eq!(chamber.treasury_base_ata, borsh_chamber.treasury_base_ata);
+    assert_eq!(chamber.treasury_quote_ata, borsh_chamber.treasury_quote_ata);
+    assert_eq!(
+        chamber.protocol_type().unwrap(),
+        borsh_chamber.protocol_type
+    );
+    assert_eq!(chamber.amm_type().unwrap(), borsh_chamber.amm_type);
+    assert_eq!({ chamber.config }, borsh_chamber.config);
+    assert_eq!({ chamber.legs }, borsh_chamber.legs);
+    assert_eq!(
+        { chamber.total_base_amount },
+        borsh_chamber.total_base_amount
+    );
+    assert_eq!(
+        { chamber.total_quote_amount },
+        borsh_chamber.total_quote_amount
+    );
+    assert_eq!({ chamber.accrued_base_fee }, borsh_chamber.accrued_base_fee);
+    assert_eq!(
+        { chamber.accrued_quote_fee },
+        borsh_chamber.accrued_quote_fee
+    );
+    assert_eq!(
+        { chamber.base_fee_remainder },
+        borsh_chamber.base_fee_remainder
+    );
+    assert_eq!(
+        { chamber.quote_fee_remainder },
+        borsh_chamber.quote_fee_remainder
+    );
+    assert_eq!(
+        { chamber.last_fee_accrual_ts },
+        borsh_chamber.last_fee_accrual_ts
+    );
+    assert_eq!({ chamber.total_shares }, borsh_chamber.total_shares);
+    assert_eq!({ chamber.epoch }, borsh_chamber.epoch);
+    assert_eq!(
+        { chamber.claimable_quote_amount },
+        borsh_chamber.claimable_quote_amount
+    );
+    assert_eq!(chamber.operation().unwrap(), borsh_chamber.operation);
+    assert_eq!(chamber.bump, borsh_chamber.bump);
+    assert_eq!(chamber.authority_bump, borsh_chamber.authority_bump);
+    assert_eq!(chamber.version, borsh_chamber.version);
+
+    // Fields after version 1 layout are zeroed until `migrate()`
+    assert_eq!({ chamber.total_base_units }, 0);
+    assert_eq!({ chamber.total_quote_units }, 0);
+    assert_eq!({ chamber.lp_amounts }, [0; 2]);
+    assert_eq!(chamber.keeper, Pubkey::default());
+    assert_eq!({ chamber.pool }, ChamberPool::default());
+    assert_eq!(chamber.base_oracle, Pubkey::default());
+    assert_eq!(chamber.quote_oracle, Pubkey::default());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_chamber() {
+    let (mut test_context, test_chamber_tulip, _) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    let before = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!(before.version, Chamber::VERSION);
+
+    resize_account(
+        &mut test_context,
+        test_chamber_tulip.pubkey,
+        Chamber::V0_LEN,
+    )
+    .await;
+
+    test_chamber_tulip
+        .migrate_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let account = test_context
+        .get_account(&test_chamber_tulip.pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), Chamber::LEN);
+
+    let after = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!(after.version, Chamber::VERSION);
+    assert_eq!(after.admin, before.admin);
+    assert_eq!(after.leveraged_farm, before.leveraged_farm);
+    assert_eq!(after.config, before.config);
+
+    // Balances held before units are converted 1:1
+    assert_eq!({ after.total_quote_units }, { before.total_quote_amount });
+    assert_eq!({ after.total_quote_amount }, { before.total_quote_amount });
+
+    // Keeper and pool are set by admin
+    assert_eq!(after.keeper, payer.pubkey());
+    assert_eq!({ after.pool }, test_chamber_tulip.get_pool());
+    assert_eq!(
+        after.base_oracle,
+        test_chamber_tulip.farm_config.coin_price_account
+    );
+    assert_eq!(
+        after.quote_oracle,
+        test_chamber_tulip.farm_config.pc_price_account
+    );
+    assert_eq!({ after.lp_amounts }, [0; 2]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_chamber_v1() {
+    let (mut test_context, test_chamber_tulip, _) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    let before = test_chamber_tulip.load(&mut test_context).await.unwrap();
+
+    resize_account(
+        &mut test_context,
+        test_chamber_tulip.pubkey,
+        Chamber::V1_LEN,
+    )
+    .await;
+
+    // Layout of version 2 ends before pool
+    let mut account = test_context
+        .get_account(&test_chamber_tulip.pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    account.data[Chamber::V0_LEN] = 2;
+    account.data[Chamber::V0_LEN + 1 + 8 * 2..].fill(0);
+    test_context
+        .context
+        .set_account(&test_chamber_tulip.pubkey, &account.into());
+
+    test_chamber_tulip
+        .migrate_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let account = test_context
+        .get_account(&test_chamber_tulip.pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), Chamber::LEN);
+
+    let after = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!(after.version, Chamber::VERSION);
+    assert_eq!(after.admin, before.admin);
+    assert_eq!(after.config, before.config);
+    assert_eq!(after.keeper, payer.pubkey());
+    assert_eq!({ after.pool }, test_chamber_tulip.get_pool());
+    assert_eq!(
+        after.base_oracle,
+        test_chamber_tulip.farm_config.coin_price_account
+    );
+    assert_eq!(
+        after.quote_oracle,
+        test_chamber_tulip.farm_config.pc_price_account
+    );
+
+    // Units issued by version 2 are kept
+    assert_eq!({ after.total_quote_units }, { before.total_quote_units });
+    assert_eq!({ after.total_quote_amount }, { before.total_quote_amount });
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_user_position() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup().await;
+
+    let before = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+
+    let (user_position, _) =
+        derive_user_position(&test_user.wallet.pubkey(), &test_chamber_tulip.pubkey);
+    resize_account(&mut test_context, user_position, UserPosition::V0_LEN).await;
+
+    test_user
+        .migrate_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+
+    let after = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+    assert_eq!(after.version, UserPosition::VERSION);
+    assert_eq!(after.owner, before.owner);
+    assert_eq!(after.chamber, before.chamber);
+    assert_eq!(after.quote_amount, before.quote_amount);
+    assert_eq!(after.status, before.status);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_already_migrated() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    assert!(test_chamber_tulip
+        .migrate_chamber(&mut test_context, &payer)
+        .await
+        .is_err());
+
+    assert!(test_user
+        .migrate_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_unknown_layout() {
+    let (mut test_context, test_chamber_tulip, _) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    resize_account(
+        &mut test_context,
+        test_chamber_tulip.pubkey,
+        Chamber::V0_LEN + 1,
+    )
+    .await;
+
+    assert!(test_chamber_tulip
+        .migrate_chamber(&mut test_context, &payer)
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_not_admin() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup().await;
+
+    resize_account(
+        &mut test_context,
+        test_chamber_tulip.pubkey,
+        Chamber::V0_LEN,
+    )
+    .await;
+
+    assert!(test_chamber_tulip
+        .migrate_chamber(&mut test_context, &test_user.wallet)
+        .await
+        .is_err());
+}
+
+/// Writes account owned by chamber program, e.g. state encoded by the first release.
+fn set_program_account(test_context: &mut TestContext, address: Pubkey, data: Vec<u8>) {
+    let account = Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: cetra_chamber::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    test_context.context.set_account(&address, &account.into());
+}
+
+/// Writes associated token account of `owner`, which may be program address.
+fn set_token_account(test_context: &mut TestContext, owner: Pubkey, mint: Pubkey, amount: u64) {
+    let rent_lamports = Rent::default().minimum_balance(TokenAccount::LEN);
+    let is_native = mint == spl_token::native_mint::id();
+
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint,
+        owner,
+        amount,
+        state: AccountState::Initialized,
+        is_native: if is_native {
+            COption::Some(rent_lamports)
+        } else {
+            COption::None
+        },
+        ..TokenAccount::default()
+    }
+    .pack_into_slice(&mut data);
+
+    let account = Account {
+        lamports: if is_native {
+            rent_lamports + amount
+        } else {
+            rent_lamports
+        },
+        data,
+        owner: spl_token::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    test_context.context.set_account(
+        &get_associated_token_address(&owner, &mint),
+        &account.into(),
+    );
+}
+
+/// Chamber of the first release holding `quote_amount` and program upgrade authority.
+async fn setup_legacy(quote_amount: u64) -> (TestContext, TestChamberTulip, TestUser) {
+    let upgrade_authority = TestUser::new();
+    let mut test_context = start_upgradeable_test_context(&upgrade_authority.wallet.pubkey()).await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    upgrade_authority
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    let test_chamber_tulip = TestChamberTulip::new_legacy_sol_usdc_raydium();
+    let base_mint = test_chamber_tulip.farm_config.base_token_mint;
+    let quote_mint = test_chamber_tulip.farm_config.quote_token_mint;
+
+    test_chamber_tulip
+        .treasury
+        .create_ata(&mut test_context, &base_mint, 0)
+        .await
+        .unwrap();
+    test_chamber_tulip
+        .treasury
+        .create_ata(&mut test_context, &quote_mint, 0)
+        .await
+        .unwrap();
+
+    let mut data = Chamber::discriminator().to_vec();
+    BaselineChamber {
+        leveraged_farm: test_chamber_tulip.farm_config.account,
+        authority: test_chamber_tulip.authority,
+        base_ata: test_chamber_tulip.get_base_ata(),
+        quote_ata: test_chamber_tulip.get_quote_ata(),
+        base_mint,
+        quote_mint,
+        protocol_type: ProtocolType::Tulip,
+        bump: test_chamber_tulip.bump,
+        authority_bump: test_chamber_tulip.authority_bump,
+    }
+    .serialize(&mut data)
+    .unwrap();
+    assert_eq!(data.len(), LegacyChamber::LEN);
+
+    set_program_account(&mut test_context, test_chamber_tulip.pubkey, data);
+    set_token_account(
+        &mut test_context,
+        test_chamber_tulip.authority,
+        base_mint,
+        0,
+    );
+    set_token_account(
+        &mut test_context,
+        test_chamber_tulip.authority,
+        quote_mint,
+        quote_amount,
+    );
+
+    (test_context, test_chamber_tulip, upgrade_authority)
+}
+
+/// Position of the first release holding `quote_amount` in legacy chamber.
+async fn setup_legacy_user(
+    test_context: &mut TestContext,
+    test_chamber_tulip: &TestChamberTulip,
+    quote_amount: u64,
+) -> TestUser {
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    let (user_position, bump) =
+        derive_user_position(&test_user.wallet.pubkey(), &test_chamber_tulip.pubkey);
+
+    let mut data = UserPosition::discriminator().to_vec();
+    BaselineUserPosition {
+        owner: test_user.wallet.pubkey(),
+        chamber: test_chamber_tulip.pubkey,
+        base_amount: 0,
+        quote_amount,
+        bump,
+    }
+    .serialize(&mut data)
+    .unwrap();
+    assert_eq!(data.len(), LegacyUserPosition::LEN);
+
+    set_program_account(test_context, user_position, data);
+
+    test_user
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_legacy_chamber() {
+    let (mut test_context, test_chamber_tulip, upgrade_authority) = setup_legacy(100000000).await;
+
+    test_chamber_tulip
+        .migrate_legacy_chamber(&mut test_context, &upgrade_authority.wallet)
+        .await
+        .unwrap();
+
+    let account = test_context
+        .get_account(&test_chamber_tulip.pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), Chamber::LEN);
+
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!(chamber.version, Chamber::VERSION);
+    assert_eq!(chamber.legacy_address, 1);
+    assert_eq!(chamber.index_seed(), &[] as &[u8]);
+    assert_eq!(
+        chamber.leveraged_farm,
+        test_chamber_tulip.farm_config.account
+    );
+    assert_eq!(chamber.authority, test_chamber_tulip.authority);
+    assert_eq!(chamber.bump, test_chamber_tulip.bump);
+    assert_eq!(chamber.authority_bump, test_chamber_tulip.authority_bump);
+    assert_eq!(chamber.quote_ata, test_chamber_tulip.get_quote_ata());
+    assert_eq!(chamber.protocol_type().unwrap(), ProtocolType::Tulip);
+    assert_eq!(chamber.operation().unwrap(), ChamberOperation::Idle);
+
+    // Upgrade authority takes over chamber created without admin
+    assert_eq!(chamber.admin, upgrade_authority.wallet.pubkey());
+    assert_eq!(chamber.keeper, upgrade_authority.wallet.pubkey());
+    assert_eq!({ chamber.pool }, test_chamber_tulip.get_pool());
+
+    // Token account balances become idle balances with units issued 1:1
+    assert_eq!({ chamber.total_quote_amount }, 100000000);
+    assert_eq!({ chamber.total_quote_units }, 100000000);
+    assert_eq!({ chamber.total_base_amount }, 0);
+    assert_eq!({ chamber.total_shares }, 0);
+
+    let epoch = test_chamber_tulip
+        .load_epoch(&mut test_context, 0)
+        .await
+        .unwrap();
+    assert_eq!(epoch.chamber, test_chamber_tulip.pubkey);
+
+    // Only once
+    assert!(test_chamber_tulip
+        .migrate_legacy_chamber(&mut test_context, &upgrade_authority.wallet)
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_legacy_user_position() {
+    let (mut test_context, test_chamber_tulip, upgrade_authority) = setup_legacy(100000000).await;
+    let test_user = setup_legacy_user(&mut test_context, &test_chamber_tulip, 100000000).await;
+
+    test_chamber_tulip
+        .migrate_legacy_chamber(&mut test_context, &upgrade_authority.wallet)
+        .await
+        .unwrap();
+
+    test_user
+        .migrate_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+
+    let (user_position, bump) =
+        derive_user_position(&test_user.wallet.pubkey(), &test_chamber_tulip.pubkey);
+    let account = test_context
+        .get_account(&user_position)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), UserPosition::LEN);
+
+    let position = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+    assert_eq!(position.version, UserPosition::VERSION);
+    assert_eq!(position.owner, test_user.wallet.pubkey());
+    assert_eq!(position.chamber, test_chamber_tulip.pubkey);
+    assert_eq!(position.bump, bump);
+    assert_eq!(position.base_amount, 0);
+    assert_eq!(position.quote_amount, 100000000);
+    assert_eq!(position.shares, 0);
+    assert_eq!(position.status, PositionStatus::Pending);
+
+    // Legacy chamber address passes seeds constraints of current instructions
+    test_user
+        .create_ata(
+            &mut test_context,
+            &test_chamber_tulip.farm_config.quote_token_mint,
+            0,
+        )
+        .await
+        .unwrap();
+    test_user
+        .withdraw_user_position(&mut test_context, &test_chamber_tulip, 0, 100000000)
+        .await
+        .unwrap();
+
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!({ chamber.total_quote_amount }, 0);
+    assert_eq!(
+        test_chamber_tulip
+            .get_token_amount(&mut test_context, test_chamber_tulip.get_quote_ata())
+            .await
+            .unwrap(),
+        0
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_legacy_not_upgrade_authority() {
+    let (mut test_context, test_chamber_tulip, _) = setup_legacy(100000000).await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    assert_eq!(
+        transaction_error_code(
+            test_chamber_tulip
+                .migrate_legacy_chamber(&mut test_context, &payer)
+                .await
+        ),
+        Some(ChamberError::InvalidUpgradeAuthority.into())
+    );
+
+    // Layout of the first release isn't a prefix of the current one
+    assert_eq!(
+        transaction_error_code(
+            test_chamber_tulip
+                .migrate_chamber(&mut test_context, &payer)
+                .await
+        ),
+        Some(ChamberError::InvalidAccountLayout.into())
+    );
+}
@@ -0,0 +1,224 @@
+mod test_state;
+mod test_utils;
+
+use cetra_chamber::state::{ChamberOperation, StrategyLeg};
+use mock_levfarm::{Journal, MockInstruction};
+use solana_sdk::account::Account;
+use test_state::*;
+use test_utils::*;
+
+async fn set_journal(
+    test_context: &mut TestContext,
+    test_chamber_tulip: &TestChamberTulip,
+    fail_on: Option<MockInstruction>,
+) {
+    let (chamber_farm, _) = test_chamber_tulip.derive_chamber_farm();
+
+    let account = Account {
+        lamports: test_context.get_rent().await.minimum_balance(Journal::LEN),
+        data: Journal::new(fail_on).pack().unwrap(),
+        owner: mock_levfarm::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    test_context
+        .context
+        .set_account(&chamber_farm, &account.into());
+}
+
+async fn load_journal(
+    test_context: &mut TestContext,
+    test_chamber_tulip: &TestChamberTulip,
+) -> Journal {
+    let (chamber_farm, _) = test_chamber_tulip.derive_chamber_farm();
+    let account = test_context
+        .get_account(&chamber_farm)
+        .await
+        .unwrap()
+        .unwrap();
+
+    Journal::unpack(&account.data).unwrap()
+}
+
+fn recorded_instructions(journal: &Journal) -> Vec<MockInstruction> {
+    journal.calls.iter().map(|call| call.instruction).collect()
+}
+
+fn leg_amounts(leg: &StrategyLeg) -> Vec<u64> {
+    vec![
+        leg.base_collateral,
+        leg.quote_collateral,
+        leg.base_debt,
+        leg.quote_debt,
+    ]
+}
+
+/// Initializes chamber strategy against mock levfarm and user position with 2.5 SOL and 100 USDC.
+async fn setup(fail_on: Option<MockInstruction>) -> (TestContext, TestChamberTulip, TestUser) {
+    let mut test_context = start_mock_levfarm_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+
+    let pyth_price_account = test_context
+        .get_account(&test_chamber_tulip.farm_config.coin_price_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let pyth_price =
+        tulipv2_sdk_common::pyth::load::<tulipv2_sdk_common::pyth::Price>(&pyth_price_account.data)
+            .unwrap();
+
+    test_context
+        .context
+        .warp_to_slot(pyth_price.valid_slot)
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    set_journal(&mut test_context, &test_chamber_tulip, fail_on).await;
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 2500000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    (test_context, test_chamber_tulip, test_user)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_deposit() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup(None).await;
+
+    let journal = load_journal(&mut test_context, &test_chamber_tulip).await;
+    assert_eq!(
+        recorded_instructions(&journal),
+        vec![
+            MockInstruction::CreateUserFarm,
+            MockInstruction::CreateUserFarmObligation,
+        ]
+    );
+
+    test_chamber_tulip
+        .deposit_chamber(&mut test_context, &test_user, 2500000000, 100000000)
+        .await
+        .unwrap();
+
+    let journal = load_journal(&mut test_context, &test_chamber_tulip).await;
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    let deposits = journal.calls_of(MockInstruction::DepositBorrowDual);
+
+    // Each obligation receives exactly amounts recorded in chamber legs
+    assert_eq!(deposits.len(), 2);
+    assert_eq!(deposits[0].amounts, leg_amounts(&chamber.legs[0]));
+    assert_eq!(deposits[1].amounts, leg_amounts(&chamber.legs[1]));
+    assert_eq!(deposits[0].amounts[..2], [625000000, 25000000]);
+    assert_eq!(deposits[0].amounts[2], 0);
+    assert_eq!(deposits[1].amounts[3], 0);
+    assert_eq!(chamber.operation().unwrap(), ChamberOperation::Borrowed);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_crank() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup(None).await;
+
+    test_chamber_tulip
+        .deposit_chamber(&mut test_context, &test_user, 2500000000, 100000000)
+        .await
+        .unwrap();
+
+    for _ in 0..3 {
+        test_chamber_tulip
+            .crank_chamber(&mut test_context, &test_user.wallet)
+            .await
+            .unwrap();
+    }
+
+    let journal = load_journal(&mut test_context, &test_chamber_tulip).await;
+    assert_eq!(
+        recorded_instructions(&journal)[4..],
+        [
+            MockInstruction::SwapTokensRaydiumStats,
+            MockInstruction::SwapTokensRaydiumStats,
+            MockInstruction::AddLiquidityStats,
+            MockInstruction::AddLiquidityStats,
+            MockInstruction::DepositRaydiumVault,
+            MockInstruction::DepositRaydiumVault,
+        ]
+    );
+
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!(chamber.operation().unwrap(), ChamberOperation::Staked);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_deposit_borrow() {
+    let (mut test_context, test_chamber_tulip, test_user) =
+        setup(Some(MockInstruction::DepositBorrowDual)).await;
+
+    let result = test_chamber_tulip
+        .deposit_chamber(&mut test_context, &test_user, 2500000000, 100000000)
+        .await;
+    assert!(result.is_err());
+
+    // Failed levfarm call reverts chamber accounting
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!(chamber.legs, [StrategyLeg::default(); 2]);
+    assert_eq!(chamber.operation().unwrap(), ChamberOperation::Idle);
+
+    let journal = load_journal(&mut test_context, &test_chamber_tulip).await;
+    assert!(journal
+        .calls_of(MockInstruction::DepositBorrowDual)
+        .is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_crank_swap() {
+    let (mut test_context, test_chamber_tulip, test_user) =
+        setup(Some(MockInstruction::SwapTokensRaydiumStats)).await;
+
+    test_chamber_tulip
+        .deposit_chamber(&mut test_context, &test_user, 2500000000, 100000000)
+        .await
+        .unwrap();
+
+    let result = test_chamber_tulip
+        .crank_chamber(&mut test_context, &test_user.wallet)
+        .await;
+    assert!(result.is_err());
+
+    // Interrupted flow stays at the same step, so next crank retries swap
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!(chamber.operation().unwrap(), ChamberOperation::Borrowed);
+}
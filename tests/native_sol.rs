@@ -0,0 +1,113 @@
+mod test_state;
+mod test_utils;
+
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::spl_token::state::Account as TokenAccount,
+};
+use solana_sdk::{program_pack::Pack, signer::Signer};
+use test_state::*;
+use test_utils::*;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    // Fund wallet with 4 SOL, no wrapped SOL account
+    test_user
+        .fund(&mut test_context, &payer, 4000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position_native(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    let user_position = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+    assert_eq!(user_position.base_amount, 2500000000);
+    assert_eq!(user_position.quote_amount, 100000000);
+
+    // Lamports are wrapped into chamber token account
+    let chamber_base_ata = test_context
+        .context
+        .banks_client
+        .get_account(test_chamber_tulip.get_base_ata())
+        .await
+        .unwrap()
+        .unwrap();
+    let chamber_base_ata = TokenAccount::unpack(&chamber_base_ata.data).unwrap();
+    assert_eq!(chamber_base_ata.amount, 2500000000);
+
+    let chamber_state = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!({ chamber_state.total_base_amount }, 2500000000);
+
+    let balance_before = test_context
+        .context
+        .banks_client
+        .get_balance(test_user.wallet.pubkey())
+        .await
+        .unwrap();
+
+    test_user
+        .withdraw_user_position_native(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    let balance_after = test_context
+        .context
+        .banks_client
+        .get_balance(test_user.wallet.pubkey())
+        .await
+        .unwrap();
+
+    // Unwrapped SOL minus transaction fee
+    assert!(balance_after - balance_before > 2499000000);
+    assert!(balance_after - balance_before <= 2500000000);
+
+    let user_position = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+    assert_eq!(user_position.base_amount, 0);
+    assert_eq!(user_position.quote_amount, 0);
+
+    // User never owns wrapped SOL account
+    let user_wsol_ata =
+        get_associated_token_address(&test_user.wallet.pubkey(), &test_utils::wsol_mint::id());
+    assert!(test_context
+        .context
+        .banks_client
+        .get_account(user_wsol_ata)
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,322 @@
+mod test_state;
+mod test_utils;
+
+use cetra_chamber::{
+    error::ChamberError,
+    math::{
+        calculate_base_exposure, calculate_exposure_drift_bps, calculate_pool_price_deviation_bps,
+        calculate_repay_percent,
+    },
+    state::StrategyLeg,
+};
+use solana_sdk::{program_pack::Pack, signer::Signer};
+use test_state::*;
+use test_utils::*;
+use tulipv2_sdk_common::math::decimal::Decimal;
+
+#[test]
+fn exposure_follows_pool_reserves() {
+    // 2.5 SOL and 100 USDC deposited at 40$ per SOL
+    let legs = [
+        StrategyLeg {
+            base_collateral: 625000000,
+            quote_collateral: 25000000,
+            base_debt: 0,
+            quote_debt: 100000000,
+        },
+        StrategyLeg {
+            base_collateral: 1875000000,
+            quote_collateral: 75000000,
+            base_debt: 7500000000,
+            quote_debt: 0,
+        },
+    ];
+
+    // Chamber owns 0.75% of pool holding 1000 SOL
+    let lp_amounts = [2500000, 5000000];
+
+    let (base_liquidity, base_debt) =
+        calculate_base_exposure(&legs, &lp_amounts, 1000000000000, 1000000000).unwrap();
+    assert_eq!(base_liquidity, 7500000000);
+    assert_eq!(base_liquidity, base_debt);
+    assert_eq!(
+        calculate_exposure_drift_bps(base_liquidity, base_debt).unwrap(),
+        0
+    );
+
+    // Price goes up, arbitrage takes base out of the pool
+    let (base_liquidity, base_debt) =
+        calculate_base_exposure(&legs, &lp_amounts, 900000000000, 1000000000).unwrap();
+    assert_eq!(base_liquidity, 6750000000);
+    assert_eq!(
+        calculate_exposure_drift_bps(base_liquidity, base_debt).unwrap(),
+        1111
+    );
+
+    // Second leg holds 4.5 SOL of liquidity against 7.5 SOL of debt, so unwinding
+    // 25% of it repays 0.75 SOL of net debt
+    let leg_base_liquidity = 4500000000;
+    assert_eq!(
+        calculate_repay_percent(
+            base_debt - base_liquidity,
+            leg_base_liquidity,
+            legs[1].base_debt
+        )
+        .unwrap(),
+        25
+    );
+    assert_eq!(
+        calculate_repay_percent(1, leg_base_liquidity, legs[1].base_debt).unwrap(),
+        1
+    );
+    assert_eq!(
+        calculate_repay_percent(base_debt, leg_base_liquidity, legs[1].base_debt).unwrap(),
+        100
+    );
+    assert_eq!(
+        calculate_repay_percent(base_debt, legs[1].base_debt, legs[1].base_debt).unwrap(),
+        0
+    );
+}
+
+#[test]
+fn pool_price_follows_oracles() {
+    let base_price = Decimal::from(40u64);
+    let quote_price = Decimal::from(1u64);
+
+    // 1000 SOL against 40000 USDC at 40$ per SOL
+    assert_eq!(
+        calculate_pool_price_deviation_bps(
+            1000000000000,
+            40000000000,
+            base_price,
+            quote_price,
+            9,
+            6
+        )
+        .unwrap(),
+        0
+    );
+
+    // Base taken out of the pool without arbitrage against oracle
+    assert_eq!(
+        calculate_pool_price_deviation_bps(
+            900000000000,
+            40000000000,
+            base_price,
+            quote_price,
+            9,
+            6
+        )
+        .unwrap(),
+        1000
+    );
+    assert_eq!(
+        calculate_pool_price_deviation_bps(
+            1000000000000,
+            36000000000,
+            base_price,
+            quote_price,
+            9,
+            6
+        )
+        .unwrap(),
+        1111
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_threshold_not_reached() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.rebalance_threshold_bps = 100;
+
+    let pyth_price_account = test_context
+        .get_account(&test_chamber_tulip.farm_config.coin_price_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let pyth_price =
+        tulipv2_sdk_common::pyth::load::<tulipv2_sdk_common::pyth::Price>(&pyth_price_account.data)
+            .unwrap();
+
+    test_context
+        .context
+        .warp_to_slot(pyth_price.valid_slot)
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 2500000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .deposit_chamber(&mut test_context, &test_user, 2500000000, 100000000)
+        .await
+        .unwrap();
+
+    for _ in 0..3 {
+        test_chamber_tulip
+            .crank_chamber(&mut test_context, &payer)
+            .await
+            .unwrap();
+    }
+
+    // Freshly deployed position is delta-neutral
+    let result = test_chamber_tulip
+        .rebalance_chamber(&mut test_context, &payer, false)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_zero_threshold() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let result = test_chamber_tulip
+        .rebalance_chamber(&mut test_context, &payer, false)
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::InvalidConfig.into())
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_not_keeper() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.rebalance_threshold_bps = 100;
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    assert_ne!(test_user.wallet.pubkey(), payer.pubkey());
+
+    let result = test_chamber_tulip
+        .rebalance_chamber(&mut test_context, &test_user.wallet, false)
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::InvalidKeeper.into())
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_pool_price_deviation() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.rebalance_threshold_bps = 100;
+
+    let pyth_price_account = test_context
+        .get_account(&test_chamber_tulip.farm_config.coin_price_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let pyth_price =
+        tulipv2_sdk_common::pyth::load::<tulipv2_sdk_common::pyth::Price>(&pyth_price_account.data)
+            .unwrap();
+
+    test_context
+        .context
+        .warp_to_slot(pyth_price.valid_slot)
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    // Half of base reserve is drained, so pool prices base twice as high as oracle
+    let pool_coin_token_account = test_chamber_tulip.farm_config.amm_coin_account;
+    let mut account = test_context
+        .get_account(&pool_coin_token_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut token_account =
+        anchor_spl::token::spl_token::state::Account::unpack(&account.data).unwrap();
+    token_account.amount /= 2;
+    token_account.pack_into_slice(&mut account.data);
+    test_context
+        .context
+        .set_account(&pool_coin_token_account, &account.into());
+
+    let result = test_chamber_tulip
+        .rebalance_chamber(&mut test_context, &payer, false)
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::PoolPriceDeviation.into())
+    );
+}
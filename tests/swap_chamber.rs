@@ -0,0 +1,390 @@
+mod test_state;
+mod test_utils;
+
+use anchor_spl::token::spl_token::{
+    self,
+    state::{Account as TokenAccount, AccountState},
+};
+use cetra_chamber::{error::ChamberError, state::ChamberConfig};
+use solana_sdk::{account::Account, program_pack::Pack, pubkey::Pubkey, signer::Signer};
+use test_state::*;
+use test_utils::*;
+use tulipv2_sdk_common::config::RAYDIUM_LIQUIDITY_V4;
+
+/// Raydium AMM v4 `SwapBaseIn` data, stands for aggregator route data.
+fn raydium_swap_data(amount_in: u64) -> Vec<u8> {
+    let mut data = vec![9];
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data
+}
+
+async fn setup() -> (TestContext, TestChamberTulip) {
+    setup_with_keeper(None).await
+}
+
+/// Chamber with 100$ of users idle quote, `keeper` defaults to admin.
+async fn setup_with_keeper(keeper: Option<Pubkey>) -> (TestContext, TestChamberTulip) {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.swap_program = RAYDIUM_LIQUIDITY_V4;
+
+    test_chamber_tulip
+        .initialize_chamber_with_keeper(
+            &mut test_context,
+            &payer,
+            &keeper.unwrap_or(payer.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    // USDC with 100$, no SOL
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position(&mut test_context, &test_chamber_tulip, 0, 100000000)
+        .await
+        .unwrap();
+
+    // 20$ on top of users idle balances, e.g. swapped rewards
+    test_chamber_tulip
+        .add_quote_ata_balance(&mut test_context, 20000000)
+        .await
+        .unwrap();
+
+    (test_context, test_chamber_tulip)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success() {
+    let (mut test_context, test_chamber_tulip) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    let base_ata = test_chamber_tulip.get_base_ata();
+    let quote_ata = test_chamber_tulip.get_quote_ata();
+
+    test_chamber_tulip
+        .swap_chamber(
+            &mut test_context,
+            &payer,
+            quote_ata,
+            base_ata,
+            RAYDIUM_LIQUIDITY_V4,
+            test_chamber_tulip.get_raydium_swap_route(quote_ata, base_ata),
+            10000000,
+            1,
+            raydium_swap_data(10000000),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_chamber_tulip
+            .get_token_amount(&mut test_context, quote_ata)
+            .await
+            .unwrap(),
+        110000000
+    );
+    assert!(
+        test_chamber_tulip
+            .get_token_amount(&mut test_context, base_ata)
+            .await
+            .unwrap()
+            > 0
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_minimum_amount_out() {
+    let (mut test_context, test_chamber_tulip) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    let base_ata = test_chamber_tulip.get_base_ata();
+    let quote_ata = test_chamber_tulip.get_quote_ata();
+
+    // 10$ never buys 100 SOL, even though route itself accepts any output
+    assert!(test_chamber_tulip
+        .swap_chamber(
+            &mut test_context,
+            &payer,
+            quote_ata,
+            base_ata,
+            RAYDIUM_LIQUIDITY_V4,
+            test_chamber_tulip.get_raydium_swap_route(quote_ata, base_ata),
+            10000000,
+            100000000000,
+            raydium_swap_data(10000000),
+        )
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_amount_in_exceeded() {
+    let (mut test_context, test_chamber_tulip) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    let base_ata = test_chamber_tulip.get_base_ata();
+    let quote_ata = test_chamber_tulip.get_quote_ata();
+
+    // Route spends more than declared
+    assert!(test_chamber_tulip
+        .swap_chamber(
+            &mut test_context,
+            &payer,
+            quote_ata,
+            base_ata,
+            RAYDIUM_LIQUIDITY_V4,
+            test_chamber_tulip.get_raydium_swap_route(quote_ata, base_ata),
+            10000000,
+            1,
+            raydium_swap_data(20000000),
+        )
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_swap_program() {
+    let (mut test_context, test_chamber_tulip) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    let base_ata = test_chamber_tulip.get_base_ata();
+    let quote_ata = test_chamber_tulip.get_quote_ata();
+
+    assert!(test_chamber_tulip
+        .swap_chamber(
+            &mut test_context,
+            &payer,
+            quote_ata,
+            base_ata,
+            serum_program::id(),
+            test_chamber_tulip.get_raydium_swap_route(quote_ata, base_ata),
+            10000000,
+            1,
+            raydium_swap_data(10000000),
+        )
+        .await
+        .is_err());
+
+    // Only allowlisted programs can be configured
+    let result = test_chamber_tulip
+        .update_chamber_config(
+            &mut test_context,
+            &payer,
+            ChamberConfig {
+                swap_program: serum_program::id(),
+                ..test_chamber_tulip.config
+            },
+        )
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::InvalidSwapProgram.into())
+    );
+
+    // Swaps are disabled with default config
+    test_chamber_tulip
+        .update_chamber_config(&mut test_context, &payer, ChamberConfig::default())
+        .await
+        .unwrap();
+
+    assert!(test_chamber_tulip
+        .swap_chamber(
+            &mut test_context,
+            &payer,
+            quote_ata,
+            base_ata,
+            Pubkey::default(),
+            vec![],
+            10000000,
+            1,
+            vec![],
+        )
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_reserved_balance_spent() {
+    let (mut test_context, test_chamber_tulip) = setup().await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    let base_ata = test_chamber_tulip.get_base_ata();
+    let quote_ata = test_chamber_tulip.get_quote_ata();
+
+    // 30$ eats 10$ of users idle quote balance
+    let result = test_chamber_tulip
+        .swap_chamber(
+            &mut test_context,
+            &payer,
+            quote_ata,
+            base_ata,
+            RAYDIUM_LIQUIDITY_V4,
+            test_chamber_tulip.get_raydium_swap_route(quote_ata, base_ata),
+            30000000,
+            1,
+            raydium_swap_data(30000000),
+        )
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::ReservedBalanceSpent.into())
+    );
+
+    assert_eq!(
+        test_chamber_tulip
+            .get_token_amount(&mut test_context, quote_ata)
+            .await
+            .unwrap(),
+        120000000
+    );
+}
+
+/// Creates token account of chamber authority besides chamber base and quote token accounts,
+/// which stands for reward token account.
+async fn create_reward_account(
+    test_context: &mut TestContext,
+    test_chamber_tulip: &TestChamberTulip,
+    amount: u64,
+) -> Pubkey {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint: test_utils::usdc_mint::id(),
+        owner: test_chamber_tulip.authority,
+        amount,
+        state: AccountState::Initialized,
+        ..TokenAccount::default()
+    }
+    .pack_into_slice(&mut data);
+
+    let reward_account = Pubkey::new_unique();
+    let account = Account {
+        lamports: test_context.get_rent().await.minimum_balance(data.len()),
+        data,
+        owner: spl_token::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    test_context
+        .context
+        .set_account(&reward_account, &account.into());
+
+    reward_account
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_keeper_harvest() {
+    let keeper = TestUser::new();
+    let (mut test_context, test_chamber_tulip) =
+        setup_with_keeper(Some(keeper.wallet.pubkey())).await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    keeper
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    let base_ata = test_chamber_tulip.get_base_ata();
+    let reward_account =
+        create_reward_account(&mut test_context, &test_chamber_tulip, 10000000).await;
+
+    test_chamber_tulip
+        .swap_chamber(
+            &mut test_context,
+            &keeper.wallet,
+            reward_account,
+            base_ata,
+            RAYDIUM_LIQUIDITY_V4,
+            test_chamber_tulip.get_raydium_swap_route(reward_account, base_ata),
+            10000000,
+            1,
+            raydium_swap_data(10000000),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_chamber_tulip
+            .get_token_amount(&mut test_context, reward_account)
+            .await
+            .unwrap(),
+        0
+    );
+    assert!(
+        test_chamber_tulip
+            .get_token_amount(&mut test_context, base_ata)
+            .await
+            .unwrap()
+            > 0
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_keeper_swaps_strategy_tokens() {
+    let keeper = TestUser::new();
+    let (mut test_context, test_chamber_tulip) =
+        setup_with_keeper(Some(keeper.wallet.pubkey())).await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    keeper
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    let base_ata = test_chamber_tulip.get_base_ata();
+    let quote_ata = test_chamber_tulip.get_quote_ata();
+    let reward_account =
+        create_reward_account(&mut test_context, &test_chamber_tulip, 10000000).await;
+
+    // Free quote is admin business, even though it doesn't belong to users
+    let result = test_chamber_tulip
+        .swap_chamber(
+            &mut test_context,
+            &keeper.wallet,
+            quote_ata,
+            base_ata,
+            RAYDIUM_LIQUIDITY_V4,
+            test_chamber_tulip.get_raydium_swap_route(quote_ata, base_ata),
+            10000000,
+            1,
+            raydium_swap_data(10000000),
+        )
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::InvalidKeeper.into())
+    );
+
+    // Rewards are converted into chamber tokens only
+    let other_account = create_reward_account(&mut test_context, &test_chamber_tulip, 0).await;
+    let result = test_chamber_tulip
+        .swap_chamber(
+            &mut test_context,
+            &keeper.wallet,
+            reward_account,
+            other_account,
+            RAYDIUM_LIQUIDITY_V4,
+            test_chamber_tulip.get_raydium_swap_route(reward_account, other_account),
+            10000000,
+            1,
+            raydium_swap_data(10000000),
+        )
+        .await;
+    assert_eq!(
+        transaction_error_code(result),
+        Some(ChamberError::InvalidKeeper.into())
+    );
+}
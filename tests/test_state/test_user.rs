@@ -1,5 +1,6 @@
 use super::TestChamberTulip;
-use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use crate::test_utils::TestContext;
+use anchor_lang::AccountDeserialize;
 use anchor_spl::{
     associated_token::get_associated_token_address,
     token::spl_token::{
@@ -8,13 +9,14 @@ use anchor_spl::{
     },
 };
 use cetra_chamber::{
-    accounts as cetra_chamber_accounts, instruction as cetra_chamber_instruction,
-    state::UserPosition, utils::derive_user_position,
+    state::{TokenSide, UserPosition},
+    utils::derive_user_position,
 };
-use cetra_program_test::{solana_program_test::*, TestContext};
+use cetra_chamber_client::instruction;
+use solana_program_test::*;
 use solana_sdk::{
     account::Account, instruction::Instruction, program_option::COption, program_pack::Pack,
-    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction, system_program,
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
     transaction::Transaction, transport,
 };
 
@@ -93,6 +95,22 @@ impl TestUser {
         Ok(())
     }
 
+    /// Sends `instruction` signed by user wallet, e.g. built by client and modified by test.
+    pub async fn process(
+        &self,
+        test_context: &mut TestContext,
+        instruction: Instruction,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.wallet.pubkey()),
+            &[&self.wallet],
+            test_context.context.last_blockhash,
+        );
+
+        test_context.process_transaction(tx).await
+    }
+
     pub async fn initialize_user_position(
         &self,
         test_context: &mut TestContext,
@@ -100,50 +118,189 @@ impl TestUser {
         base_amount: u64,
         quote_amount: u64,
     ) -> Result<(), BanksClientError> {
-        let (user_position, bump) =
-            derive_user_position(&self.wallet.pubkey(), &test_chamber_tulip.pubkey);
+        let ix = instruction::initialize_user_position_ix(
+            test_chamber_tulip,
+            &self.wallet.pubkey(),
+            base_amount,
+            quote_amount,
+        );
 
-        let user_base_ata = get_associated_token_address(
+        Ok(self.process(test_context, ix).await.unwrap())
+    }
+
+    pub async fn initialize_user_position_single_sided(
+        &self,
+        test_context: &mut TestContext,
+        test_chamber_tulip: &TestChamberTulip,
+        side: TokenSide,
+        amount: u64,
+        minimum_amount_out: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = instruction::initialize_user_position_single_sided_ix(
+            test_chamber_tulip,
+            &self.wallet.pubkey(),
+            side,
+            amount,
+            minimum_amount_out,
+        );
+
+        self.process(test_context, ix).await
+    }
+
+    pub async fn initialize_user_position_native(
+        &self,
+        test_context: &mut TestContext,
+        test_chamber_tulip: &TestChamberTulip,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = instruction::initialize_user_position_native_ix(
+            test_chamber_tulip,
             &self.wallet.pubkey(),
-            &test_chamber_tulip.farm_config.base_token_mint,
+            base_amount,
+            quote_amount,
         );
-        let user_quote_ata = get_associated_token_address(
+
+        self.process(test_context, ix).await
+    }
+
+    pub async fn withdraw_user_position(
+        &self,
+        test_context: &mut TestContext,
+        test_chamber_tulip: &TestChamberTulip,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = instruction::withdraw_user_position_ix(
+            test_chamber_tulip,
             &self.wallet.pubkey(),
-            &test_chamber_tulip.farm_config.quote_token_mint,
+            base_amount,
+            quote_amount,
         );
 
-        let accounts = cetra_chamber_accounts::InitializeUserPosition {
-            chamber: test_chamber_tulip.pubkey,
-            user_position,
-            user_base_ata,
-            user_quote_ata,
-            chamber_base_ata: test_chamber_tulip.get_base_ata(),
-            chamber_quote_ata: test_chamber_tulip.get_quote_ata(),
-            payer: self.wallet.pubkey(),
-            token_program: spl_token::id(),
-            system_program: system_program::id(),
-        }
-        .to_account_metas(None);
+        self.process(test_context, ix).await
+    }
 
-        let data = cetra_chamber_instruction::InitializeUserPosition {
-            bump,
+    pub async fn withdraw_user_position_native(
+        &self,
+        test_context: &mut TestContext,
+        test_chamber_tulip: &TestChamberTulip,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = instruction::withdraw_user_position_native_ix(
+            test_chamber_tulip,
+            &self.wallet.pubkey(),
             base_amount,
             quote_amount,
-        }
-        .data();
+        );
 
-        let tx = Transaction::new_signed_with_payer(
-            &[Instruction {
-                program_id: cetra_chamber::id(),
-                data,
-                accounts,
-            }],
-            Some(&self.wallet.pubkey()),
-            &[&self.wallet],
-            test_context.context.last_blockhash,
+        self.process(test_context, ix).await
+    }
+
+    pub async fn queue_deposit(
+        &self,
+        test_context: &mut TestContext,
+        test_chamber_tulip: &TestChamberTulip,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let chamber_state = test_chamber_tulip.load(test_context).await?;
+
+        let ix = instruction::queue_deposit_ix(
+            test_chamber_tulip,
+            &self.wallet.pubkey(),
+            chamber_state.epoch,
+            base_amount,
+            quote_amount,
+        );
+
+        self.process(test_context, ix).await
+    }
+
+    pub async fn request_withdrawal(
+        &self,
+        test_context: &mut TestContext,
+        test_chamber_tulip: &TestChamberTulip,
+        shares: u64,
+    ) -> Result<(), BanksClientError> {
+        let chamber_state = test_chamber_tulip.load(test_context).await?;
+
+        let ix = instruction::request_withdrawal_ix(
+            test_chamber_tulip,
+            &self.wallet.pubkey(),
+            chamber_state.epoch,
+            shares,
+        );
+
+        self.process(test_context, ix).await
+    }
+
+    pub async fn cancel_withdrawal(
+        &self,
+        test_context: &mut TestContext,
+        test_chamber_tulip: &TestChamberTulip,
+    ) -> Result<(), BanksClientError> {
+        let user_position_state = self
+            .load_user_position(test_context, test_chamber_tulip)
+            .await?;
+
+        let ix = instruction::cancel_withdrawal_ix(
+            test_chamber_tulip,
+            &self.wallet.pubkey(),
+            user_position_state.withdrawal_epoch,
         );
 
-        Ok(test_context.process_transaction(tx).await.unwrap())
+        self.process(test_context, ix).await
+    }
+
+    pub async fn claim_deposit(
+        &self,
+        test_context: &mut TestContext,
+        test_chamber_tulip: &TestChamberTulip,
+    ) -> Result<(), BanksClientError> {
+        let user_position_state = self
+            .load_user_position(test_context, test_chamber_tulip)
+            .await?;
+
+        let ix = instruction::claim_deposit_ix(
+            test_chamber_tulip,
+            &self.wallet.pubkey(),
+            user_position_state.queued_epoch,
+        );
+
+        self.process(test_context, ix).await
+    }
+
+    pub async fn claim_withdrawal(
+        &self,
+        test_context: &mut TestContext,
+        test_chamber_tulip: &TestChamberTulip,
+    ) -> Result<(), BanksClientError> {
+        let user_position_state = self
+            .load_user_position(test_context, test_chamber_tulip)
+            .await?;
+
+        let ix = instruction::claim_withdrawal_ix(
+            test_chamber_tulip,
+            &self.wallet.pubkey(),
+            user_position_state.withdrawal_epoch,
+        );
+
+        self.process(test_context, ix).await
+    }
+
+    pub async fn migrate_user_position(
+        &self,
+        test_context: &mut TestContext,
+        test_chamber_tulip: &TestChamberTulip,
+    ) -> Result<(), BanksClientError> {
+        let (user_position, _) =
+            derive_user_position(&self.wallet.pubkey(), &test_chamber_tulip.pubkey);
+
+        let ix = instruction::migrate_user_position_ix(&user_position, &self.wallet.pubkey());
+
+        self.process(test_context, ix).await
     }
 
     pub async fn load_user_position(
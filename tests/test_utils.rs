@@ -1,4 +1,21 @@
-use solana_sdk::signature::Keypair;
+use anchor_lang::AccountSerialize;
+use serde::{Deserialize, Serialize};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    bpf_loader_upgradeable,
+    clock::Clock,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use std::{fs, path::PathBuf, str::FromStr};
+use tulipv2_sdk_common::math::decimal::Decimal;
+use tulipv2_sdk_levfarm::accounts::obligation_account::ObligationAccount;
 
 pub mod usdc_mint {
     use anchor_lang::declare_id;
@@ -12,12 +29,6 @@ pub mod serum_program {
     declare_id!("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin");
 }
 
-pub mod raydium_raysrm_farm {
-    use anchor_lang::declare_id;
-
-    declare_id!("GUzaohfNuFbBqQTnPgPSNciv3aUvriXYjQduRE3ZkqFw");
-}
-
 pub mod usdt_mint {
     use anchor_lang::declare_id;
 
@@ -28,12 +39,294 @@ pub mod wsol_mint {
     pub use anchor_spl::token::spl_token::native_mint::id;
 }
 
-pub mod tulip_mint {
-    use anchor_lang::declare_id;
+/// Account snapshot as stored in `tests/fixtures/account_<pubkey>.json`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: [u8; 32],
+    pub executable: bool,
+    pub rent_epoch: u64,
+}
+
+impl From<FixtureAccount> for Account {
+    fn from(fixture: FixtureAccount) -> Self {
+        Account {
+            lamports: fixture.lamports,
+            data: fixture.data,
+            owner: Pubkey::new_from_array(fixture.owner),
+            executable: fixture.executable,
+            rent_epoch: fixture.rent_epoch,
+        }
+    }
+}
+
+impl From<Account> for FixtureAccount {
+    fn from(account: Account) -> Self {
+        FixtureAccount {
+            lamports: account.lamports,
+            data: account.data,
+            owner: account.owner.to_bytes(),
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+        }
+    }
+}
+
+/// Upgradeable program snapshot as stored in `tests/fixtures/program_<pubkey>.json`,
+/// `buffer` is the raw programdata account data.
+#[derive(Serialize, Deserialize)]
+pub struct FixtureProgram {
+    pub account: FixtureAccount,
+    pub buffer_pubkey: [u8; 32],
+    pub buffer: Vec<u8>,
+}
+
+/// Resolution of `TestContext::measure_compute_units`.
+pub const COMPUTE_UNITS_STEP: u32 = 5000;
+
+pub struct TestContext {
+    pub context: ProgramTestContext,
+}
+
+impl TestContext {
+    pub async fn process_transaction(&mut self, tx: Transaction) -> Result<(), BanksClientError> {
+        self.context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn get_account(
+        &mut self,
+        pubkey: &Pubkey,
+    ) -> Result<Option<Account>, BanksClientError> {
+        self.context.banks_client.get_account(*pubkey).await
+    }
+
+    pub async fn get_rent(&mut self) -> Rent {
+        self.context.banks_client.get_rent().await.unwrap()
+    }
+
+    /// Process `instruction` with the smallest compute budget, which lets it succeed,
+    /// probed in `COMPUTE_UNITS_STEP` increments up to `max_units`.
+    ///
+    /// Only attempts, which ran out of budget, are retried, any other failure is returned.
+    /// Failed attempts don't change state, so the instruction is executed exactly once.
+    pub async fn measure_compute_units(
+        &mut self,
+        payer: &Keypair,
+        instruction: Instruction,
+        max_units: u32,
+    ) -> Result<u32, BanksClientError> {
+        let mut units = COMPUTE_UNITS_STEP;
+
+        loop {
+            let tx = Transaction::new_signed_with_payer(
+                &[
+                    ComputeBudgetInstruction::request_units(units, 0),
+                    instruction.clone(),
+                ],
+                Some(&payer.pubkey()),
+                &[payer],
+                self.context.last_blockhash,
+            );
+
+            match self.process_transaction(tx).await {
+                Ok(()) => return Ok(units),
+                Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+                    _,
+                    InstructionError::ComputationalBudgetExceeded,
+                ))) if units < max_units => units = (units + COMPUTE_UNITS_STEP).min(max_units),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+pub fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+}
+
+/// Adds every account and program dumped under `tests/fixtures` to `program_test`,
+/// except `excluded` ones, which are replaced by test programs.
+pub fn load_fixtures(program_test: &mut ProgramTest, excluded: &[Pubkey]) {
+    let rent = Rent::default();
+
+    for entry in fs::read_dir(fixtures_dir()).unwrap() {
+        let path = entry.unwrap().path();
+        let file_stem = path.file_stem().unwrap().to_str().unwrap();
+        let data = fs::read(&path).unwrap();
+
+        if let Some(pubkey) = file_stem.strip_prefix("account_") {
+            let pubkey = Pubkey::from_str(pubkey).unwrap();
+            if excluded.contains(&pubkey) {
+                continue;
+            }
+
+            let fixture: FixtureAccount = serde_json::from_slice(&data).unwrap();
 
-    declare_id!("TuLipcqtGVXP9XR62wM8WWCm6a9vhLs7T1uoWBk6FDs");
+            program_test.add_account(pubkey, fixture.into());
+        } else if let Some(pubkey) = file_stem.strip_prefix("program_") {
+            let pubkey = Pubkey::from_str(pubkey).unwrap();
+            if excluded.contains(&pubkey) {
+                continue;
+            }
+
+            let fixture: FixtureProgram = serde_json::from_slice(&data).unwrap();
+            let program_account: Account = fixture.account.into();
+
+            program_test.add_account(
+                Pubkey::new_from_array(fixture.buffer_pubkey),
+                Account {
+                    lamports: rent.minimum_balance(fixture.buffer.len()),
+                    data: fixture.buffer,
+                    owner: program_account.owner,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            );
+            program_test.add_account(pubkey, program_account);
+        }
+    }
+}
+
+/// Starts a bank with the chamber program and all offline fixtures, no RPC access needed.
+pub async fn start_test_context() -> TestContext {
+    let mut program_test = ProgramTest::default();
+
+    program_test.add_program("cetra_chamber", cetra_chamber::id(), None);
+    load_fixtures(&mut program_test, &[]);
+
+    TestContext {
+        context: program_test.start_with_context().await,
+    }
+}
+
+/// Same as `start_test_context`, but chamber program is deployed from `cetra_chamber.so` as
+/// upgradeable program, so instructions gated by `upgrade_authority` can be tested.
+pub async fn start_upgradeable_test_context(upgrade_authority: &Pubkey) -> TestContext {
+    let mut program_test = ProgramTest::default();
+    let rent = Rent::default();
+
+    let out_dir = std::env::var("BPF_OUT_DIR").unwrap_or_else(|_| "target/deploy".to_string());
+    let elf = fs::read(PathBuf::from(out_dir).join("cetra_chamber.so")).unwrap();
+
+    let (program_data, _) = Pubkey::find_program_address(
+        &[cetra_chamber::id().as_ref()],
+        &bpf_loader_upgradeable::id(),
+    );
+
+    // `UpgradeableLoaderState::Program` stores programdata address after 4 bytes of enum tag
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(program_data.as_ref());
+    program_test.add_account(
+        cetra_chamber::id(),
+        Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: bpf_loader_upgradeable::id(),
+            executable: true,
+            rent_epoch: 0,
+        },
+    );
+
+    // `UpgradeableLoaderState::ProgramData` stores slot and authority before program bytes
+    let mut data = 3u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(upgrade_authority.as_ref());
+    data.extend_from_slice(&elf);
+    program_test.add_account(
+        program_data,
+        Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    load_fixtures(&mut program_test, &[]);
+
+    TestContext {
+        context: program_test.start_with_context().await,
+    }
+}
+
+/// Same as `start_test_context`, but Tulip levfarm program is replaced by `mock_levfarm`.
+pub async fn start_mock_levfarm_test_context() -> TestContext {
+    let mut program_test = ProgramTest::default();
+
+    program_test.add_program("cetra_chamber", cetra_chamber::id(), None);
+    program_test.add_program(
+        "mock_levfarm",
+        mock_levfarm::id(),
+        processor!(mock_levfarm::process_instruction),
+    );
+    load_fixtures(&mut program_test, &[mock_levfarm::id()]);
+
+    TestContext {
+        context: program_test.start_with_context().await,
+    }
+}
+
+/// Custom error code of failed instruction, e.g. `ChamberError` or Anchor `ErrorCode`.
+pub fn transaction_error_code(result: Result<(), BanksClientError>) -> Option<u32> {
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        ))) => Some(code),
+        _ => None,
+    }
 }
 
 pub fn clone_keypair(keypair: &Keypair) -> Keypair {
     Keypair::from_bytes(&keypair.to_bytes()).unwrap()
 }
+
+/// Creates levfarm obligation with provided values in dollars at random address, which is
+/// not derived for any chamber.
+pub async fn set_forged_obligation(
+    test_context: &mut TestContext,
+    deposited_value: u64,
+    borrowed_value: u64,
+) -> Pubkey {
+    let mut data = vec![];
+    ObligationAccount {
+        deposited_value: Decimal::from(deposited_value),
+        borrowed_value: Decimal::from(borrowed_value),
+        ..Default::default()
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+
+    let obligation = Pubkey::new_unique();
+    let account = Account {
+        lamports: test_context.get_rent().await.minimum_balance(data.len()),
+        data,
+        owner: tulipv2_sdk_levfarm::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    test_context
+        .context
+        .set_account(&obligation, &account.into());
+
+    obligation
+}
+
+pub async fn warp_clock(test_context: &mut TestContext, seconds: i64) {
+    let mut clock: Clock = test_context
+        .context
+        .banks_client
+        .get_sysvar()
+        .await
+        .unwrap();
+
+    clock.unix_timestamp += seconds;
+
+    test_context.context.set_sysvar(&clock);
+}
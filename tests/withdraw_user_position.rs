@@ -0,0 +1,115 @@
+mod test_state;
+mod test_utils;
+
+use cetra_chamber::state::{ChamberConfig, PositionStatus};
+use test_state::*;
+use test_utils::*;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.entry_fee_bps = 50;
+    test_chamber_tulip.config.exit_fee_bps = 100;
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 2000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2000000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    // 0.5% entry fee
+    let user_position = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+    assert_eq!(user_position.base_amount, 1990000000);
+    assert_eq!(user_position.quote_amount, 99500000);
+
+    test_user
+        .withdraw_user_position(&mut test_context, &test_chamber_tulip, 1990000000, 99500000)
+        .await
+        .unwrap();
+
+    let user_position = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+    assert_eq!(user_position.base_amount, 0);
+    assert_eq!(user_position.quote_amount, 0);
+    assert_eq!(user_position.status, PositionStatus::Closed);
+    assert!(user_position.last_withdraw_ts > 0);
+
+    // Closed position can't be used anymore
+    let result = test_user
+        .withdraw_user_position(&mut test_context, &test_chamber_tulip, 0, 0)
+        .await;
+    assert!(result.is_err());
+
+    // 1% exit fee
+    let (user_quote_ata, _) = test_user
+        .get_ata(&mut test_context, &test_utils::usdc_mint::id())
+        .await
+        .unwrap();
+    assert_eq!(user_quote_ata.amount, 98505000);
+
+    let (treasury_quote_ata, _) = test_chamber_tulip
+        .treasury
+        .get_ata(&mut test_context, &test_utils::usdc_mint::id())
+        .await
+        .unwrap();
+    assert_eq!(treasury_quote_ata.amount, 500000 + 995000);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_fee_above_maximum() {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let result = test_chamber_tulip
+        .update_chamber_config(
+            &mut test_context,
+            &payer,
+            ChamberConfig {
+                exit_fee_bps: 10000,
+                ..ChamberConfig::default()
+            },
+        )
+        .await;
+    assert!(result.is_err());
+}
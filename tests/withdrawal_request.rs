@@ -0,0 +1,244 @@
+mod test_state;
+mod test_utils;
+
+use cetra_chamber::{math::calculate_fee, state::PositionStatus};
+use test_state::*;
+use test_utils::*;
+
+/// Deposit through the first epoch, so user holds shares.
+async fn setup_user_with_shares(
+    withdrawal_cooldown: u32,
+    exit_fee_bps: u16,
+) -> (TestContext, TestChamberTulip, TestUser) {
+    let mut test_context = start_test_context().await;
+
+    let payer = clone_keypair(&test_context.context.payer);
+    let mut test_chamber_tulip = TestChamberTulip::new_sol_usdc_raydium(0);
+    test_chamber_tulip.config.withdrawal_cooldown = withdrawal_cooldown;
+    test_chamber_tulip.config.exit_fee_bps = exit_fee_bps;
+
+    let pyth_price_account = test_context
+        .get_account(&test_chamber_tulip.farm_config.coin_price_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let pyth_price =
+        tulipv2_sdk_common::pyth::load::<tulipv2_sdk_common::pyth::Price>(&pyth_price_account.data)
+            .unwrap();
+
+    test_context
+        .context
+        .warp_to_slot(pyth_price.valid_slot)
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .initialize_chamber_strategy(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let test_user = TestUser::new();
+
+    test_user
+        .fund(&mut test_context, &payer, 1000000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::wsol_mint::id(), 2500000000)
+        .await
+        .unwrap();
+
+    test_user
+        .create_ata(&mut test_context, &test_utils::usdc_mint::id(), 100000000)
+        .await
+        .unwrap();
+
+    test_user
+        .initialize_user_position(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    test_user
+        .queue_deposit(
+            &mut test_context,
+            &test_chamber_tulip,
+            2500000000,
+            100000000,
+        )
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .process_chamber_epoch(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    test_user
+        .claim_deposit(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+
+    (test_context, test_chamber_tulip, test_user)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_cancel() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup_user_with_shares(0, 0).await;
+
+    let shares = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap()
+        .shares;
+    assert!(shares > 0);
+
+    test_user
+        .request_withdrawal(&mut test_context, &test_chamber_tulip, shares / 2)
+        .await
+        .unwrap();
+
+    let user_position = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+    assert_eq!(user_position.shares, shares - shares / 2);
+    assert_eq!(user_position.withdrawal_shares, shares / 2);
+    assert_eq!(user_position.withdrawal_epoch, 1);
+    assert_eq!(user_position.status, PositionStatus::WithdrawRequested);
+
+    let epoch = test_chamber_tulip
+        .load_epoch(&mut test_context, 1)
+        .await
+        .unwrap();
+    assert_eq!(epoch.pending_withdraw_shares, shares / 2);
+
+    test_user
+        .cancel_withdrawal(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+
+    let user_position = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+    assert_eq!(user_position.shares, shares);
+    assert_eq!(user_position.withdrawal_shares, 0);
+    assert_eq!(user_position.status, PositionStatus::Deployed);
+
+    let epoch = test_chamber_tulip
+        .load_epoch(&mut test_context, 1)
+        .await
+        .unwrap();
+    assert_eq!(epoch.pending_withdraw_shares, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failure_claim_before_cooldown() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup_user_with_shares(86400, 0).await;
+
+    let shares = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap()
+        .shares;
+
+    test_user
+        .request_withdrawal(&mut test_context, &test_chamber_tulip, shares)
+        .await
+        .unwrap();
+
+    assert!(test_user
+        .claim_withdrawal(&mut test_context, &test_chamber_tulip)
+        .await
+        .is_err());
+
+    // Cooldown passed, but keeper has not processed the request yet
+    warp_clock(&mut test_context, 86400).await;
+
+    assert!(test_user
+        .claim_withdrawal(&mut test_context, &test_chamber_tulip)
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn success_claim_exit_fee() {
+    let (mut test_context, test_chamber_tulip, test_user) = setup_user_with_shares(0, 100).await;
+    let payer = clone_keypair(&test_context.context.payer);
+
+    let shares = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap()
+        .shares;
+
+    test_user
+        .request_withdrawal(&mut test_context, &test_chamber_tulip, shares / 2)
+        .await
+        .unwrap();
+
+    // Idle quote covers the withdrawal, so keeper doesn't need to unwind liquidity
+    test_chamber_tulip
+        .add_quote_ata_balance(&mut test_context, 1000000000)
+        .await
+        .unwrap();
+
+    test_chamber_tulip
+        .process_chamber_epoch(&mut test_context, &payer)
+        .await
+        .unwrap();
+
+    let epoch = test_chamber_tulip
+        .load_epoch(&mut test_context, 1)
+        .await
+        .unwrap();
+    let quote_amount = epoch.withdraw_quote_amount;
+    assert!(quote_amount > 0);
+
+    let quote_fee = calculate_fee(quote_amount, 100).unwrap();
+    assert!(quote_fee > 0);
+
+    let (user_quote_ata, _) = test_user
+        .get_ata(&mut test_context, &test_utils::usdc_mint::id())
+        .await
+        .unwrap();
+    let accrued_quote_fee = test_chamber_tulip
+        .load(&mut test_context)
+        .await
+        .unwrap()
+        .accrued_quote_fee;
+
+    test_user
+        .claim_withdrawal(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+
+    let (user_quote_ata_after, _) = test_user
+        .get_ata(&mut test_context, &test_utils::usdc_mint::id())
+        .await
+        .unwrap();
+    assert_eq!(
+        user_quote_ata_after.amount - user_quote_ata.amount,
+        quote_amount - quote_fee
+    );
+
+    let chamber = test_chamber_tulip.load(&mut test_context).await.unwrap();
+    assert_eq!({ chamber.accrued_quote_fee }, accrued_quote_fee + quote_fee);
+    assert_eq!({ chamber.claimable_quote_amount }, 0);
+
+    let user_position = test_user
+        .load_user_position(&mut test_context, &test_chamber_tulip)
+        .await
+        .unwrap();
+    assert_eq!(user_position.withdrawal_shares, 0);
+}